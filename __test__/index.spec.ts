@@ -1,6 +1,161 @@
+import { execFileSync, spawn } from 'node:child_process'
+import { fileURLToPath } from 'node:url'
+
 import test from 'ava'
 
-import { nice, getCurrentProcessPriority } from '../index.js'
+import {
+  nice,
+  adjustPriority,
+  beginActivity,
+  boostFor,
+  canRaisePriority,
+  canSetPriority,
+  clearTimerResolution,
+  enableAudioThreadPriority,
+  enableIncreaseBasePriorityPrivilege,
+  endActivity,
+  enterBackgroundMode,
+  exitBackgroundMode,
+  formatCpuList,
+  getAllowedCpus,
+  getCgroupCpuMax,
+  getCgroupCpuWeight,
+  getCgroupIdle,
+  getContextSwitches,
+  getCoreClasses,
+  getCpuAffinity,
+  getCpuPressure,
+  getCpuTopology,
+  getCurrentCpu,
+  getCurrentProcessPriority,
+  getDarwinIoPolicy,
+  getEffectiveCpuLimit,
+  getEffectivePriorityRange,
+  getIoPriority,
+  getLatencyNice,
+  getMetricsText,
+  getNativeThreadId,
+  getNormalizedPriority,
+  getOomScore,
+  getOomScoreAdj,
+  getPlatformPriorityInfo,
+  getPowerSource,
+  getPriority,
+  getPriorityLevel,
+  getProcessCycleTime,
+  getProcessDefaultCpuSets,
+  getProcessIoPriority,
+  getProcessPriorityBoost,
+  getProcessorGroups,
+  getQosClass,
+  getResourceUsage,
+  getRoundRobinInterval,
+  getScheduler,
+  getSchedStats,
+  getSchedulerPriorityRange,
+  getSchedulingSnapshot,
+  getSchedulingState,
+  getStats,
+  getSystemCpuSets,
+  getSystemLoad,
+  getThermalState,
+  getThreadAffinityTag,
+  getThreadCycleTime,
+  getThreadGroupAffinity,
+  getThreadIdealProcessor,
+  getThreadIoPriority,
+  getThreadPriorityBoost,
+  getThreadSchedulingInfo,
+  getThreadSelectedCpuSets,
+  getTimerResolution,
+  getTimerSlack,
+  getWindowsThreadPriorityMapping,
+  isInBackgroundMode,
+  listThreads,
+  lockPriorityCeiling,
+  measureSchedulingJitter,
+  moveToCgroup,
+  niceToWindowsThreadPriority,
+  niceWithPrevious,
+  onExternalPriorityChange,
+  onPowerSourceChange,
+  onThermalStateChange,
+  parseCpuList,
+  pinToEfficiencyCores,
+  pinToPerformanceCores,
+  reniceBy,
+  resetStats,
+  restoreDefaultPriority,
+  sampleThreadCpu,
+  getProcessPriorityClass,
+  getProcessGroupPriority,
+  setAbsolutePriority,
+  setAllThreadsPriority,
+  setCgroupCpuMax,
+  setCgroupCpuWeight,
+  setCgroupIdle,
+  setCpuAffinity,
+  setDarwinBackground,
+  setEfficiencyMode,
+  setDarwinIoPolicy,
+  setHighestAllowed,
+  setIdlePriority,
+  setIoPriority,
+  setLatencyNice,
+  setLowestPossible,
+  setNumaNode,
+  setNormalizedPriority,
+  setOomScoreAdj,
+  setPriority,
+  setPriorityTree,
+  setProcessBackgroundMode,
+  setProcessDefaultCpuSets,
+  setProcessIoPriority,
+  setProcessPriorityBoost,
+  setRealtimeThreadPolicy,
+  setProcessPriorityClass,
+  setProcessGroupPriority,
+  setQosClass,
+  setScheduler,
+  setSystemdUnitCpuWeight,
+  setSystemdUnitIoWeight,
+  setThreadAffinityTag,
+  setThreadEfficiencyMode,
+  setThreadGroupAffinity,
+  setThreadIdealProcessor,
+  setThreadIoPriority,
+  setThreadSelectedCpuSets,
+  setTimerResolution,
+  setTimerSlack,
+  setThreadPriority,
+  setThreadPriorityBoost,
+  setUserPriority,
+  watchCpuPressure,
+  watchPriority,
+  windowsThreadPriorityToNice,
+  withPriority,
+  yieldNow,
+  DarwinIoPolicy,
+  IoPriorityClass,
+  JobObject,
+  MmcssPriority,
+  MmcssTask,
+  PowerSource,
+  PriorityGuard,
+  PriorityLevel,
+  ProcessPriorityClass,
+  QosClass,
+  QosOverride,
+  SchedulerPolicy,
+  ThermalState,
+  WindowsIoPriority,
+  WindowsThreadPriority,
+  WorkgroupJoin,
+} from '../index.js'
+import niceBinding from '../nice.js'
+
+// restoring a higher priority requires root or CAP_SYS_NICE on Unix
+const privileged = process.platform === 'win32' || process.getuid?.() === 0
 
 test('should be able to call nice', (t) => {
   t.notThrows(() => {
@@ -9,6 +164,1355 @@ test('should be able to call nice', (t) => {
   })
 })
 
+test('niceWithPrevious should return the priority before and after the change', (t) => {
+  const before = getCurrentProcessPriority()
+  const { previous, current } = niceWithPrevious()
+  t.is(previous, before)
+  t.is(current, getCurrentProcessPriority())
+})
+
+test('should accept nice values on every platform with mapNiceValue', (t) => {
+  t.notThrows(() => {
+    nice(process.platform === 'win32' ? 10 : 0, { mapNiceValue: true })
+  })
+})
+
+test('nice should accept a target', (t) => {
+  t.notThrows(() => {
+    nice({ value: 0, target: 'process' })
+    nice({ value: 0, pid: process.pid })
+  })
+  t.throws(() => nice({ value: 0, target: 'thread', pid: process.pid }), { message: /target must be process/ })
+  // @ts-expect-error
+  t.throws(() => nice({ value: 0, target: 'group' }), { message: /not a valid target/ })
+})
+
+test('nice should accept priority level names', (t) => {
+  if (privileged) {
+    t.notThrows(() => {
+      nice('belowNormal')
+      nice('normal')
+    })
+  }
+  // @ts-expect-error
+  t.throws(() => nice('lowest'), { message: /not a valid priority level/ })
+})
+
 test('should be able to get current process priority', (t) => {
   t.is(typeof getCurrentProcessPriority(), 'number')
 })
+
+test('should be able to set priority of a process by pid', (t) => {
+  t.notThrows(() => {
+    setPriority(process.pid, process.platform === 'win32' ? 0 : getCurrentProcessPriority())
+  })
+})
+
+test('should throw when setting priority of a non-existent process', (t) => {
+  t.throws(() => setPriority(0x7ffffffe, 0))
+})
+
+test('should be able to get priority of a process by pid', (t) => {
+  t.is(typeof getPriority(process.pid), 'number')
+  t.is(getPriority(0), getPriority(process.pid))
+})
+
+test('should be able to get and set process group priority', (t) => {
+  if (process.platform === 'win32') {
+    t.throws(() => getProcessGroupPriority(0))
+    return
+  }
+  const priority = getProcessGroupPriority(0)
+  t.is(typeof priority, 'number')
+  t.notThrows(() => setProcessGroupPriority(0, Math.max(priority, getCurrentProcessPriority())))
+})
+
+test('should reject unknown user names in setUserPriority', (t) => {
+  t.throws(() => setUserPriority('napi-rs-nice-no-such-user', 10))
+})
+
+test('should be able to get and set process priority class', (t) => {
+  const priorityClass = getProcessPriorityClass()
+  t.true(Object.values(ProcessPriorityClass).includes(priorityClass))
+  t.notThrows(() => setProcessPriorityClass(priorityClass, process.pid))
+  t.is(getProcessPriorityClass(process.pid), priorityClass)
+})
+
+test('should be able to toggle process background mode on Windows', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => setProcessBackgroundMode(true))
+    return
+  }
+  t.notThrows(() => {
+    setProcessBackgroundMode(true)
+    setProcessBackgroundMode(false)
+  })
+})
+
+test('should be able to set priority of a thread by native thread id', (t) => {
+  if (process.platform === 'linux' || process.platform === 'win32') {
+    t.notThrows(() => setThreadPriority(getNativeThreadId(), getCurrentProcessPriority()))
+  } else {
+    t.throws(() => setThreadPriority(getNativeThreadId(), 0))
+  }
+})
+
+test('should be able to get native thread id', (t) => {
+  const tid = getNativeThreadId()
+  t.true(Number.isInteger(tid))
+  if (process.platform === 'linux') {
+    // the main thread id is the same as the process id on Linux
+    t.is(tid, process.pid)
+  }
+})
+
+test('should be able to set priority of all threads', (t) => {
+  const updated = setAllThreadsPriority(getCurrentProcessPriority())
+  t.true(updated >= 1)
+})
+
+test('should be able to set priority of a process tree', async (t) => {
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  t.teardown(() => child.kill())
+  await new Promise((resolve) => child.once('spawn', resolve))
+  if (process.platform === 'freebsd') {
+    t.throws(() => setPriorityTree(child.pid!, 10))
+    return
+  }
+  t.is(setPriorityTree(child.pid!, 10), 1)
+  t.is(getPriority(child.pid!), 10)
+})
+
+test('should be able to get priority level', (t) => {
+  t.true(Object.values(PriorityLevel).includes(getPriorityLevel()))
+})
+
+test('should map nice values and Windows thread priorities consistently', (t) => {
+  t.is(niceToWindowsThreadPriority(0), WindowsThreadPriority.ThreadPriorityNormal)
+  t.is(niceToWindowsThreadPriority(100), WindowsThreadPriority.ThreadPriorityIdle)
+  t.throws(() => windowsThreadPriorityToNice(WindowsThreadPriority.ThreadModeBackgroundBegin))
+  for (const { windowsThreadPriority, nice, minNice, maxNice } of getWindowsThreadPriorityMapping()) {
+    t.is(windowsThreadPriorityToNice(windowsThreadPriority), nice)
+    t.is(niceToWindowsThreadPriority(minNice), windowsThreadPriority)
+    t.is(niceToWindowsThreadPriority(maxNice), windowsThreadPriority)
+  }
+})
+
+test('withPriority should restore the priority after the callback', async (t) => {
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  const before = getCurrentProcessPriority()
+  t.is(
+    withPriority(PriorityLevel.Idle, () => 42),
+    42,
+  )
+  t.is(getCurrentProcessPriority(), before)
+  t.throws(
+    () =>
+      withPriority(PriorityLevel.Idle, () => {
+        throw new TypeError('callback failed')
+      }),
+    { instanceOf: TypeError },
+  )
+  t.is(getCurrentProcessPriority(), before)
+  t.is(await withPriority(PriorityLevel.Idle, async () => 'done'), 'done')
+  t.is(getCurrentProcessPriority(), before)
+})
+
+test('PriorityGuard should reject unknown level names', (t) => {
+  // @ts-expect-error
+  t.throws(() => new PriorityGuard('unknown'))
+})
+
+test('PriorityGuard should restore the priority when disposed', (t) => {
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  const before = getCurrentProcessPriority()
+  const guard = new niceBinding.PriorityGuard('idle')
+  t.false(guard.restored)
+  guard[Symbol.dispose]()
+  t.true(guard.restored)
+  t.is(getCurrentProcessPriority(), before)
+  t.notThrows(() => guard.restore())
+})
+
+test('adjustPriority and setAbsolutePriority should use nice values on every platform', (t) => {
+  const before = adjustPriority(0)
+  t.true(before >= -20 && before <= 19)
+  const after = adjustPriority(1)
+  t.true(after >= before)
+  t.notThrows(() => setAbsolutePriority(after))
+})
+
+test('boostFor should restore the priority after the duration', async (t) => {
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  const before = getCurrentProcessPriority()
+  const restored = boostFor(PriorityLevel.AboveNormal, 10)
+  await restored
+  t.is(getCurrentProcessPriority(), before)
+})
+
+test('restoreDefaultPriority should revert to the priority at load time', (t) => {
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  setAbsolutePriority(5)
+  t.notThrows(() => restoreDefaultPriority())
+})
+
+test('should be able to get and set normalized priority', (t) => {
+  const before = getNormalizedPriority()
+  t.true(before >= 0 && before <= 100)
+  t.throws(() => setNormalizedPriority(101), { message: /not a valid normalized priority/ })
+  if (privileged) {
+    setNormalizedPriority(before)
+    t.is(getNormalizedPriority(), before)
+  }
+})
+
+test('reniceBy should return the nice values before and after the change', (t) => {
+  const { previous, current } = reniceBy(1)
+  // a positive delta always lowers the priority, unless it is already the lowest
+  t.true(current > previous || previous === 19)
+})
+
+test('setLowestPossible and setHighestAllowed should return the new nice value', (t) => {
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  t.is(setLowestPossible(), 19)
+  t.true(setHighestAllowed() < 19)
+  setAbsolutePriority(0)
+})
+
+test('getPlatformPriorityInfo should describe nice on the current platform', (t) => {
+  const { kind, range, relative } = getPlatformPriorityInfo()
+  t.true(kind === 'thread' || kind === 'process')
+  t.true(range.min < range.max)
+  t.is(relative, process.platform !== 'win32')
+})
+
+test('should be able to get and set the scheduling policy on Linux', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getScheduler())
+    return
+  }
+  const { policy, priority } = getScheduler()
+  t.is(policy, SchedulerPolicy.Other)
+  t.is(priority, 0)
+  t.notThrows(() => setScheduler(SchedulerPolicy.Batch))
+  t.is(getScheduler().policy, SchedulerPolicy.Batch)
+  setScheduler(SchedulerPolicy.Other)
+})
+
+test('setScheduler should support resetOnFork', (t) => {
+  if (process.platform !== 'linux') {
+    t.pass()
+    return
+  }
+  setScheduler(SchedulerPolicy.Batch, 0, { resetOnFork: true })
+  t.true(getScheduler().resetOnFork)
+  // clearing SCHED_RESET_ON_FORK requires root or CAP_SYS_NICE
+  if (privileged) {
+    setScheduler(SchedulerPolicy.Other)
+    t.false(getScheduler().resetOnFork)
+  }
+})
+
+test('getSchedulerPriorityRange should return the real-time priority range', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getSchedulerPriorityRange(SchedulerPolicy.Fifo))
+    return
+  }
+  t.deepEqual(getSchedulerPriorityRange(SchedulerPolicy.Other), { min: 0, max: 0 })
+  const { min, max } = getSchedulerPriorityRange(SchedulerPolicy.Fifo)
+  t.true(min >= 1 && max > min)
+})
+
+test('getRoundRobinInterval should return the time slice in nanoseconds', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getRoundRobinInterval())
+    return
+  }
+  t.true(getRoundRobinInterval() >= 0)
+  t.true(getRoundRobinInterval(process.pid) >= 0)
+})
+
+test('yieldNow should yield the processor', (t) => {
+  t.is(typeof yieldNow(), 'boolean')
+})
+
+test('setScheduler and getScheduler should accept a native thread id', (t) => {
+  if (process.platform !== 'linux') {
+    t.pass()
+    return
+  }
+  const tid = getNativeThreadId()
+  setScheduler(SchedulerPolicy.Batch, 0, { tid })
+  t.is(getScheduler(tid).policy, SchedulerPolicy.Batch)
+  setScheduler(SchedulerPolicy.Other, 0, { tid })
+})
+
+test('latency nice should fall back gracefully on kernels without support', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getLatencyNice())
+    return
+  }
+  const before = getLatencyNice()
+  if (before === null) {
+    t.false(setLatencyNice(0))
+    return
+  }
+  t.true(setLatencyNice(before))
+  t.throws(() => setLatencyNice(20), { message: /not a valid latency nice value/ })
+})
+
+test('getSchedulingState should return the whole scheduling state', (t) => {
+  const state = getSchedulingState()
+  t.is(typeof state.nice, 'number')
+  if (process.platform === 'linux') {
+    t.is(state.policy, getScheduler().policy)
+    t.true(state.affinity!.length > 0)
+    t.is(typeof state.ioClass, 'number')
+  } else {
+    t.is(state.policy, null)
+  }
+})
+
+test('setScheduler watchdog should demote a busy real-time thread', async (t) => {
+  if (process.platform !== 'linux') {
+    t.pass()
+    return
+  }
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  try {
+    await new Promise((resolve) => child.once('spawn', resolve))
+    t.throws(() => setScheduler(SchedulerPolicy.Fifo, 10, { tid: child.pid, watchdogMs: 50 }), {
+      code: 'InvalidArg',
+    })
+  } finally {
+    child.kill()
+  }
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  setScheduler(SchedulerPolicy.Fifo, 10, { watchdogMs: 50 })
+  const end = Date.now() + 300
+  while (Date.now() < end) {
+    // keep the CPU busy without blocking
+  }
+  t.is(getScheduler().policy, SchedulerPolicy.Other)
+})
+
+test('should be able to get and set CPU affinity', (t) => {
+  if (!['linux', 'win32', 'freebsd'].includes(process.platform)) {
+    t.throws(() => getCpuAffinity())
+    return
+  }
+  const cpus = getCpuAffinity()
+  t.true(cpus.length > 0)
+  t.notThrows(() => setCpuAffinity(cpus))
+  t.deepEqual(getCpuAffinity(), cpus)
+  t.true(getCpuAffinity({ target: 'process' }).length > 0)
+  t.throws(() => setCpuAffinity([]), { message: /at least one CPU/ })
+})
+
+test('should be able to get and set CPU affinity of another process or thread', (t) => {
+  if (!['linux', 'win32', 'freebsd'].includes(process.platform)) {
+    t.pass()
+    return
+  }
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  try {
+    const cpus = getCpuAffinity({ pid: child.pid! })
+    t.true(cpus.length > 0)
+    t.notThrows(() => setCpuAffinity(cpus, { pid: child.pid! }))
+    t.deepEqual(getCpuAffinity({ tid: getNativeThreadId() }), getCpuAffinity())
+    t.throws(() => getCpuAffinity({ pid: child.pid!, tid: 1 }), { message: /can't be used together/ })
+  } finally {
+    child.kill()
+  }
+})
+
+test('should be able to use processor groups on Windows', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getProcessorGroups())
+    return
+  }
+  const groups = getProcessorGroups()
+  t.true(groups.length > 0)
+  const { group, cpus } = getThreadGroupAffinity()
+  t.true(groups.some((g) => g.group === group))
+  t.notThrows(() => setThreadGroupAffinity(group, cpus))
+})
+
+test('should be able to use CPU Sets on Windows', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getSystemCpuSets())
+    t.throws(() => setThreadSelectedCpuSets([]))
+    return
+  }
+  const cpuSets = getSystemCpuSets()
+  t.true(cpuSets.length > 0)
+  const ids = cpuSets.map((cpuSet) => cpuSet.id)
+  t.notThrows(() => setThreadSelectedCpuSets(ids))
+  t.deepEqual(getThreadSelectedCpuSets(), ids)
+  t.notThrows(() => setThreadSelectedCpuSets([]))
+  t.deepEqual(getThreadSelectedCpuSets(), [])
+  t.notThrows(() => setProcessDefaultCpuSets([]))
+  t.deepEqual(getProcessDefaultCpuSets(), [])
+})
+
+test('should be able to set the thread affinity tag on macOS', (t) => {
+  if (process.platform !== 'darwin') {
+    t.throws(() => setThreadAffinityTag(1))
+    t.throws(() => getThreadAffinityTag())
+    return
+  }
+  if (setThreadAffinityTag(1)) {
+    t.is(getThreadAffinityTag(), 1)
+    t.true(setThreadAffinityTag(0))
+  }
+  t.is(getThreadAffinityTag(), 0)
+})
+
+test('should be able to set the ideal processor on Windows', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getThreadIdealProcessor())
+    t.throws(() => setThreadIdealProcessor({ group: 0, number: 0 }))
+    return
+  }
+  const ideal = getThreadIdealProcessor()
+  t.deepEqual(setThreadIdealProcessor({ group: ideal.group, number: 0 }), ideal)
+  t.deepEqual(getThreadIdealProcessor(), { group: ideal.group, number: 0 })
+  t.notThrows(() => setThreadIdealProcessor(ideal))
+})
+
+test('should be able to get the CPU topology', (t) => {
+  if (!['linux', 'win32', 'darwin'].includes(process.platform)) {
+    t.throws(() => getCpuTopology())
+    return
+  }
+  const topology = getCpuTopology()
+  t.is(topology.cpus.length, topology.logicalCpus)
+  t.true(topology.sockets >= 1)
+  t.true(topology.physicalCores >= topology.sockets)
+  t.true(topology.logicalCpus >= topology.physicalCores)
+  t.true(topology.numaNodes >= 1)
+  for (const cache of topology.caches) {
+    t.true(cache.cpus.every((cpu) => topology.cpus.some((logical) => logical.cpu === cpu)))
+  }
+})
+
+test('should be able to pin to a core class', (t) => {
+  if (!['linux', 'win32', 'darwin'].includes(process.platform)) {
+    t.throws(() => getCoreClasses())
+    return
+  }
+  const { performance, efficiency } = getCoreClasses()
+  t.true(performance.length > 0)
+  t.false(efficiency.some((cpu) => performance.includes(cpu)))
+  if (process.platform === 'darwin') {
+    t.throws(() => pinToPerformanceCores({ target: 'process' }))
+    return
+  }
+  const cpus = getCpuAffinity()
+  try {
+    t.true(pinToPerformanceCores())
+    t.deepEqual(getCpuAffinity(), performance)
+    t.is(pinToEfficiencyCores(), efficiency.length > 0)
+  } finally {
+    setCpuAffinity(cpus)
+  }
+})
+
+test('should be able to parse and format CPU lists', (t) => {
+  t.deepEqual(parseCpuList('0-3,8,10-11'), [0, 1, 2, 3, 8, 10, 11])
+  t.deepEqual(parseCpuList(''), [])
+  t.is(formatCpuList([11, 0, 1, 2, 3, 8, 10]), '0-3,8,10-11')
+  t.is(formatCpuList(parseCpuList('4,0-2,1')), '0-2,4')
+  t.throws(() => parseCpuList('3-1'), { message: /not a valid CPU list/ })
+  t.throws(() => parseCpuList('a'), { message: /not a valid CPU list/ })
+  t.throws(() => parseCpuList('0-4294967295'), { message: /not a valid CPU list/ })
+  if (['linux', 'win32', 'freebsd'].includes(process.platform)) {
+    const cpus = getCpuAffinity()
+    t.notThrows(() => setCpuAffinity(formatCpuList(cpus)))
+    t.deepEqual(getCpuAffinity(), cpus)
+  }
+})
+
+test('should be able to set the NUMA node', (t) => {
+  if (process.platform !== 'linux' && process.platform !== 'win32') {
+    t.throws(() => setNumaNode(0))
+    return
+  }
+  const cpus = getCpuAffinity()
+  try {
+    t.notThrows(() => setNumaNode(0))
+    const node0 = getCpuTopology()
+      .cpus.filter((cpu) => cpu.numaNode === 0)
+      .map((cpu) => cpu.cpu)
+    t.true(getCpuAffinity().every((cpu) => node0.includes(cpu)))
+    t.throws(() => setNumaNode(1024), { message: /not a valid NUMA node/ })
+  } finally {
+    setCpuAffinity(cpus)
+  }
+})
+
+test('should be able to get the allowed CPUs', (t) => {
+  if (!['linux', 'win32', 'freebsd', 'darwin'].includes(process.platform)) {
+    t.throws(() => getAllowedCpus())
+    return
+  }
+  const allowed = getAllowedCpus()
+  t.true(allowed.length > 0)
+  if (process.platform !== 'freebsd') {
+    const cpus = getCpuTopology().cpus.map((cpu) => cpu.cpu)
+    t.true(allowed.every((cpu) => cpus.includes(cpu)))
+  }
+})
+
+test('should be able to get the current CPU', (t) => {
+  if (!['linux', 'win32', 'freebsd'].includes(process.platform)) {
+    t.throws(() => getCurrentCpu())
+    return
+  }
+  const cpus = getCpuAffinity()
+  try {
+    setCpuAffinity([cpus[0]])
+    t.is(getCurrentCpu(), cpus[0])
+  } finally {
+    setCpuAffinity(cpus)
+  }
+})
+
+test('should be able to set the I/O priority on Linux', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getIoPriority())
+    t.throws(() => setIoPriority(IoPriorityClass.Idle))
+    return
+  }
+  const previous = getIoPriority()
+  try {
+    setIoPriority(IoPriorityClass.BestEffort, 6)
+    t.deepEqual(getIoPriority(), { class: IoPriorityClass.BestEffort, level: 6 })
+    setIoPriority(IoPriorityClass.Idle)
+    t.is(getIoPriority().class, IoPriorityClass.Idle)
+    t.throws(() => setIoPriority(IoPriorityClass.BestEffort, 8), { message: /not a valid I\/O priority level/ })
+  } finally {
+    setIoPriority(previous.class, previous.level)
+  }
+})
+
+test('should be able to set the process I/O priority on Windows', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getProcessIoPriority())
+    t.throws(() => setProcessIoPriority(WindowsIoPriority.VeryLow))
+    return
+  }
+  const previous = getProcessIoPriority()
+  try {
+    setProcessIoPriority(WindowsIoPriority.VeryLow)
+    t.is(getProcessIoPriority(), WindowsIoPriority.VeryLow)
+    setProcessIoPriority(WindowsIoPriority.Low)
+    t.is(getProcessIoPriority(), WindowsIoPriority.Low)
+  } finally {
+    setProcessIoPriority(previous)
+  }
+})
+
+test('should be able to set the I/O policy on macOS', (t) => {
+  if (process.platform !== 'darwin') {
+    t.throws(() => getDarwinIoPolicy('thread'))
+    t.throws(() => setDarwinIoPolicy('thread', DarwinIoPolicy.Throttle))
+    return
+  }
+  const previous = getDarwinIoPolicy('thread')
+  try {
+    setDarwinIoPolicy('thread', DarwinIoPolicy.Throttle)
+    t.is(getDarwinIoPolicy('thread'), DarwinIoPolicy.Throttle)
+    // @ts-expect-error
+    t.throws(() => getDarwinIoPolicy('system'), { message: /not a valid scope/ })
+  } finally {
+    setDarwinIoPolicy('thread', previous)
+  }
+})
+
+test('should be able to set the thread I/O priority on Windows', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getThreadIoPriority())
+    t.throws(() => setThreadIoPriority(WindowsIoPriority.VeryLow))
+    return
+  }
+  const previous = getThreadIoPriority()
+  try {
+    setThreadIoPriority(WindowsIoPriority.VeryLow)
+    t.is(getThreadIoPriority(), WindowsIoPriority.VeryLow)
+  } finally {
+    setThreadIoPriority(previous)
+  }
+})
+
+test('should be able to set the I/O priority of another process or process group', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getIoPriority({ pid: process.pid }))
+    return
+  }
+  // detached children lead their own process group
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'], { detached: true })
+  try {
+    setIoPriority(IoPriorityClass.Idle, undefined, { pid: child.pid! })
+    t.is(getIoPriority({ pid: child.pid! }).class, IoPriorityClass.Idle)
+    setIoPriority(IoPriorityClass.BestEffort, 7, { pgid: child.pid! })
+    t.deepEqual(getIoPriority({ pgid: child.pid! }), { class: IoPriorityClass.BestEffort, level: 7 })
+    t.throws(() => getIoPriority({ pid: child.pid!, pgid: child.pid! }), { message: /can't be used together/ })
+  } finally {
+    child.kill()
+  }
+})
+
+test('should be able to enter and exit background mode', (t) => {
+  if (!['linux', 'darwin', 'win32'].includes(process.platform)) {
+    t.throws(() => enterBackgroundMode())
+    t.throws(() => isInBackgroundMode())
+    return
+  }
+  t.false(isInBackgroundMode())
+  // leaving SCHED_IDLE requires privileges on Linux
+  if (process.platform === 'linux' && !privileged) {
+    t.pass()
+    return
+  }
+  t.false(exitBackgroundMode())
+  const previous = process.platform === 'linux' ? [getScheduler(), getIoPriority()] : undefined
+  t.true(enterBackgroundMode())
+  try {
+    t.false(enterBackgroundMode())
+    t.true(isInBackgroundMode())
+    if (process.platform === 'linux') {
+      t.is(getScheduler().policy, SchedulerPolicy.Idle)
+      t.is(getIoPriority().class, IoPriorityClass.Idle)
+    }
+  } finally {
+    t.true(exitBackgroundMode())
+  }
+  t.false(exitBackgroundMode())
+  t.false(isInBackgroundMode())
+  if (previous) {
+    t.deepEqual([getScheduler(), getIoPriority()], previous)
+  }
+})
+
+test('should be able to set the OOM score adjustment', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getOomScoreAdj())
+    return
+  }
+  t.throws(() => setOomScoreAdj(1001), { message: /not a valid OOM score adjustment/ })
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  try {
+    setOomScoreAdj(500, child.pid!)
+    t.is(getOomScoreAdj(child.pid!), 500)
+  } finally {
+    child.kill()
+  }
+})
+
+test('should be able to read the OOM score', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getOomScore())
+    return
+  }
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  try {
+    const score = getOomScore(child.pid!)
+    t.true(score >= 0 && score <= 2000)
+    setOomScoreAdj(1000, child.pid!)
+    t.true(getOomScore(child.pid!) >= 1000)
+  } finally {
+    child.kill()
+  }
+})
+
+test('should be able to toggle the efficiency mode', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => setEfficiencyMode(true))
+    t.throws(() => setThreadEfficiencyMode(true))
+    return
+  }
+  t.notThrows(() => {
+    setEfficiencyMode(true)
+    setEfficiencyMode(false)
+    setThreadEfficiencyMode(true)
+    setThreadEfficiencyMode(false)
+  })
+})
+
+test('should be able to set the QoS class', (t) => {
+  if (process.platform !== 'darwin') {
+    t.throws(() => getQosClass())
+    t.throws(() => setQosClass(QosClass.Utility))
+    return
+  }
+  t.throws(() => setQosClass(QosClass.Utility, 1), { message: /not a valid relative priority/ })
+  const previous = getQosClass()
+  try {
+    setQosClass(QosClass.Utility, -5)
+    t.is(getQosClass(), QosClass.Utility)
+  } finally {
+    setQosClass(previous === QosClass.Unspecified ? QosClass.Default : previous)
+  }
+})
+
+test('should be able to override the QoS class of another thread', (t) => {
+  if (process.platform !== 'darwin') {
+    t.throws(() => new QosOverride(getNativeThreadId(), QosClass.UserInitiated))
+    return
+  }
+  t.throws(() => new QosOverride(-1, QosClass.UserInitiated), { message: /not a thread of the current process/ })
+  const qosOverride = new QosOverride(getNativeThreadId(), QosClass.UserInitiated)
+  t.false(qosOverride.ended)
+  qosOverride[Symbol.dispose]()
+  t.true(qosOverride.ended)
+  t.notThrows(() => qosOverride.end())
+})
+
+test('should be able to begin and end an activity', (t) => {
+  t.throws(() => beginActivity([], 'test'), { message: /At least one activity option/ })
+  if (process.platform !== 'darwin') {
+    t.throws(() => beginActivity(['userInitiated'], 'test'))
+    return
+  }
+  const token = beginActivity(['userInitiated', 'latencyCritical'], 'running the tests')
+  endActivity(token)
+  t.throws(() => endActivity(token), { message: /not an active activity/ })
+})
+
+test('should be able to put a process into the Darwin background state', (t) => {
+  if (process.platform !== 'darwin') {
+    t.throws(() => setDarwinBackground(true))
+    return
+  }
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  try {
+    t.notThrows(() => {
+      setDarwinBackground(true, child.pid!)
+      setDarwinBackground(false, child.pid!)
+    })
+  } finally {
+    child.kill()
+  }
+})
+
+test('should be able to watch the thermal state', (t) => {
+  if (!['linux', 'darwin', 'win32'].includes(process.platform)) {
+    t.throws(() => getThermalState())
+    t.throws(() => onThermalStateChange(() => {}))
+    return
+  }
+  t.true(getThermalState() >= ThermalState.Nominal && getThermalState() <= ThermalState.Critical)
+  const watcher = onThermalStateChange(() => {}, { interval: 10 })
+  t.false(watcher.stopped)
+  watcher.stop()
+  t.true(watcher.stopped)
+})
+
+test('should be able to watch the power source', (t) => {
+  if (!['linux', 'darwin', 'win32'].includes(process.platform)) {
+    t.throws(() => getPowerSource())
+    t.throws(() => onPowerSourceChange(() => {}))
+    return
+  }
+  t.true([PowerSource.Ac, PowerSource.Battery].includes(getPowerSource()))
+  const watcher = onPowerSourceChange(() => {}, { interval: 10 })
+  watcher.stop()
+  t.true(watcher.stopped)
+})
+
+test('should be able to watch the priority of a process', async (t) => {
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  try {
+    await new Promise((resolve) => child.once('spawn', resolve))
+    const pid = child.pid!
+    const previous = getPriority(pid)
+    const change = new Promise<{ pid: number; previous: number; current: number }>((resolve) => {
+      const watcher = watchPriority(
+        (change) => {
+          watcher.stop()
+          resolve(change)
+        },
+        { pid, interval: 10 },
+      )
+    })
+    // lowering the priority is always allowed
+    setPriority(pid, 10)
+    const { pid: changed, previous: before, current } = await change
+    t.is(changed, pid)
+    t.is(before, previous)
+    t.is(current, getPriority(pid))
+  } finally {
+    child.kill()
+  }
+})
+
+test('should reject a zero watch interval', (t) => {
+  t.throws(() => watchPriority(() => {}, { interval: 0 }), { code: 'InvalidArg' })
+  t.throws(() => onExternalPriorityChange(() => {}, { interval: 0 }), { code: 'InvalidArg' })
+})
+
+test('should be able to watch the CPU pressure', (t) => {
+  t.throws(() => watchCpuPressure(101, () => {}), { message: /not a valid threshold/ })
+  if (process.platform !== 'linux') {
+    t.throws(() => getCpuPressure())
+    t.throws(() => watchCpuPressure(50, () => {}))
+    return
+  }
+  const pressure = getCpuPressure()
+  t.true(pressure.some.avg10 >= 0 && pressure.some.avg10 <= 100)
+  const watcher = watchCpuPressure(50, () => {})
+  watcher.stop()
+  t.true(watcher.stopped)
+})
+
+test('should be able to control the priority boost', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getProcessPriorityBoost())
+    t.throws(() => setThreadPriorityBoost(false))
+    return
+  }
+  const previousProcess = getProcessPriorityBoost()
+  const previousThread = getThreadPriorityBoost()
+  try {
+    setProcessPriorityBoost(false)
+    t.false(getProcessPriorityBoost())
+    setThreadPriorityBoost(false)
+    t.false(getThreadPriorityBoost())
+    t.false(getThreadPriorityBoost(getNativeThreadId()))
+  } finally {
+    setProcessPriorityBoost(previousProcess)
+    setThreadPriorityBoost(previousThread)
+  }
+})
+
+test('should be able to set the timer resolution', (t) => {
+  t.throws(() => setTimerResolution(0), { message: /not a valid timer resolution/ })
+  if (process.platform !== 'win32') {
+    t.throws(() => getTimerResolution())
+    t.throws(() => setTimerResolution(1))
+    return
+  }
+  const { finest, coarsest } = getTimerResolution()
+  t.true(finest <= coarsest)
+  try {
+    t.true(setTimerResolution(1) <= 1)
+  } finally {
+    clearTimerResolution()
+  }
+  t.notThrows(() => clearTimerResolution())
+})
+
+test('should be able to set the timer slack', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getTimerSlack())
+    t.throws(() => setTimerSlack(1000))
+    return
+  }
+  const previous = getTimerSlack()
+  try {
+    setTimerSlack(1000)
+    t.is(getTimerSlack(), 1000)
+  } finally {
+    setTimerSlack(previous)
+  }
+})
+
+test('should be able to check whether the priority can be raised', (t) => {
+  const canRaise = canRaisePriority()
+  t.is(typeof canRaise, 'boolean')
+  // containers may drop CAP_SYS_NICE even for root on Linux
+  if (process.platform !== 'win32' && process.platform !== 'linux') {
+    t.is(canRaise, process.getuid!() === 0)
+  }
+})
+
+test('should be able to predict whether a priority can be set', (t) => {
+  t.deepEqual(canSetPriority(getPriority(0)), { allowed: true })
+  t.true(canSetPriority(19).allowed)
+  const check = canSetPriority(-20)
+  if (!check.allowed) {
+    t.truthy(check.reason)
+    t.truthy(check.message)
+    t.throws(() => setPriority(0, -20))
+  }
+})
+
+test('should be able to enable SeIncreaseBasePriorityPrivilege', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => enableIncreaseBasePriorityPrivilege())
+    return
+  }
+  if (enableIncreaseBasePriorityPrivilege()) {
+    t.true(canSetPriority(-20).allowed)
+  } else {
+    t.is(canSetPriority(-20).reason, 'missingPrivilege')
+  }
+})
+
+test('should be able to lock the priority ceiling', (t) => {
+  if (!['linux', 'win32'].includes(process.platform)) {
+    t.throws(() => lockPriorityCeiling())
+    return
+  }
+  // locking can't be undone, so it runs in a child process
+  const binding = fileURLToPath(new URL('../index.js', import.meta.url))
+  const output = execFileSync(process.execPath, [
+    '-e',
+    `const { lockPriorityCeiling, canSetPriority } = require(${JSON.stringify(binding)})
+    lockPriorityCeiling()
+    console.log(JSON.stringify(canSetPriority(-20)))`,
+  ])
+  const check = JSON.parse(output.toString())
+  t.false(check.allowed)
+  t.truthy(check.reason)
+})
+
+test('should be able to get the effective priority range', (t) => {
+  const { min, max, priorityClasses } = getEffectivePriorityRange()
+  t.true(min <= max)
+  t.true(canSetPriority(min).allowed)
+  t.true(canSetPriority(max).allowed)
+  if (process.platform === 'win32') {
+    t.true(priorityClasses!.includes(ProcessPriorityClass.High))
+  } else {
+    t.is(max, 19)
+    t.is(priorityClasses, undefined)
+  }
+})
+
+test('should be able to register a thread with MMCSS', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => new MmcssTask())
+    return
+  }
+  t.throws(() => new MmcssTask('Not A Task'))
+  const task = new MmcssTask()
+  t.false(task.reverted)
+  task.setPriority(MmcssPriority.High)
+  task[Symbol.dispose]()
+  t.true(task.reverted)
+  t.notThrows(() => task.revert())
+  t.throws(() => task.setPriority(MmcssPriority.Normal), { message: /already been reverted/ })
+})
+
+test('should be able to set the realtime thread policy', async (t) => {
+  const policy = { periodNs: 2_900_000, computationNs: 1_000_000, constraintNs: 2_900_000 }
+  t.throws(() => setRealtimeThreadPolicy({ ...policy, computationNs: 3_000_000 }), {
+    message: /not greater than constraintNs/,
+  })
+  if (process.platform !== 'darwin') {
+    t.throws(() => setRealtimeThreadPolicy(policy))
+    return
+  }
+  // the policy can't be reverted, so it's applied to a worker thread
+  const { Worker } = await import('node:worker_threads')
+  const binding = fileURLToPath(new URL('../index.js', import.meta.url))
+  const worker = new Worker(
+    `require(${JSON.stringify(binding)}).setRealtimeThreadPolicy(${JSON.stringify(policy)})`,
+    { eval: true },
+  )
+  await t.notThrowsAsync(new Promise((resolve, reject) => worker.once('error', reject).once('exit', resolve)))
+})
+
+test('should only join workgroups wrapped in externals', (t) => {
+  // a real workgroup can only be obtained from CoreAudio through another addon
+  t.throws(() => new WorkgroupJoin({}), { message: /not an external/ })
+})
+
+test('should be able to enable the audio thread priority', (t) => {
+  if (!['linux', 'darwin', 'win32'].includes(process.platform)) {
+    t.throws(() => enableAudioThreadPriority())
+    return
+  }
+  if (process.platform === 'linux') {
+    t.throws(() => enableAudioThreadPriority({ rtPriority: 100 }), { message: /not a valid real-time priority/ })
+    if (!canRaisePriority()) {
+      t.pass()
+      return
+    }
+  }
+  const audio = enableAudioThreadPriority()
+  if (process.platform === 'linux') {
+    t.deepEqual(getScheduler(), { policy: SchedulerPolicy.Fifo, priority: 10, resetOnFork: true })
+  }
+  t.false(audio.reverted)
+  audio[Symbol.dispose]()
+  t.true(audio.reverted)
+  t.notThrows(() => audio.revert())
+  if (process.platform === 'linux') {
+    t.is(getScheduler().policy, SchedulerPolicy.Other)
+  }
+})
+
+test('should be able to limit the scheduling of a job object', async (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => new JobObject())
+    return
+  }
+  const job = new JobObject()
+  t.deepEqual(job.getLimits(), {})
+  t.throws(() => job.setLimits({ schedulingClass: 10 }), { message: /not a valid scheduling class/ })
+  job.setLimits({ priorityClass: ProcessPriorityClass.BelowNormal, schedulingClass: 2, affinity: [0] })
+  t.deepEqual(job.getLimits(), { priorityClass: ProcessPriorityClass.BelowNormal, schedulingClass: 2, affinity: [0] })
+  const child = spawn(process.execPath, ['-e', 'setTimeout(() => {}, 10000)'])
+  try {
+    await new Promise((resolve) => child.once('spawn', resolve))
+    job.assignProcess(child.pid!)
+    t.is(getProcessPriorityClass(child.pid!), ProcessPriorityClass.BelowNormal)
+  } finally {
+    child.kill()
+  }
+})
+
+test('should be able to limit the memory of a job object', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => new JobObject())
+    return
+  }
+  const job = new JobObject()
+  t.deepEqual(job.getMemoryLimits(), {})
+  t.throws(() => job.setMemoryLimits({ jobMemory: -1 }), { code: 'InvalidArg' })
+  t.throws(() => job.setMemoryLimits({ maxWorkingSet: 64 * 1024 * 1024 }), { message: /must be set together/ })
+  t.throws(() => job.setIoRateLimits({ maxBandwidth: 0.5 }), { code: 'InvalidArg' })
+  job.setLimits({ schedulingClass: 2 })
+  job.setMemoryLimits({ processMemory: 512 * 1024 * 1024, jobMemory: 1024 * 1024 * 1024 })
+  t.deepEqual(job.getMemoryLimits(), { processMemory: 512 * 1024 * 1024, jobMemory: 1024 * 1024 * 1024 })
+  // the scheduling limits are kept
+  t.deepEqual(job.getLimits(), { schedulingClass: 2 })
+  job.setMemoryLimits({})
+  t.deepEqual(job.getMemoryLimits(), {})
+})
+
+test('should be able to get the cgroup CPU weight', (t) => {
+  t.throws(() => setCgroupCpuWeight(0), { message: /not a valid CPU weight/ })
+  if (process.platform !== 'linux') {
+    t.throws(() => getCgroupCpuWeight())
+    return
+  }
+  let weight: number
+  try {
+    weight = getCgroupCpuWeight()
+  } catch (e) {
+    // the cpu controller may not be enabled in either cgroup hierarchy
+    t.regex((e as Error).message, /cpu\.weight|cpu\.shares|cgroup/)
+    return
+  }
+  t.true(weight >= 1 && weight <= 10000)
+})
+
+test('should be able to get the cgroup CPU bandwidth limit', (t) => {
+  t.throws(() => setCgroupCpuMax(10), { message: /not a valid CPU quota/ })
+  t.throws(() => setCgroupCpuMax(null, 10), { message: /not a valid CPU period/ })
+  if (process.platform !== 'linux') {
+    t.throws(() => getCgroupCpuMax())
+    return
+  }
+  let limit: ReturnType<typeof getCgroupCpuMax>
+  try {
+    limit = getCgroupCpuMax()
+  } catch (e) {
+    t.regex((e as Error).message, /cpu\.max|cgroup/)
+    return
+  }
+  t.true(limit.period >= 1000)
+  t.true(limit.quota === undefined || limit.quota >= 1000)
+})
+
+test('should be able to set the idle priority', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getCgroupIdle())
+    t.throws(() => setCgroupIdle(true))
+    t.throws(() => setIdlePriority(true))
+    return
+  }
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  const mechanism = setIdlePriority(true)
+  try {
+    if (mechanism === 'cgroup') {
+      t.true(getCgroupIdle())
+    } else {
+      t.is(getScheduler().policy, SchedulerPolicy.Idle)
+    }
+  } finally {
+    t.is(setIdlePriority(false), mechanism)
+  }
+})
+
+test('should be able to get the effective CPU limit', (t) => {
+  const { cpus, quota, cpuset } = getEffectiveCpuLimit()
+  t.true(cpuset >= 1)
+  t.true(cpus > 0 && cpus <= cpuset)
+  if (quota !== undefined) {
+    t.is(cpus, Math.min(quota, cpuset))
+  }
+})
+
+test('should validate the cgroup to move to', (t) => {
+  t.throws(() => moveToCgroup(''), { message: /must not be empty/ })
+  if (process.platform !== 'linux') {
+    t.throws(() => moveToCgroup('workers'))
+    return
+  }
+  t.throws(() => moveToCgroup('does-not-exist'), { message: /cgroup/ })
+  t.throws(() => moveToCgroup('/etc'), { message: /cgroup/ })
+})
+
+test('should validate systemd unit weights', async (t) => {
+  await t.throwsAsync(setSystemdUnitCpuWeight(0), { code: 'InvalidArg' })
+  await t.throwsAsync(setSystemdUnitIoWeight(10001), { code: 'InvalidArg' })
+  if (process.platform !== 'linux') {
+    await t.throwsAsync(setSystemdUnitCpuWeight(100))
+    return
+  }
+  await t.throwsAsync(setSystemdUnitCpuWeight(100, { unit: '' }), { message: /must not be empty/ })
+})
+
+test('should be able to take a scheduling snapshot', (t) => {
+  const snapshot = getSchedulingSnapshot()
+  t.is(snapshot.platform, process.platform)
+  t.is(snapshot.pid, process.pid)
+  t.is(snapshot.nice, getPriority(0))
+  t.deepEqual(JSON.parse(JSON.stringify(snapshot)), snapshot)
+  if (process.platform === 'linux') {
+    t.is(snapshot.policy, SchedulerPolicy.Other)
+    t.true(snapshot.affinity!.length > 0)
+    t.not(snapshot.ioClass, null)
+  } else {
+    t.is(snapshot.policy, null)
+  }
+})
+
+test('should be able to get the resource usage', (t) => {
+  t.throws(() => getResourceUsage('everything' as 'self'), { code: 'InvalidArg' })
+  const before = getResourceUsage('self')
+  const end = Date.now() + 50
+  while (Date.now() < end) {
+    // burn some CPU time
+  }
+  const after = getResourceUsage('self')
+  t.true(after.userCpuTime + after.systemCpuTime > before.userCpuTime + before.systemCpuTime)
+  const thread = getResourceUsage('thread')
+  t.true(thread.userCpuTime + thread.systemCpuTime <= after.userCpuTime + after.systemCpuTime + 1000)
+  t.is(thread.maxRss, undefined)
+  if (process.platform === 'win32') {
+    t.throws(() => getResourceUsage('children'))
+    return
+  }
+  t.true(getResourceUsage('self').maxRss! > 0)
+  t.true(getResourceUsage('children').userCpuTime >= 0)
+})
+
+test('should be able to get the context switches', (t) => {
+  const switches = getContextSwitches()
+  t.true(switches.total >= 0)
+  t.is(switches.threads, undefined)
+  if (process.platform === 'win32') {
+    t.is(switches.voluntary, undefined)
+  } else {
+    t.is(switches.total, switches.voluntary! + switches.involuntary!)
+  }
+  if (!['linux', 'win32'].includes(process.platform)) {
+    t.throws(() => getContextSwitches({ threads: true }))
+    return
+  }
+  const { threads } = getContextSwitches({ threads: true })
+  t.true(threads!.some(({ tid }) => tid === getNativeThreadId()))
+})
+
+test('should be able to list the threads', (t) => {
+  if (!['linux', 'darwin', 'win32'].includes(process.platform)) {
+    t.throws(() => listThreads())
+    return
+  }
+  const threads = listThreads()
+  const current = threads.find(({ tid }) => tid === getNativeThreadId())
+  t.truthy(current)
+  t.true(current!.cpuTime! > 0)
+  if (process.platform === 'linux') {
+    t.is(current!.nice, getPriority(0))
+    t.is(current!.policy, SchedulerPolicy.Other)
+    t.is(current!.state, 'running')
+  }
+  if (process.platform === 'win32') {
+    t.is(current!.priority, WindowsThreadPriority.ThreadPriorityNormal)
+  }
+})
+
+test('should be able to sample the CPU usage of each thread', async (t) => {
+  if (!['linux', 'darwin', 'win32'].includes(process.platform)) {
+    await t.throwsAsync(() => sampleThreadCpu({ durationMs: 10 }))
+    return
+  }
+  const sampling = sampleThreadCpu({ durationMs: 200 })
+  const end = Date.now() + 150
+  while (Date.now() < end) {
+    // keep the JavaScript thread busy during the window
+  }
+  const samples = await sampling
+  t.true(samples.length > 0)
+  const current = samples.find(({ tid }) => tid === getNativeThreadId())
+  t.truthy(current)
+  t.true(current!.cpuPercent > 0 && current!.cpuPercent <= 100)
+  t.true(samples.every((sample, i) => i === 0 || samples[i - 1].cpuTime >= sample.cpuTime))
+})
+
+test('should be able to get the scheduler statistics', (t) => {
+  if (process.platform !== 'linux') {
+    t.throws(() => getSchedStats())
+    return
+  }
+  const stats = getSchedStats()
+  t.true(stats.runTime > 0)
+  t.true(stats.waitTime >= 0)
+  t.true(stats.timeslices > 0)
+  t.true(getSchedStats(getNativeThreadId()).runTime >= stats.runTime)
+})
+
+test('should be able to measure the scheduling jitter', (t) => {
+  t.throws(() => measureSchedulingJitter({ intervalUs: 0 }), { code: 'InvalidArg' })
+  t.throws(() => measureSchedulingJitter({ durationMs: 1, intervalUs: 5000 }), { code: 'InvalidArg' })
+  const jitter = measureSchedulingJitter({ durationMs: 100, intervalUs: 10000 })
+  t.true(jitter.samples > 0 && jitter.samples <= 10)
+  t.true(jitter.min <= jitter.p50 && jitter.p50 <= jitter.p99 && jitter.p99 <= jitter.max)
+  t.true(jitter.min >= 0)
+})
+
+test('should be able to get the cycle time', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getThreadCycleTime())
+    t.throws(() => getProcessCycleTime())
+    return
+  }
+  const threadCycles = getThreadCycleTime()
+  const processCycles = getProcessCycleTime()
+  let sum = 0
+  for (let i = 0; i < 1e6; i++) {
+    sum += i
+  }
+  t.true(sum > 0)
+  t.true(getThreadCycleTime() > threadCycles)
+  t.true(getThreadCycleTime(getNativeThreadId()) > threadCycles)
+  t.true(getProcessCycleTime() > processCycles)
+  t.true(getProcessCycleTime(process.pid) >= getThreadCycleTime())
+})
+
+test('should be able to get the thread scheduling info', (t) => {
+  if (process.platform !== 'win32') {
+    t.throws(() => getThreadSchedulingInfo())
+    return
+  }
+  const info = getThreadSchedulingInfo()
+  t.true(info.basePriority >= 1 && info.basePriority <= 31)
+  t.true(info.currentPriority >= info.basePriority)
+  t.is(info.boostEnabled, getThreadPriorityBoost())
+  t.false(info.backgroundMode)
+  t.is(getThreadSchedulingInfo(getNativeThreadId()).basePriority, info.basePriority)
+  t.true(enterBackgroundMode())
+  try {
+    t.true(getThreadSchedulingInfo().backgroundMode)
+  } finally {
+    exitBackgroundMode()
+  }
+  t.false(getThreadSchedulingInfo().backgroundMode)
+})
+
+test('should be able to get the system load', (t) => {
+  if (!['linux', 'darwin', 'freebsd', 'win32'].includes(process.platform)) {
+    t.throws(() => getSystemLoad())
+    return
+  }
+  const load = getSystemLoad()
+  t.is(load.emulated, process.platform === 'win32')
+  for (const average of [load.avg1, load.avg5, load.avg15]) {
+    t.true(Number.isFinite(average) && average >= 0)
+  }
+})
+
+test('should be able to export the metrics text', (t) => {
+  setPriority(0, getPriority(0))
+  const text = getMetricsText()
+  t.regex(text, new RegExp(`^nice_info\\{platform="${process.platform}",pid="${process.pid}",tid="\\d+"\\} 1$`, 'm'))
+  t.regex(text, new RegExp(`^nice_value ${getPriority(0)}$`, 'm'))
+  t.regex(text, /^# TYPE nice_calls_total counter$/m)
+  t.regex(text, /^nice_calls_total\{function="setPriority",result="success"\} [1-9]\d*$/m)
+  for (const line of text.trimEnd().split('\n')) {
+    t.regex(line, /^(# (HELP|TYPE) nice_\w+ .+|nice_\w+(\{.*\})? -?\d+(\.\d+)?)$/)
+  }
+})
+
+test('should be able to count the calls and errors', (t) => {
+  resetStats()
+  t.deepEqual(getStats(), [])
+  const before = Date.now()
+  setPriority(0, getPriority(0))
+  t.throws(() => setQosClass(QosClass.Unspecified), { code: 'InvalidArg' })
+  const stats = getStats()
+  t.deepEqual(
+    stats.map(({ function: name }) => name),
+    ['setPriority', 'setQosClass'],
+  )
+  const [setPriorityStats, setQosClassStats] = stats
+  t.is(setPriorityStats.successes, 1)
+  t.is(setPriorityStats.failures, 0)
+  t.is(setPriorityStats.lastError, undefined)
+  t.is(setQosClassStats.successes, 0)
+  t.is(setQosClassStats.failures, 1)
+  const { code, message, timestamp } = setQosClassStats.lastError!
+  t.is(code, 'InvalidArg')
+  t.regex(message, /Unspecified/)
+  t.true(timestamp >= before && timestamp <= Date.now())
+  resetStats()
+  adjustPriority(0)
+  t.deepEqual(
+    getStats().map(({ function: name, successes }) => [name, successes]),
+    [['adjustPriority', 1]],
+  )
+  resetStats()
+  t.deepEqual(getStats(), [])
+})
+
+test('should only report external priority changes', async (t) => {
+  // restoring the priority requires privileges on Unix
+  if (!privileged) {
+    t.pass()
+    return
+  }
+  const previous = getPriority(0)
+  const changes: { previous: number; current: number }[] = []
+  const watcher = onExternalPriorityChange((change) => changes.push(change), { interval: 10 })
+  try {
+    setPriority(0, 10)
+    await new Promise((resolve) => setTimeout(resolve, 100))
+    t.deepEqual(changes, [])
+    execFileSync(process.execPath, ['-e', `require('node:os').setPriority(${process.pid}, 19)`])
+    while (changes.length === 0) {
+      await new Promise((resolve) => setTimeout(resolve, 10))
+    }
+    t.like(changes, [{ previous: 10, current: 19 }])
+  } finally {
+    watcher.stop()
+    setPriority(0, previous)
+  }
+})
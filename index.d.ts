@@ -1,5 +1,403 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/**
+ * This function adds `delta` to the nice value of the current process, and returns the new nice value. A higher nice value means a lower priority.
+ * The result is clamped to `-20..19`.
+ *
+ * On Unix, it reads the nice value with [`getpriority(2)`](https://linux.die.net/man/2/getpriority) and applies the new one with [`setpriority(2)`](https://linux.die.net/man/2/setpriority).
+ *
+ * On Windows, the calling thread priority is translated into a nice value, adjusted, and mapped back onto the nearest `WindowsThreadPriority`.
+ * Because there are only 7 thread priorities, small deltas may not change anything.
+ */
+export declare function adjustPriority(delta: number): number
+
+export interface AffinityOptions {
+  /** Whether the affinity of the calling thread or of the whole process is used, it defaults to `thread`. */
+  target?: 'thread' | 'process'
+  /** The process to use instead of the current one, `target` must be `process` or omitted. */
+  pid?: number
+  /** The native id of the thread to use instead of the calling one (see `getNativeThreadId`), `target` must be `thread` or omitted. */
+  tid?: number
+}
+
+export interface AudioThreadOptions {
+  /** On macOS, the time between two audio callbacks in nanoseconds, it defaults to `10000000` (10ms). */
+  periodNs?: number
+  /** On macOS, the CPU time needed by each callback in nanoseconds, it defaults to half of `periodNs`. */
+  computationNs?: number
+  /** On macOS, the time within which each callback must complete in nanoseconds, it defaults to `periodNs`. */
+  constraintNs?: number
+  /** On Windows, the MMCSS task to register with, it defaults to `Pro Audio`. */
+  taskName?: string
+  /**
+   * On Linux, the `SCHED_FIFO` priority between `1` and `99`, it defaults to `10`, which stays below the kernel threads.
+   * Without root or CAP_SYS_NICE, it's lowered to `RLIMIT_RTPRIO`.
+   */
+  rtPriority?: number
+}
+
+/**
+ * The audio priority of the calling thread, which lasts until `revert()` is called or the object is garbage collected, so keep a reference to it.
+ *
+ * It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+ */
+export declare class AudioThreadPriority {
+  /** Restores the scheduling of the thread, calling it more than once has no effect. */
+  revert(): void
+  /** Whether `revert()` has already been called. */
+  get reverted(): boolean
+}
+
+/**
+ * This function tells macOS that the process is performing a long-running task, so it isn't App Napped or timer-throttled while the app is in the background, and returns a token for `endActivity`.
+ * `options` are the names of the [`NSActivityOptions`](https://developer.apple.com/documentation/foundation/nsactivityoptions) to combine, and `reason` is shown in diagnostic tools.
+ *
+ * On macOS, it uses the [`beginActivityWithOptions:reason:`](https://developer.apple.com/documentation/foundation/nsprocessinfo/1415995-beginactivitywithoptions) method of `NSProcessInfo`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function beginActivity(options: Array<'idleDisplaySleepDisabled' | 'idleSystemSleepDisabled' | 'suddenTerminationDisabled' | 'automaticTerminationDisabled' | 'userInitiated' | 'userInitiatedAllowingIdleSystemSleep' | 'background' | 'latencyCritical'>, reason: string): number
+
+/**
+ * This function set the priority of the current process to `level`, and restores the previous priority automatically after `milliseconds`.
+ * The returned `Promise` resolves once the priority is restored, and rejects if restoring fails.
+ *
+ * The timer runs on a dedicated thread, so it doesn't occupy the libuv thread pool, and the priority is restored on the JavaScript thread, which is the thread that was boosted.
+ */
+export declare function boostFor(level: PriorityLevel, milliseconds: number): Promise<void>
+
+/**
+ * This function returns whether the current process is privileged enough to raise its priority above the normal one, so libraries can decide up front whether to even attempt a boost.
+ *
+ * On Linux, it checks whether the effective capabilities in `/proc/self/status` include [`CAP_SYS_NICE`](https://man7.org/linux/man-pages/man7/capabilities.7.html). Note that `RLIMIT_NICE` may still allow unprivileged processes to raise their priority a little, see `setHighestAllowed`.
+ *
+ * On other Unix platforms, it checks whether the process runs as root.
+ *
+ * On Windows, it checks whether the process is elevated or its account holds the `SeIncreaseBasePriorityPrivilege` privilege, which is required by the `Realtime` priority class.
+ */
+export declare function canRaisePriority(): boolean
+
+/**
+ * This function predicts whether `setPriority(0, value)` would succeed without changing anything, and explains why when it would not.
+ *
+ * On Unix, lowering the priority is always allowed, and raising it requires root or `CAP_SYS_NICE`. On Linux, unprivileged processes may still raise their nice value down to `20 - RLIMIT_NICE`.
+ *
+ * On Windows, every priority class except `Realtime` is allowed, `Realtime` requires the `SeIncreaseBasePriorityPrivilege` privilege to be enabled, otherwise `SetPriorityClass` silently falls back to `High`.
+ */
+export declare function canSetPriority(value: number): PriorityCheck
+
+/** The CPU bandwidth limit of a cgroup, the cgroup may use `quota` microseconds of CPU time every `period` microseconds. */
+export interface CgroupCpuMax {
+  /** The CPU time in microseconds, `undefined` means unlimited. */
+  quota?: number
+  /** The length of a period in microseconds. */
+  period: number
+}
+
+/**
+ * This function withdraws the timer resolution requested with `setTimerResolution`, and returns the resolution in effect in milliseconds.
+ * Calling it without a request has no effect.
+ *
+ * On Windows, it uses the `NtSetTimerResolution` function, the equivalent of [`timeEndPeriod`](https://learn.microsoft.com/en-us/windows/win32/api/timeapi/nf-timeapi-timeendperiod).
+ *
+ * On other platforms, it throws.
+ */
+export declare function clearTimerResolution(): number
+
+export interface ContextSwitches {
+  /** The context switches because a thread blocked, for example on I/O or a lock, `undefined` on Windows. */
+  voluntary?: number
+  /** The context switches because a thread was preempted, `undefined` on Windows. */
+  involuntary?: number
+  total: number
+  /** The context switches of every thread, when the `threads` option is set. */
+  threads?: Array<ThreadContextSwitches>
+}
+
+export interface ContextSwitchesOptions {
+  /** Whether to list the context switches of every thread of the current process as well, which is only supported on Linux and Windows. */
+  threads?: boolean
+}
+
+export interface CoreClasses {
+  /** The CPUs of the performance cores, every CPU on machines without hybrid CPUs. */
+  performance: Array<number>
+  /** The CPUs of the efficiency cores, empty on machines without hybrid CPUs. */
+  efficiency: Array<number>
+}
+
+export interface CpuCache {
+  level: number
+  kind: 'data' | 'instruction' | 'unified'
+  /** The size of the cache in bytes. */
+  size: number
+  /** The CPUs sharing this cache. */
+  cpus: Array<number>
+}
+
+export interface CpuPressure {
+  /** The time at least one runnable task waited for a CPU. */
+  some: PressureStats
+  /** The time all non-idle tasks waited for a CPU at once, only reported since Linux 5.13. */
+  full?: PressureStats
+}
+
+export interface CpuPressureOptions {
+  /**
+   * The time window of the threshold in milliseconds, between `500` and `10000`, it defaults to `2000`.
+   * Unprivileged processes can only use multiples of `2000`.
+   */
+  window?: number
+}
+
+/** A Windows CPU Set, which identifies a logical CPU independently of processor groups. */
+export interface CpuSet {
+  /** The id passed to `setThreadSelectedCpuSets` and `setProcessDefaultCpuSets`. */
+  id: number
+  group: number
+  /** The index of the CPU within `group`. */
+  logicalProcessorIndex: number
+  coreIndex: number
+  lastLevelCacheIndex: number
+  numaNodeIndex: number
+  /** Higher values mean more performant cores on hybrid CPUs. */
+  efficiencyClass: number
+  /** Whether the CPU is parked by the power manager. */
+  parked: boolean
+  /** Whether the CPU is allocated to a specific process. */
+  allocated: boolean
+  /** Whether the CPU is reserved for real-time work. */
+  realtime: boolean
+}
+
+export interface CpuTopology {
+  sockets: number
+  physicalCores: number
+  logicalCpus: number
+  numaNodes: number
+  cpus: Array<LogicalCpu>
+  caches: Array<CpuCache>
+}
+
+/** macOS disk I/O policies, see [`setiopolicy_np(3)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/setiopolicy_np.3.html). */
+export declare const enum DarwinIoPolicy {
+  /** Inherit the policy of the process, or of the system for a process. */
+  Default = 0,
+  /** The normal policy, I/O is not throttled. */
+  Important = 1,
+  /** Passive I/O doesn't throttle other I/O, for example for backups reading files. */
+  Passive = 2,
+  /** Throttled I/O, the macOS equivalent of the idle `ionice` class. */
+  Throttle = 3,
+  /** Throttled less aggressively than `Throttle`. */
+  Utility = 4,
+  Standard = 5
+}
+
+/** How much CPU the current process can really use. */
+export interface EffectiveCpuLimit {
+  /** The number of CPUs worth of bandwidth, which is the lower of `quota` and `cpuset` and may be fractional. */
+  cpus: number
+  /** The number of CPUs worth of bandwidth allowed by the CPU quota of the cgroup and its ancestors, `undefined` when unlimited. */
+  quota?: number
+  /** The number of CPUs the process may run on, which reflects the cpuset of the cgroup on Linux. */
+  cpuset: number
+}
+
+/** The result of `getEffectivePriorityRange`. */
+export interface EffectivePriorityRange {
+  /** The lowest nice value, which is the highest priority, that `setPriority(0, value)` can actually apply. */
+  min: number
+  /** The highest nice value, which is the lowest priority, that `setPriority(0, value)` can actually apply. */
+  max: number
+  /** The priority classes that `setProcessPriorityClass` can actually apply, only reported on Windows. */
+  priorityClasses?: Array<ProcessPriorityClass>
+  /**
+   * The nice value of the autogroup of the current process, only reported on Linux when autogroup is enabled.
+   * Nice values then only weigh threads against the other threads of the same session, while the session itself is weighed by this value.
+   */
+  autogroupNice?: number
+}
+
+/**
+ * This function gives the calling thread the scheduling audio work needs, composing the right pieces for each platform, and must be called on the audio thread, such as a worker thread.
+ *
+ * On Linux, it sets `SCHED_FIFO` with `SCHED_RESET_ON_FORK` and a conservative real-time priority, which is lowered to `RLIMIT_RTPRIO` without root or CAP_SYS_NICE, see `setScheduler`.
+ *
+ * On macOS, it sets the time-constraint policy, see `setRealtimeThreadPolicy`.
+ *
+ * On Windows, it registers the thread with MMCSS and disables the dynamic priority boost of the thread, see `MmcssTask` and `setThreadPriorityBoost`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function enableAudioThreadPriority(options?: AudioThreadOptions | undefined | null): AudioThreadPriority
+
+/**
+ * This function enables the `SeIncreaseBasePriorityPrivilege` privilege in the token of the current process, which is required before the `Realtime` priority class takes effect.
+ * It returns `false` when the account doesn't hold the privilege, in which case `Realtime` silently falls back to `High`.
+ *
+ * On Windows, it uses the [`AdjustTokenPrivileges`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-adjusttokenprivileges) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function enableIncreaseBasePriorityPrivilege(): boolean
+
+/**
+ * This function ends the activity identified by `token`, returned by `beginActivity`.
+ *
+ * On macOS, it uses the [`endActivity:`](https://developer.apple.com/documentation/foundation/nsprocessinfo/1411091-endactivity) method of `NSProcessInfo`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function endActivity(token: number): void
+
+/**
+ * This function lowers the CPU, I/O and memory priority of the calling thread at once, using the best mechanism of each platform.
+ * It returns `false` without changing anything when the thread is already in background mode.
+ *
+ * On Linux, it uses the `SCHED_IDLE` policy with [`sched_setscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_setscheduler.2.html) and the idle I/O class with [`ioprio_set(2)`](https://man7.org/linux/man-pages/man2/ioprio_set.2.html). Linux has no memory priority for threads.
+ *
+ * On macOS, it uses the [`setpriority(2)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/setpriority.2.html) function with `PRIO_DARWIN_THREAD` and `PRIO_DARWIN_BG`.
+ *
+ * On Windows, it uses the [`SetThreadPriority`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) function with `THREAD_MODE_BACKGROUND_BEGIN`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function enterBackgroundMode(): boolean
+
+/**
+ * This function restores the priorities the calling thread had before `enterBackgroundMode`.
+ * It returns `false` without changing anything when the thread is not in background mode.
+ *
+ * On Linux, leaving `SCHED_IDLE` requires `CAP_SYS_NICE` or a `RLIMIT_NICE` allowing the previous nice value, like raising the priority.
+ *
+ * On other platforms, it throws.
+ */
+export declare function exitBackgroundMode(): boolean
+
+/** This function formats CPU indexes as a Linux-style CPU list such as `0-3,8,10-11`, the reverse of `parseCpuList`. */
+export declare function formatCpuList(cpus: Array<number>): string
+
+export interface FunctionStats {
+  /** The JavaScript name of the function, such as `setPriority`. */
+  function: string
+  successes: number
+  failures: number
+  /** The most recent failure, `undefined` when the function never failed. */
+  lastError?: LastError
+}
+
+/**
+ * This function returns the CPUs the current process may actually run on, which differs from the CPU topology when running under `taskset`, cgroups or Kubernetes.
+ *
+ * On Linux, it reads `Cpus_allowed_list` from `/proc/self/status`, which also reflects the cpuset cgroup.
+ *
+ * On Windows and FreeBSD, it returns the affinity of the current process, see `getCpuAffinity`.
+ *
+ * On macOS, the CPUs can't be restricted, so it returns every logical CPU from the `hw.logicalcpu` `sysctl` value.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getAllowedCpus(): Array<number>
+
+/**
+ * This function get the CPU bandwidth limit of the cgroup of the process identified by `pid`, it defaults to the current process.
+ *
+ * On Linux, it reads the [`cpu.max`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCgroupCpuMax(pid?: number | undefined | null): CgroupCpuMax
+
+/**
+ * This function get the CPU weight of the cgroup of the process identified by `pid`, it defaults to the current process.
+ *
+ * On Linux, it reads the [`cpu.weight`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, or converts [`cpu.shares`](https://docs.kernel.org/scheduler/sched-design-CFS.html#group-scheduler-extensions-to-cfs) on cgroup v1 hosts.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCgroupCpuWeight(pid?: number | undefined | null): number
+
+/**
+ * This function returns whether the cgroup of the process identified by `pid` is idle, it defaults to the current process.
+ *
+ * On Linux, it reads the [`cpu.idle`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCgroupIdle(pid?: number | undefined | null): boolean
+
+/**
+ * This function returns the voluntary and involuntary context switches of the current process, and optionally of each of its threads, to diagnose scheduling thrash after priority changes.
+ * Many involuntary context switches mean the threads are preempted, for example by higher priority work or a tight CPU quota.
+ *
+ * On Unix, it uses [`getrusage(2)`](https://man7.org/linux/man-pages/man2/getrusage.2.html) with `RUSAGE_SELF`. On Linux, the threads are read from [`/proc/self/task/<tid>/status`](https://man7.org/linux/man-pages/man5/proc_pid_status.5.html).
+ *
+ * On Windows, it uses the [`NtQuerySystemInformation`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntquerysysteminformation) function with `SystemProcessInformation`, which only counts the context switches of the running threads, and doesn't tell voluntary and involuntary ones apart.
+ */
+export declare function getContextSwitches(options?: ContextSwitchesOptions | undefined | null): ContextSwitches
+
+/**
+ * This function splits the CPUs of hybrid machines into performance cores and efficiency cores.
+ *
+ * On Linux, it reads `/sys/devices/cpu_core/cpus` and `/sys/devices/cpu_atom/cpus` on Intel hybrid CPUs, and the `cpu_capacity` of every CPU on ARM big.LITTLE CPUs, where only the CPUs with the highest capacity are performance cores.
+ *
+ * On Windows, it uses the `EfficiencyClass` returned by the [`GetLogicalProcessorInformationEx`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getlogicalprocessorinformationex) function, where only the cores with the highest class are performance cores.
+ *
+ * On macOS, it uses the `hw.perflevel0` and `hw.perflevel1` `sysctl` values.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCoreClasses(): CoreClasses
+
+/**
+ * This function returns the CPUs the calling thread may run on.
+ * Pass `{ target: 'process' }` to use the whole process, `{ pid }` to use another process, or `{ tid }` to use another thread.
+ *
+ * On Linux, it uses the [`sched_getaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_getaffinity.2.html) function. For a process, the affinity of its main thread is returned.
+ *
+ * On Windows, it uses the [`GetProcessAffinityMask`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getprocessaffinitymask) function. The affinity of a thread is read with the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadBasicInformation`, without changing it.
+ *
+ * On FreeBSD, it uses the [`cpuset_getaffinity(2)`](https://man.freebsd.org/cgi/man.cgi?query=cpuset_getaffinity) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCpuAffinity(options?: AffinityOptions | undefined | null): Array<number>
+
+/**
+ * This function returns how much the tasks of the system wait for a CPU, so adaptive schedulers can renice their workers when the system is overloaded.
+ *
+ * On Linux, it reads [`/proc/pressure/cpu`](https://docs.kernel.org/accounting/psi.html), which requires a kernel built with `CONFIG_PSI`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCpuPressure(): CpuPressure
+
+/**
+ * This function returns the sockets, physical cores, logical CPUs, caches and NUMA nodes of the machine.
+ *
+ * On Linux, it reads `/sys/devices/system/cpu` and `/sys/devices/system/node`.
+ *
+ * On Windows, it uses the [`GetLogicalProcessorInformationEx`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getlogicalprocessorinformationex) function with `RelationAll`.
+ *
+ * On macOS, it uses the `hw.*` [`sysctl`](https://developer.apple.com/documentation/kernel/1387446-sysctlbyname) values, which don't say which CPU belongs to which core, so CPUs are assumed to be numbered core by core.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCpuTopology(): CpuTopology
+
+/**
+ * This function returns the CPU the calling thread is running on, to verify that pinning took effect or to shard work per CPU.
+ * The thread may be moved to another CPU right after the call unless it is pinned.
+ *
+ * On Linux and FreeBSD, it uses the [`sched_getcpu(3)`](https://man7.org/linux/man-pages/man3/sched_getcpu.3.html) function.
+ *
+ * On Windows, it uses the [`GetCurrentProcessorNumberEx`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getcurrentprocessornumberex) function, and returns `group * bits + index within the group`, where `bits` is the number of bits of a processor group mask (64 on 64-bit Windows, 32 on 32-bit Windows), like `getCpuTopology`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getCurrentCpu(): number
+
 /**
  * This function get the priority of the current process.
  * On Unix, it uses the [`getpriority(2)`](https://linux.die.net/man/2/getpriority).
@@ -25,22 +423,1752 @@
 export declare function getCurrentProcessPriority(): number
 
 /**
- * This function set the priority of the current process.
- * On Unix, it uses the [`nice`](https://linux.die.net/man/2/nice) function.
+ * This function get the disk I/O policy of the current process or of the calling thread.
  *
- * On Windows, it uses the [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) function.
+ * On macOS, it uses the [`getiopolicy_np(3)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/getiopolicy_np.3.html) function with `IOPOL_TYPE_DISK`.
+ *
+ * On other platforms, it throws.
  */
-export declare function nice(incr?: number | undefined | null): number
+export declare function getDarwinIoPolicy(scope: 'thread' | 'process'): DarwinIoPolicy
 
-export declare const enum WindowsThreadPriority {
-  ThreadModeBackgroundBegin = 65536,
-  ThreadModeBackgroundEnd = 131072,
-  ThreadPriorityAboveNormal = 1,
-  ThreadPriorityBelowNormal = -1,
-  ThreadPriorityHighest = 2,
-  ThreadPriorityIdle = -15,
-  ThreadPriorityLowest = -2,
-  ThreadPriorityNormal = 0,
-  ThreadPriorityTimeCritical = 15
-}
+/**
+ * This function returns how many CPUs worth of bandwidth the current process really has, which is what thread pools should be sized by inside containers.
+ *
+ * On Linux, it combines the CPU quota of the cgroup and its ancestors, from `cpu.max` on cgroup v2 and `cpu.cfs_quota_us` / `cpu.cfs_period_us` on cgroup v1, with the CPUs the process may run on, which reflects the cpuset of the cgroup.
+ *
+ * On other platforms, there is no quota, and `cpuset` is the number of CPUs available to the process.
+ */
+export declare function getEffectiveCpuLimit(): EffectiveCpuLimit
+
+/**
+ * This function returns the range of nice values the current process can actually apply, considering the rlimits and privileges, so callers don't have to discover it by trial and error.
+ *
+ * On Unix, the range ends at `19`. It starts at `-20` with root or `CAP_SYS_NICE`, at `20 - RLIMIT_NICE` on Linux, and at the current nice value elsewhere. On Linux, it also reports the nice value of the [autogroup](https://man7.org/linux/man-pages/man7/sched.7.html) when it's enabled.
+ *
+ * On Windows, it also reports the settable priority classes, `Realtime` is only included when `SeIncreaseBasePriorityPrivilege` is enabled.
+ */
+export declare function getEffectivePriorityRange(): EffectivePriorityRange
+
+/**
+ * This function get the I/O scheduling class and level of the calling thread.
+ * Pass `{ pid }` to use the main thread of another process, or `{ pgid }` to use the highest priority of a process group.
+ *
+ * On Linux, it uses the [`ioprio_get(2)`](https://man7.org/linux/man-pages/man2/ioprio_get.2.html) syscall.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getIoPriority(options?: IoPriorityOptions | undefined | null): IoPriority
+
+/**
+ * This function get the latency nice value of the calling thread, or `null` when the kernel doesn't support latency nice.
+ *
+ * On Linux, it uses the [`sched_getattr(2)`](https://man7.org/linux/man-pages/man2/sched_getattr.2.html) syscall.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getLatencyNice(): number | null
+
+/**
+ * This function renders the scheduling state of the current process and the calling thread, and the number of successful and failed calls to the functions changing priorities, in the [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/), so it can be served on a `/metrics` endpoint as is.
+ *
+ * The scheduling state comes from `getSchedulingSnapshot`, and the metrics of the fields which are `null` on the current platform are omitted.
+ * The call counters are the same as `getStats`, they're cumulative since the addon was loaded or `resetStats` was called, and cover the same functions.
+ */
+export declare function getMetricsText(): string
+
+/**
+ * This function get the native id of the calling thread, which can be passed to other threads for targeted renicing.
+ *
+ * On Linux, it uses the `gettid` syscall.
+ *
+ * On macOS, it uses the `pthread_threadid_np` function.
+ *
+ * On FreeBSD, it uses the [`pthread_getthreadid_np(3)`](https://man.freebsd.org/cgi/man.cgi?query=pthread_getthreadid_np) function.
+ *
+ * On Windows, it uses the [`GetCurrentThreadId`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getcurrentthreadid) function.
+ */
+export declare function getNativeThreadId(): number
+
+/**
+ * This function get the priority of the current process on a normalized scale, from `0` (the lowest priority) to `100` (the highest priority).
+ *
+ * On Unix, the nice value `19..-20` is mapped linearly onto `0..100`.
+ *
+ * On Windows, the process priority class is translated into a nice value the same way as `getPriority`, then mapped the same way.
+ */
+export declare function getNormalizedPriority(): number
+
+/**
+ * This function get the OOM score of the process identified by `pid`, it defaults to the current process.
+ * The process with the highest score is killed first when the system runs out of memory, the score ranges from `0` to `2000` and includes the OOM score adjustment.
+ *
+ * On Linux, it reads [`/proc/<pid>/oom_score`](https://man7.org/linux/man-pages/man5/proc_pid_oom_score.5.html).
+ *
+ * On other platforms, it throws.
+ */
+export declare function getOomScore(pid?: number | undefined | null): number
+
+/**
+ * This function get the OOM score adjustment of the process identified by `pid`, it defaults to the current process.
+ *
+ * On Linux, it reads [`/proc/<pid>/oom_score_adj`](https://man7.org/linux/man-pages/man5/proc_pid_oom_score_adj.5.html).
+ *
+ * On other platforms, it throws.
+ */
+export declare function getOomScoreAdj(pid?: number | undefined | null): number
+
+/**
+ * This function describes how `nice` behaves on the current platform, so callers can adapt to it programmatically.
+ *
+ * On Linux, nice values belong to threads, so `nice` changes the calling thread. On other Unix platforms it changes the process. In both cases the increment is relative and the nice values range from `-20` to `19`.
+ *
+ * On Windows, `nice` sets the absolute `WindowsThreadPriority` of the calling thread, from `-15` to `15`.
+ */
+export declare function getPlatformPriorityInfo(): PlatformPriorityInfo
+
+/**
+ * This function returns where the machine gets its power from, so background services can lower their priority on battery.
+ *
+ * On Linux, it reads `/sys/class/power_supply`, the source is `Battery` when the machine has a battery and no online mains or USB supply.
+ *
+ * On macOS, it uses the [`IOPSGetProvidingPowerSourceType`](https://github.com/apple-oss-distributions/IOKitUser/blob/main/ps.subproj/IOPowerSources.h) function.
+ *
+ * On Windows, it uses the [`GetSystemPowerStatus`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getsystempowerstatus) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getPowerSource(): PowerSource
+
+/**
+ * This function get the priority of the process identified by `pid`.
+ * `pid` `0` means the current process.
+ *
+ * On Unix, it uses the [`getpriority(2)`](https://linux.die.net/man/2/getpriority) function with `PRIO_PROCESS`, and returns the nice value.
+ *
+ * On Windows, it uses the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function.
+ * The priority class is mapped back to a nice value the same way as Node.js `os.getPriority`.
+ */
+export declare function getPriority(pid: number): number
+
+/**
+ * This function get the priority of the current process as a cross-platform `PriorityLevel`.
+ *
+ * On Unix, the nice value is mapped onto the nearest level.
+ *
+ * On Windows, the level is derived from the process priority class and the calling thread priority.
+ */
+export declare function getPriorityLevel(): PriorityLevel
+
+/**
+ * This function returns the number of CPU clock cycles consumed by all the threads of the process identified by `pid`, including the terminated ones, it defaults to the current process.
+ *
+ * On Windows, it uses the [`QueryProcessCycleTime`](https://learn.microsoft.com/en-us/windows/win32/api/realtimeapiset/nf-realtimeapiset-queryprocesscycletime) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getProcessCycleTime(pid?: number | undefined | null): number
+
+/**
+ * This function returns the default CPU Sets of the current process, an empty list means there are none.
+ *
+ * On Windows, it uses the [`GetProcessDefaultCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocessdefaultcpusets) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getProcessDefaultCpuSets(): Array<number>
+
+/**
+ * This function get the priority of the process group `pgid`, which is the highest priority (lowest nice value) of any process in the group.
+ * `pgid` `0` means the process group of the current process.
+ *
+ * On Unix, it uses the [`getpriority(2)`](https://linux.die.net/man/2/getpriority) function with `PRIO_PGRP`.
+ *
+ * Windows doesn't have process groups, this function always throws on Windows.
+ */
+export declare function getProcessGroupPriority(pgid: number): number
+
+/**
+ * This function get the I/O priority hint of the current process.
+ *
+ * On Windows, it uses the [`NtQueryInformationProcess`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationprocess) function with `ProcessIoPriority`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getProcessIoPriority(): WindowsIoPriority
+
+/**
+ * This function returns the processor groups of the machine.
+ *
+ * On Windows, it uses the [`GetLogicalProcessorInformationEx`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getlogicalprocessorinformationex) function with `RelationGroup`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getProcessorGroups(): Array<ProcessorGroup>
+
+/**
+ * This function returns whether the dynamic priority boost is enabled for the process identified by `pid`, it defaults to the current process.
+ *
+ * On Windows, it uses the [`GetProcessPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocesspriorityboost) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getProcessPriorityBoost(pid?: number | undefined | null): boolean
+
+/**
+ * This function get the priority class of the process identified by `pid`, it defaults to the current process.
+ *
+ * On Windows, it uses the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function.
+ *
+ * On Unix, the nice value of the process is mapped onto the nearest priority class.
+ */
+export declare function getProcessPriorityClass(pid?: number | undefined | null): ProcessPriorityClass
+
+/**
+ * This function get the QoS class of the calling thread.
+ *
+ * On macOS, it uses the [`pthread_get_qos_class_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getQosClass(): QosClass
+
+/**
+ * This function returns the CPU time consumed by the current process, the calling thread, or the terminated and waited-for children of the current process, so callers can check that lowering the priority actually reduced the CPU consumption.
+ * `scope` is `self`, `thread` or `children`.
+ *
+ * On Unix, it uses [`getrusage(2)`](https://man7.org/linux/man-pages/man2/getrusage.2.html) with `RUSAGE_SELF`, `RUSAGE_THREAD` or `RUSAGE_CHILDREN`. On macOS, which doesn't have `RUSAGE_THREAD`, the `thread` scope uses the [`thread_info`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/thread_info.h) function with `THREAD_BASIC_INFO`.
+ *
+ * On Windows, it uses the [`GetProcessTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocesstimes) and [`GetThreadTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadtimes) functions, the `children` scope throws.
+ */
+export declare function getResourceUsage(scope: 'self' | 'thread' | 'children'): ResourceUsage
+
+/**
+ * This function returns the `RoundRobin` time slice of the process identified by `pid` in nanoseconds, `pid` defaults to the current process.
+ *
+ * On Linux, it uses the [`sched_rr_get_interval(2)`](https://man7.org/linux/man-pages/man2/sched_rr_get_interval.2.html) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getRoundRobinInterval(pid?: number | undefined | null): number
+
+/**
+ * This function returns the scheduler statistics of the calling thread, or of the thread identified by the native thread id `tid`, to quantify how much a low priority thread is starved.
+ * Comparing `waitTime` with `runTime` over an interval tells how long the thread waited for a CPU for each unit of CPU time it got.
+ *
+ * On Linux, it reads [`/proc/<tid>/schedstat`](https://docs.kernel.org/scheduler/sched-stats.html#proc-pid-schedstat), which requires a kernel built with `CONFIG_SCHED_INFO`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getSchedStats(tid?: number | undefined | null): SchedStats
+
+/**
+ * This function get the scheduling policy and the real-time priority of the calling thread, or of the thread identified by the native thread id `tid`.
+ *
+ * On Linux, it uses the [`sched_getscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_getscheduler.2.html) and [`sched_getparam(2)`](https://man7.org/linux/man-pages/man2/sched_getparam.2.html) functions.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getScheduler(tid?: number | undefined | null): SchedulerInfo
+
+/**
+ * This function returns the valid real-time priorities of `policy`, so they can be validated before calling `setScheduler`.
+ *
+ * On Linux, it uses the [`sched_get_priority_min(2)`](https://man7.org/linux/man-pages/man2/sched_get_priority_min.2.html) and [`sched_get_priority_max(2)`](https://man7.org/linux/man-pages/man2/sched_get_priority_max.2.html) functions.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getSchedulerPriorityRange(policy: SchedulerPolicy): PriorityRange
+
+/**
+ * This function returns the scheduling state of the current process and the calling thread in a single call, which is suitable for bug reports and telemetry.
+ * Only reading the nice value may throw, the other fields are `null` when they can't be read.
+ *
+ * On Linux, it combines `getScheduler`, `getLatencyNice`, `getCpuAffinity`, `getIoPriority`, `getOomScoreAdj`, `getCgroupCpuWeight` and [`getrlimit(2)`](https://man7.org/linux/man-pages/man2/getrlimit.2.html).
+ *
+ * On macOS, it adds `getQosClass`.
+ *
+ * On Windows, it adds `getProcessIoPriority` and the [`GetProcessInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocessinformation) function with `ProcessMemoryPriority`.
+ */
+export declare function getSchedulingSnapshot(): SchedulingSnapshot
+
+/**
+ * This function returns the whole scheduling state of the calling thread in a single call.
+ *
+ * On Linux, it combines [`getpriority(2)`](https://linux.die.net/man/2/getpriority), [`sched_getscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_getscheduler.2.html), [`sched_getaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_getaffinity.2.html) and [`ioprio_get(2)`](https://man7.org/linux/man-pages/man2/ioprio_get.2.html).
+ *
+ * On other platforms, only `nice` is available, the other fields are `null`.
+ */
+export declare function getSchedulingState(): SchedulingState
+
+/**
+ * This function returns how many times each function changing priorities succeeded and failed since the addon was loaded or `resetStats` was called, and its last error, so libraries can report how often priority changes are rejected in production.
+ * Only the functions which were called are returned, sorted by name, and every function, constructor and method setting priorities, affinities, cgroups or job limits is counted under the name it was called with, such as `nice`, `PriorityGuard` or `JobObject.setLimits`, even when it's implemented on top of another one, e.g. `nice('idle')` only counts as `nice`.
+ * The methods undoing a previous change, such as `restore()` or `revert()`, and `beginActivity` are not counted.
+ * The counters are shared by every thread of the process, including worker threads.
+ */
+export declare function getStats(): Array<FunctionStats>
+
+/**
+ * This function returns the CPU Sets of the machine.
+ *
+ * On Windows, it uses the [`GetSystemCpuSetInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getsystemcpusetinformation) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getSystemCpuSets(): Array<CpuSet>
+
+/**
+ * This function returns the 1, 5 and 15 minutes load averages of the system, so adaptive schedulers have the same signal on every platform, unlike `getCpuPressure` which is only available on Linux.
+ *
+ * On Unix, it uses the [`getloadavg(3)`](https://man7.org/linux/man-pages/man3/getloadavg.3.html) function, and the averages count the runnable tasks, plus the tasks in uninterruptible sleep on Linux.
+ *
+ * On Windows, which has no load average, it samples the [`GetSystemTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getsystemtimes) function every 5 seconds in a background thread started by the first call, and averages the number of busy CPUs the same way. The averages start from the CPU utilization since boot, and can't exceed the number of CPUs.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getSystemLoad(): SystemLoad
+
+/**
+ * This function returns how hot the machine is, so apps can lower their priority or shed load before the system throttles them.
+ *
+ * On Linux, it compares the temperature of each `/sys/class/thermal/thermal_zone*` with its `passive` (`Fair`), `hot` (`Serious`) and `critical` trip points, it's always `Nominal` without thermal zones.
+ *
+ * On macOS, it uses the [`thermalState`](https://developer.apple.com/documentation/foundation/processinfo/thermalstate) property of `NSProcessInfo`.
+ *
+ * On Windows, it uses the [`CallNtPowerInformation`](https://learn.microsoft.com/en-us/windows/win32/api/powerbase/nf-powerbase-callntpowerinformation) function with `SystemPowerInformation`, the state is `Serious` when the system cools down by throttling the CPUs and `Nominal` otherwise.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThermalState(): ThermalState
+
+/**
+ * This function get the affinity tag of the calling thread, `0` means there is no tag.
+ *
+ * On macOS, it uses the [`thread_policy_get`](https://developer.apple.com/library/archive/releasenotes/Performance/RN-AffinityAPI/) function with `THREAD_AFFINITY_POLICY`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadAffinityTag(): number
+
+/**
+ * This function returns the number of CPU clock cycles consumed by the thread identified by `tid`, it defaults to the calling thread.
+ * Unlike `getResourceUsage`, which is updated on clock ticks, it's cycle-accurate, so it can verify the effect of priority and EcoQoS changes on short workloads. Cycles are not comparable across machines, and they depend on the CPU frequency.
+ *
+ * On Windows, it uses the [`QueryThreadCycleTime`](https://learn.microsoft.com/en-us/windows/win32/api/realtimeapiset/nf-realtimeapiset-querythreadcycletime) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadCycleTime(tid?: number | undefined | null): number
+
+/**
+ * This function returns the processor group of the calling thread, and the CPUs it may run on within that group.
+ *
+ * On Windows, it uses the [`GetThreadGroupAffinity`](https://learn.microsoft.com/en-us/windows/win32/api/processtopologyapi/nf-processtopologyapi-getthreadgroupaffinity) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadGroupAffinity(): GroupAffinity
+
+/**
+ * This function returns the ideal processor of the calling thread.
+ *
+ * On Windows, it uses the [`GetThreadIdealProcessorEx`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadidealprocessorex) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadIdealProcessor(): ProcessorNumber
+
+/**
+ * This function get the I/O priority hint of the calling thread.
+ *
+ * On Windows, it uses the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadIoPriority`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadIoPriority(): WindowsIoPriority
+
+/**
+ * This function returns whether the dynamic priority boost is enabled for the thread identified by `tid`, it defaults to the calling thread.
+ *
+ * On Windows, it uses the [`GetThreadPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadpriorityboost) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadPriorityBoost(tid?: number | undefined | null): boolean
+
+/**
+ * This function returns whether the dynamic priority boost is enabled, whether background processing mode is on, and the base and current priorities of the thread identified by `tid`, it defaults to the calling thread.
+ * The relative priority returned by `GetThreadPriority` alone hides the priority class of the process, the dynamic boosts and the background mode.
+ *
+ * On Windows, it uses the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadBasicInformation`, `ThreadIoPriority` and `ThreadPagePriority`, the [`NtQueryInformationProcess`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationprocess) function with `ProcessBasicInformation` and the [`GetThreadPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadpriorityboost) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadSchedulingInfo(tid?: number | undefined | null): ThreadSchedulingInfo
+
+/**
+ * This function returns the CPU Sets selected for the calling thread, an empty list means there is no selection.
+ *
+ * On Windows, it uses the [`GetThreadSelectedCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadselectedcpusets) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getThreadSelectedCpuSets(): Array<number>
+
+/**
+ * This function returns the supported and current timer resolutions in milliseconds.
+ *
+ * On Windows, it uses the `NtQueryTimerResolution` function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getTimerResolution(): TimerResolution
+
+/**
+ * This function get the timer slack of the calling thread in nanoseconds.
+ *
+ * On Linux, it uses the [`prctl(2)`](https://man7.org/linux/man-pages/man2/prctl.2.html) function with `PR_GET_TIMERSLACK`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function getTimerSlack(): number
+
+/** This function returns the table used by `niceToWindowsThreadPriority` and `windowsThreadPriorityToNice`, ordered from the highest to the lowest priority. */
+export declare function getWindowsThreadPriorityMapping(): Array<WindowsThreadPriorityMapping>
+
+export interface GroupAffinity {
+  group: number
+  /** The indexes of the CPUs within `group`. */
+  cpus: Array<number>
+}
+
+export interface IoPriority {
+  class: IoPriorityClass
+  /** From `0` (the highest priority) to `7`, only meaningful for the `Realtime` and `BestEffort` classes. */
+  level: number
+}
+
+/** Linux I/O scheduling classes, see [`ioprio_set(2)`](https://man7.org/linux/man-pages/man2/ioprio_set.2.html). */
+export declare const enum IoPriorityClass {
+  /** No class was set, the I/O priority is derived from the nice value. */
+  None = 0,
+  /** Real-time I/O, served first regardless of other processes. */
+  Realtime = 1,
+  /** The default class, with levels from `0` (the highest priority) to `7`. */
+  BestEffort = 2,
+  /** Only served when no other process needs the disk. */
+  Idle = 3
+}
+
+export interface IoPriorityOptions {
+  /** The process to use instead of the calling thread, every thread of the process is updated. */
+  pid?: number
+  /** The process group to use instead of the calling thread, `0` means the process group of the current process. */
+  pgid?: number
+}
+
+/**
+ * This function returns whether the calling thread is in background mode, whether it was entered with `enterBackgroundMode` or by other code, such as native addons, so callers can avoid entering it twice, which fails on Windows.
+ *
+ * On Linux, it checks whether the policy is `SCHED_IDLE` with [`sched_getscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_getscheduler.2.html).
+ *
+ * On macOS, it checks the [`getpriority(2)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/getpriority.2.html) function with `PRIO_DARWIN_THREAD`, and whether the QoS class is `Background` with the [`pthread_get_qos_class_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) function.
+ *
+ * On Windows, it uses the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadIoPriority` and `ThreadPagePriority`, because the background processing mode can't be queried directly, it's reported when both are very low.
+ *
+ * On other platforms, it throws.
+ */
+export declare function isInBackgroundMode(): boolean
+
+export interface JitterOptions {
+  /** How long to measure in milliseconds, it defaults to `1000`. */
+  durationMs?: number
+  /** The period of the timing loop in microseconds, it defaults to `1000`. */
+  intervalUs?: number
+}
+
+/** The I/O rate limits of a job on every volume, `undefined` means no limit. */
+export interface JobIoRateLimits {
+  /** The maximum I/O operations per second of the job, normalized to 8 KiB operations. */
+  maxIops?: number
+  /** The maximum bytes per second the job can read and write. */
+  maxBandwidth?: number
+  /** The I/O operations per second reserved for the job. */
+  reservationIops?: number
+}
+
+/** The scheduling limits of a job, every process in the job is constrained to them, `undefined` means no limit. */
+export interface JobLimits {
+  /** The priority class of every process in the job, the processes can't change it. */
+  priorityClass?: ProcessPriorityClass
+  /**
+   * The scheduling class between `0` and `9`, which sets the length of the time slices of the threads in the job relative to other jobs, `5` is the default.
+   * Classes above `5` require the `SeIncreaseBasePriorityPrivilege` privilege.
+   */
+  schedulingClass?: number
+  /** The CPUs the processes in the job may run on, it must be a subset of the CPUs of the current processor group. */
+  affinity?: Array<number>
+}
+
+/** The memory limits of a job in bytes, `undefined` means no limit. */
+export interface JobMemoryLimits {
+  /** The memory each process in the job can commit, allocations beyond it fail. */
+  processMemory?: number
+  /** The memory all the processes in the job can commit together, allocations beyond it fail. */
+  jobMemory?: number
+  /** The minimum working set of each process in the job, it must be set together with `maxWorkingSet`. */
+  minWorkingSet?: number
+  /** The maximum working set of each process in the job, pages beyond it are trimmed to the page file rather than failing allocations. */
+  maxWorkingSet?: number
+}
+
+/**
+ * A Windows [Job Object](https://learn.microsoft.com/en-us/windows/win32/procthread/job-objects), which constrains every process assigned to it, including the child processes they launch.
+ * The job is closed when the object is garbage collected, the limits still apply to the processes in the job until they exit.
+ *
+ * On other platforms, the constructor throws.
+ */
+export declare class JobObject {
+  /**
+   * Creates an anonymous job, or the job named `name` which other processes can open.
+   *
+   * On Windows, it uses the [`CreateJobObjectW`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-createjobobjectw) function.
+   */
+  constructor(name?: string | undefined | null)
+  /**
+   * Assigns the process identified by `pid` to the job, it defaults to the current process.
+   * A process can't leave a job, and child processes launched afterwards are assigned to the job as well.
+   *
+   * On Windows, it uses the [`AssignProcessToJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-assignprocesstojobobject) function.
+   */
+  assignProcess(pid?: number | undefined | null): void
+  /**
+   * Replaces the priority class, scheduling class and affinity limits of the job, the other limits are kept.
+   *
+   * On Windows, it uses the [`SetInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setinformationjobobject) function with `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`.
+   */
+  setLimits(limits: JobLimits): void
+  /**
+   * Returns the priority class, scheduling class and affinity limits of the job.
+   *
+   * On Windows, it uses the [`QueryInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryinformationjobobject) function with `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`.
+   */
+  getLimits(): JobLimits
+  /**
+   * Replaces the memory limits of the job, the other limits are kept.
+   *
+   * On Windows, it uses the [`SetInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setinformationjobobject) function with [`JOBOBJECT_EXTENDED_LIMIT_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_extended_limit_information).
+   */
+  setMemoryLimits(limits: JobMemoryLimits): void
+  /**
+   * Returns the memory limits of the job.
+   *
+   * On Windows, it uses the [`QueryInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryinformationjobobject) function with `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`.
+   */
+  getMemoryLimits(): JobMemoryLimits
+  /**
+   * Replaces the I/O rate limits of the job on every volume, passing no limit disables the I/O rate control.
+   *
+   * On Windows, it uses the [`SetIoRateControlInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setioratecontrolinformationjobobject) function, which requires Windows 10 and a job that is not nested in a job with I/O rate control.
+   */
+  setIoRateLimits(limits: JobIoRateLimits): void
+  /**
+   * Returns the I/O rate limits of the job on every volume.
+   *
+   * On Windows, it uses the [`QueryIoRateControlInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryioratecontrolinformationjobobject) function.
+   */
+  getIoRateLimits(): JobIoRateLimits
+}
+
+export interface LastError {
+  message: string
+  /** The status of the error, such as `GenericFailure` or `InvalidArg`, the same as the `code` of the thrown error. */
+  code: string
+  /** When the error happened, in milliseconds since the Unix epoch. */
+  timestamp: number
+}
+
+/**
+ * This function lists the threads of the process identified by `pid` with their priorities, it defaults to the current process, so users can see exactly which threads are running hot.
+ * Threads which exit while they are listed are skipped.
+ *
+ * On Linux, it reads [`/proc/<pid>/task/<tid>/stat`](https://man7.org/linux/man-pages/man5/proc_pid_stat.5.html).
+ *
+ * On macOS, it uses the [`task_threads`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/task.defs) and [`thread_info`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/thread_info.h) functions, only the current process is supported.
+ *
+ * On Windows, it enumerates the threads with [`CreateToolhelp32Snapshot`](https://learn.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-createtoolhelp32snapshot) and reads them with [`GetThreadPriority`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadpriority), [`GetThreadDescription`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreaddescription) and [`GetThreadTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadtimes).
+ *
+ * On other platforms, it throws.
+ */
+export declare function listThreads(pid?: number | undefined | null): Array<ThreadInfo>
+
+/**
+ * This function drops the ability of the calling thread to raise the priority above the current one, so sandboxed plugin hosts can stop the JavaScript code they load afterwards from promoting itself to realtime. It can't be undone.
+ *
+ * On Linux, it lowers [`RLIMIT_NICE`](https://man7.org/linux/man-pages/man2/getrlimit.2.html) to the current nice value and `RLIMIT_RTPRIO` to the current realtime priority for the whole process, and removes `CAP_SYS_NICE` from the calling thread with `capset(2)`.
+ * With `CAP_SETPCAP`, it also removes `CAP_SYS_NICE` from the bounding set with [`PR_CAPBSET_DROP`](https://man7.org/linux/man-pages/man2/PR_CAPBSET_DROP.2const.html) and locks `SECBIT_NO_CAP_AMBIENT_RAISE`, so executing a setuid or file capability binary can't bring it back.
+ * Capabilities, the bounding set and securebits belong to threads: threads created afterwards by the calling thread inherit the restriction, but threads which already exist, such as the libuv thread pool or other worker threads, keep `CAP_SYS_NICE`, and native code running on them can still bypass the limits.
+ *
+ * On Windows, it removes the `SeIncreaseBasePriorityPrivilege` privilege from the process token with the [`AdjustTokenPrivileges`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-adjusttokenprivileges) function, so the `Realtime` priority class falls back to `High`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function lockPriorityCeiling(): void
+
+export interface LogicalCpu {
+  /** The CPU index used by `setCpuAffinity`, on Windows it is `group * bits + index within the group`, where `bits` is the number of bits of a processor group mask (64 on 64-bit Windows, 32 on 32-bit Windows). */
+  cpu: number
+  /** The index of the physical core in `CpuTopology.physicalCores`, shared by SMT siblings. */
+  core: number
+  /** The index of the socket (package). */
+  socket: number
+  numaNode: number
+}
+
+/**
+ * This function runs a timing loop on the calling thread, at its current priority and scheduling policy, which sleeps until the start of each `intervalUs` period for `durationMs`, and reports how late it woke up, like [`cyclictest`](https://wiki.linuxfoundation.org/realtime/documentation/howto/tools/cyclictest/start).
+ * It verifies whether a realtime setup actually works on a given machine: under load, a real-time policy should keep `p99` and `max` low while `Other` degrades.
+ *
+ * It blocks the calling thread for `durationMs`, so it's meant to run in a worker thread or a diagnostic script. The latencies include the timer slack on Linux, see `setTimerSlack`, and the timer resolution on Windows, see `setTimerResolution`.
+ */
+export declare function measureSchedulingJitter(options?: JitterOptions | undefined | null): SchedulingJitter
+
+/** The priority of a thread relative to the other threads of the same MMCSS task, the values are the `AVRT_PRIORITY_*` constants. */
+export declare const enum MmcssPriority {
+  VeryLow = -2,
+  Low = -1,
+  Normal = 0,
+  High = 1,
+  Critical = 2
+}
+
+/**
+ * Registers the calling thread with the [Multimedia Class Scheduler Service](https://learn.microsoft.com/en-us/windows/win32/procthread/multimedia-class-scheduler-service), which boosts audio and video threads without starving the system the way `ThreadPriorityTimeCritical` does.
+ * The registration lasts until `revert()` is called or the object is garbage collected, and it must be created on the thread to register, such as a worker thread.
+ *
+ * It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+ *
+ * On Windows, it uses the [`AvSetMmThreadCharacteristicsW`](https://learn.microsoft.com/en-us/windows/win32/api/avrt/nf-avrt-avsetmmthreadcharacteristicsw), [`AvSetMmThreadPriority`](https://learn.microsoft.com/en-us/windows/win32/api/avrt/nf-avrt-avsetmmthreadpriority) and [`AvRevertMmThreadCharacteristics`](https://learn.microsoft.com/en-us/windows/win32/api/avrt/nf-avrt-avrevertmmthreadcharacteristics) functions.
+ *
+ * On other platforms, the constructor throws.
+ */
+export declare class MmcssTask {
+  /** `taskName` is one of the tasks under `HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks`, such as `Audio`, `Capture`, `Games`, `Playback` or `Pro Audio`, it defaults to `Pro Audio`. */
+  constructor(taskName?: string | undefined | null)
+  /** Sets the priority of the thread relative to the other threads of the same task. */
+  setPriority(priority: MmcssPriority): void
+  /** Unregisters the thread from the task, calling it more than once has no effect. */
+  revert(): void
+  /** The index of the task, which identifies the registration to MMCSS. */
+  get taskIndex(): number
+  /** Whether `revert()` has already been called. */
+  get reverted(): boolean
+}
+
+/**
+ * This function moves the process identified by `pid` into the cgroup at `path`, it defaults to the current process, so a supervisor can group its workers under different weights.
+ * `path` is either an absolute path in the cgroup v2 mount, such as `/sys/fs/cgroup/app/workers`, or a path relative to the cgroup of the current process, such as `workers`. The cgroup must already exist, it can be created with `fs.mkdirSync`.
+ *
+ * On Linux, it writes the pid to the [`cgroup.procs`](https://docs.kernel.org/admin-guide/cgroup-v2.html#core-interface-files) file of the cgroup v2 hierarchy, which requires write access to the `cgroup.procs` of the common ancestor of both cgroups, which is the case when it's delegated to the current user.
+ *
+ * On other platforms, it throws.
+ */
+export declare function moveToCgroup(path: string, pid?: number | undefined | null): void
+
+/**
+ * This function set the priority of the current process.
+ * On Unix, it uses the [`nice`](https://linux.die.net/man/2/nice) function.
+ *
+ * On Windows, it uses the [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) function.
+ * Pass `{ mapNiceValue: true }` to use nice values on Windows as well.
+ *
+ * This function is kept for compatibility, it is relative on Unix but absolute on Windows. Prefer `adjustPriority` and `setAbsolutePriority`, which behave the same on every platform.
+ *
+ * Pass the camelCase name of a `PriorityLevel`, such as `'belowNormal'`, to set that level the same way as `setPriorityLevel`, and get the new priority back.
+ *
+ * Pass a `NiceTarget` instead of a number to choose explicitly what is changed, `value` is then a nice value increment on every platform and the new nice value is returned. `options` is ignored in that case.
+ * - `thread`: the calling thread. On Linux the nice value is per thread, on Windows the thread priority is translated with the nice mapping. Other Unix platforms only have per-process nice values and throw.
+ * - `process`: every thread of the current process. On Linux each thread of `/proc/self/task` is updated, on Windows the process priority class is changed.
+ * - `pid`: the process identified by `pid`, the same way as `setPriority`.
+ */
+export declare function nice(incr?: number | 'idle' | 'low' | 'belowNormal' | 'normal' | 'aboveNormal' | 'high' | 'realtime' | NiceTarget, options?: NiceOptions | undefined | null): number
+
+export interface NiceOptions {
+  /**
+   * On Windows, treat `incr` as a nice value in `-20..19` and map it onto the nearest `WindowsThreadPriority` (see `niceToWindowsThreadPriority`), instead of throwing when it is not a valid `WindowsThreadPriority`.
+   * It has no effect on Unix.
+   */
+  mapNiceValue?: boolean
+}
+
+export interface NiceTarget {
+  /** The nice value increment, a higher nice value means a lower priority. The result is clamped to `-20..19`. */
+  value: number
+  /**
+   * Whether the change applies to the calling thread or to the whole process, it defaults to `thread`.
+   * It must be `process` (or omitted) when `pid` is set.
+   */
+  target?: 'thread' | 'process'
+  /** The process to change instead of the current one. */
+  pid?: number
+}
+
+/**
+ * This function translate a nice value into the nearest `WindowsThreadPriority`.
+ * Values outside of `-20..19` are clamped first.
+ */
+export declare function niceToWindowsThreadPriority(value: number): WindowsThreadPriority
+
+/** This function is the same as `nice`, but it returns the priority before the change as well, which makes save/restore patterns trivial. */
+export declare function niceWithPrevious(incr?: number | undefined | null, options?: NiceOptions | undefined | null): PriorityChange
+
+export interface NumaOptions {
+  /**
+   * Whether memory may only be allocated from the node, by default the node is preferred and other nodes are used when it is full.
+   * Only Linux supports it.
+   */
+  strict?: boolean
+}
+
+/**
+ * This function calls `callback` whenever the priority of the current process changes, except when the change was made by this addon, so applications can detect interference from ops tooling such as `renice` or the Task Manager, checking it every `interval` on a background thread.
+ * A change is attributed to this addon when the priority is the one this addon set last since the previous check, so external changes made between two checks to the same priority are missed.
+ *
+ * On Unix, it polls [`getpriority(2)`](https://linux.die.net/man/2/getpriority) with `PRIO_PROCESS`, which is the nice value of the main thread on Linux.
+ *
+ * On Windows, it polls the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function, so only changes of the priority class are reported.
+ */
+export declare function onExternalPriorityChange(callback: (arg: PriorityChangeEvent) => void, options?: WatchOptions | undefined | null): Watcher
+
+/**
+ * This function calls `callback` with the new power source whenever `getPowerSource()` changes, checking it every `interval`.
+ * It polls on every platform instead of registering with [`RegisterPowerSettingNotification`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerpowersettingnotification) or `IOPSNotificationCreateRunLoopSource`, which need a window or a run loop, so a change is reported up to `interval` late.
+ *
+ * On other platforms than Linux, macOS and Windows, it throws.
+ */
+export declare function onPowerSourceChange(callback: (arg: PowerSource) => void, options?: WatchOptions | undefined | null): Watcher
+
+/**
+ * This function calls `callback` with the new thermal state whenever `getThermalState()` changes, checking it every `interval`.
+ * Windows has no notification for passive cooling, so it's checked the same way on every platform.
+ *
+ * On other platforms than Linux, macOS and Windows, it throws.
+ */
+export declare function onThermalStateChange(callback: (arg: ThermalState) => void, options?: WatchOptions | undefined | null): Watcher
+
+/**
+ * This function parses a Linux-style CPU list such as `0-3,8,10-11`, as used by `taskset`, cgroups and Kubernetes, into CPU indexes.
+ * The CPUs must be lower than the number of CPUs an affinity mask can address, `CPU_SETSIZE` on Linux and FreeBSD and the number of bits of a processor group mask on Windows.
+ */
+export declare function parseCpuList(list: string): Array<number>
+
+/**
+ * This function restricts the calling thread to the efficiency cores returned by `getCoreClasses`, and returns `false` without changing anything when there are none.
+ * It accepts the same options as `setCpuAffinity`.
+ *
+ * On Linux and Windows, it uses `setCpuAffinity`, on Windows only the CPUs of the first processor group are used.
+ *
+ * On macOS, threads can't be pinned, so it sets the QoS class of the calling thread to `QOS_CLASS_BACKGROUND`, which the scheduler keeps on the efficiency cores, and `options` is not supported.
+ *
+ * On other platforms, it throws.
+ */
+export declare function pinToEfficiencyCores(options?: AffinityOptions | undefined | null): boolean
+
+/**
+ * This function restricts the calling thread to the performance cores returned by `getCoreClasses`.
+ * It accepts the same options as `setCpuAffinity`.
+ *
+ * On Linux and Windows, it uses `setCpuAffinity`, on Windows only the CPUs of the first processor group are used.
+ *
+ * On macOS, threads can't be pinned, so it sets the QoS class of the calling thread to `QOS_CLASS_USER_INTERACTIVE`, which the scheduler prefers to run on the performance cores, and `options` is not supported.
+ *
+ * On other platforms, it throws.
+ */
+export declare function pinToPerformanceCores(options?: AffinityOptions | undefined | null): boolean
+
+/** How `nice` behaves on the current platform. */
+export interface PlatformPriorityInfo {
+  /** Whether `nice` changes the calling thread or the whole process. */
+  kind: 'thread' | 'process'
+  /** The values accepted by `nice`, `min` is the highest priority on Unix and the lowest priority on Windows. */
+  range: PriorityRange
+  /** Whether `nice` adds its argument to the current priority instead of replacing it. */
+  relative: boolean
+}
+
+/** Where the machine gets its power from. */
+export declare const enum PowerSource {
+  /** Mains power, also used for machines without batteries. */
+  Ac = 0,
+  /** A battery, including the battery of a UPS. */
+  Battery = 1
+}
+
+/** One line of a Linux [PSI](https://docs.kernel.org/accounting/psi.html) file. */
+export interface PressureStats {
+  /** The percentage of time stalled over the last 10 seconds. */
+  avg10: number
+  /** The percentage of time stalled over the last 60 seconds. */
+  avg60: number
+  /** The percentage of time stalled over the last 300 seconds. */
+  avg300: number
+  /** The total stall time in microseconds. */
+  total: number
+}
+
+export interface PriorityChange {
+  /** The priority before the change. */
+  previous: number
+  /** The priority after the change. */
+  current: number
+}
+
+/** A change of the priority of a process reported by `watchPriority` and `onExternalPriorityChange`. */
+export interface PriorityChangeEvent {
+  pid: number
+  /** The priority before the change, as returned by `getPriority`. */
+  previous: number
+  /** The priority after the change, as returned by `getPriority`. */
+  current: number
+  /** The priority class after the change, as returned by `getProcessPriorityClass`. */
+  priorityClass: ProcessPriorityClass
+}
+
+/** The prediction of `canSetPriority`. */
+export interface PriorityCheck {
+  /** Whether the priority change would succeed. */
+  allowed: boolean
+  /** Why the priority change would fail, `rlimit` when it exceeds `RLIMIT_NICE` on Linux and `missingPrivilege` when it requires root, `CAP_SYS_NICE` or `SeIncreaseBasePriorityPrivilege`. */
+  reason?: 'rlimit' | 'missingPrivilege'
+  /** A human readable explanation of `reason`. */
+  message?: string
+}
+
+/**
+ * Changes the priority of the current process when constructed, and restores the previous priority with `restore()`.
+ *
+ * It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations:
+ *
+ * ```ts
+ * {
+ *   using _ = new PriorityGuard('low')
+ *   // runs with low priority
+ * }
+ * // the previous priority is restored here
+ * ```
+ */
+export declare class PriorityGuard {
+  constructor(level: PriorityLevel | 'idle' | 'low' | 'belowNormal' | 'normal' | 'aboveNormal' | 'high' | 'realtime')
+  /** Restores the priority captured by the constructor, calling it more than once has no effect. */
+  restore(): void
+  /** Whether `restore()` has already been called. */
+  get restored(): boolean
+}
+
+/** Cross-platform priority levels, ordered from the lowest to the highest priority. */
+export declare const enum PriorityLevel {
+  Idle = 0,
+  Low = 1,
+  BelowNormal = 2,
+  Normal = 3,
+  AboveNormal = 4,
+  High = 5,
+  Realtime = 6
+}
+
+export interface PriorityRange {
+  min: number
+  max: number
+}
+
+/** The options of `watchPriority`. */
+export interface PriorityWatchOptions {
+  /** The process to watch, it defaults to the current process. */
+  pid?: number
+  /** How often the priority is checked in milliseconds, it defaults to `1000` and must be at least `1`. */
+  interval?: number
+}
+
+/** A processor group, Windows machines with more than 64 logical CPUs have several of them. */
+export interface ProcessorGroup {
+  group: number
+  /** The number of CPUs the group can hold. */
+  maximumProcessorCount: number
+  /** The number of CPUs currently available in the group. */
+  activeProcessorCount: number
+  /** The indexes of the available CPUs within the group. */
+  activeProcessors: Array<number>
+}
+
+/** A CPU identified by its processor group and its index within that group. */
+export interface ProcessorNumber {
+  group: number
+  number: number
+}
+
+/** Windows process priority classes, the values are the `*_PRIORITY_CLASS` constants. */
+export declare const enum ProcessPriorityClass {
+  Idle = 64,
+  BelowNormal = 16384,
+  Normal = 32,
+  AboveNormal = 32768,
+  High = 128,
+  Realtime = 256
+}
+
+/** macOS Quality of Service classes, ordered from the lowest to the highest priority, see [`pthread_set_qos_class_self_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h). */
+export declare const enum QosClass {
+  /** No QoS class was assigned, only returned by `getQosClass`. */
+  Unspecified = 0,
+  /** Work the user isn't aware of, such as indexing or backups, it runs on the efficiency cores. */
+  Background = 9,
+  /** Long-running work with a progress indicator, such as downloads. */
+  Utility = 17,
+  Default = 21,
+  /** Work the user started and is waiting for, such as opening a document. */
+  UserInitiated = 25,
+  /** Work interacting with the user, such as animations and event handling. */
+  UserInteractive = 33
+}
+
+/**
+ * Temporarily raises the QoS class of another thread of the current process, so a thread waiting on a worker doesn't suffer from priority inversion.
+ * The override lasts until `end()` is called or the object is garbage collected.
+ *
+ * It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations:
+ *
+ * ```ts
+ * {
+ *   using _ = new QosOverride(workerThreadId, QosClass.UserInitiated)
+ *   // wait for the worker
+ * }
+ * // the override ends here
+ * ```
+ *
+ * On macOS, it uses the [`pthread_override_qos_class_start_np` and `pthread_override_qos_class_end_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) functions.
+ *
+ * On other platforms, the constructor throws.
+ */
+export declare class QosOverride {
+  /** `threadId` is the `getNativeThreadId()` of the thread to boost, it must be a running thread of the current process. */
+  constructor(threadId: number, qosClass: QosClass, relativePriority?: number | undefined | null)
+  /** Ends the override, calling it more than once has no effect. */
+  end(): void
+  /** Whether `end()` has already been called. */
+  get ended(): boolean
+}
+
+/** The time constraints of a realtime thread in nanoseconds. */
+export interface RealtimeThreadPolicy {
+  /** The nominal time between the starts of two processing cycles, `0` means the work isn't periodic. */
+  periodNs: number
+  /** The CPU time needed by each cycle. */
+  computationNs: number
+  /** The maximum time between the start and the end of each cycle, it must not be less than `computationNs`. */
+  constraintNs: number
+  /** Whether the computation may be interrupted, it defaults to `true`. */
+  preemptible?: boolean
+}
+
+/**
+ * This function adds `delta` to the nice value of the current process like `adjustPriority`, and returns the nice values before and after the change.
+ *
+ * On Unix, the result is clamped to `-20..19`.
+ *
+ * On Windows, there is no relative adjustment. The calling thread priority is translated into a nice value, and the new priority is picked from the ordered `WindowsThreadPriority` lattice (see `getWindowsThreadPriorityMapping`).
+ * Unlike `adjustPriority`, a non-zero `delta` always moves at least one step, so repeated small adjustments are not lost.
+ */
+export declare function reniceBy(delta: number): PriorityChange
+
+/** This function resets the counters returned by `getStats` and exported by `getMetricsText`. */
+export declare function resetStats(): void
+
+export interface ResourceUsage {
+  /** The CPU time spent in user mode in microseconds. */
+  userCpuTime: number
+  /** The CPU time spent in kernel mode in microseconds. */
+  systemCpuTime: number
+  /** The maximum resident set size in bytes, it's `undefined` for the `thread` scope and on Windows. */
+  maxRss?: number
+}
+
+/**
+ * This function restores the priority the current process had when the addon was loaded, reverting every change made since then.
+ *
+ * It restores the nice value of the calling thread, and on Linux its scheduling policy as well. On macOS it restores the QoS class of the calling thread, on Windows the process priority class and the calling thread priority.
+ *
+ * Restoring a higher priority than the current one requires the same privileges as raising it (root or CAP_SYS_NICE on Unix).
+ */
+export declare function restoreDefaultPriority(): void
+
+/**
+ * This function measures the CPU time consumed by each thread of a process over `durationMs`, and resolves with the threads sorted from the busiest, so users can find the right thread to renice or pin.
+ * The measurement runs on a dedicated thread which is left out of the results, so neither the JavaScript thread nor the libuv thread pool is blocked, and both are measured as well.
+ *
+ * It uses `listThreads` at the start and the end of the window, and only supports the same platforms, other platforms reject. The precision is limited by the clock tick on Linux, usually 10 milliseconds.
+ */
+export declare function sampleThreadCpu(options?: SampleThreadCpuOptions | undefined | null): Promise<Array<ThreadCpuSample>>
+
+export interface SampleThreadCpuOptions {
+  /** How long to measure in milliseconds, it defaults to `1000`. */
+  durationMs?: number
+  /** The process whose threads are measured, it defaults to the current process. Only the current process is supported on macOS. */
+  pid?: number
+}
+
+export interface SchedStats {
+  /** The time spent running on a CPU in nanoseconds. */
+  runTime: number
+  /** The time spent runnable, waiting on a runqueue for a CPU, in nanoseconds. It grows when the thread is starved by higher priority work. */
+  waitTime: number
+  /** The number of time slices run on a CPU. */
+  timeslices: number
+}
+
+export interface SchedulerInfo {
+  policy: SchedulerPolicy
+  /** The real-time priority, it's always `0` for `Other`, `Batch` and `Idle`. */
+  priority: number
+  /** Whether `SCHED_RESET_ON_FORK` is set. */
+  resetOnFork: boolean
+}
+
+export interface SchedulerOptions {
+  /** Set `SCHED_RESET_ON_FORK`, so that child processes fall back to `Other` and a non-negative nice value instead of inheriting a real-time policy. */
+  resetOnFork?: boolean
+  /** The native id of the thread to change (see `getNativeThreadId`), it defaults to the calling thread. */
+  tid?: number
+  /**
+   * Start a watchdog thread which demotes the thread back to `Other` once it has been running for more than `watchdogMs` consecutive milliseconds without blocking, to protect the machine from lockups.
+   * It only applies to `Fifo` and `RoundRobin` on Linux, and only to the threads of the current process.
+   * The watchdog needs a higher real-time priority than `rtPriority`, without CAP_SYS_NICE it's capped by `RLIMIT_RTPRIO`, and `setScheduler` throws when it can't get one.
+   */
+  watchdogMs?: number
+}
+
+/** Linux scheduling policies, see [`sched(7)`](https://man7.org/linux/man-pages/man7/sched.7.html). */
+export declare const enum SchedulerPolicy {
+  /** The default time-sharing policy, `SCHED_OTHER`. */
+  Other = 0,
+  /** First in, first out real-time policy, `SCHED_FIFO`. */
+  Fifo = 1,
+  /** Round-robin real-time policy, `SCHED_RR`. */
+  RoundRobin = 2,
+  /** Time-sharing policy for CPU-bound batch work, `SCHED_BATCH`. */
+  Batch = 3,
+  /** Policy for very low priority background work, `SCHED_IDLE`. */
+  Idle = 5
+}
+
+/** Wakeup latencies in microseconds, the time between when the timing loop asked to wake up and when it actually ran. */
+export interface SchedulingJitter {
+  /** The number of wakeups measured. */
+  samples: number
+  min: number
+  mean: number
+  p50: number
+  p99: number
+  max: number
+}
+
+/**
+ * The scheduling state of the current process and the calling thread, it only contains plain values so it can be passed to `JSON.stringify`.
+ * Fields which are not supported on the current platform, or which can't be read, are `null`.
+ */
+export interface SchedulingSnapshot {
+  /** The platform, the same as `process.platform`. */
+  platform: string
+  pid: number
+  /** The native id of the calling thread, see `getNativeThreadId`. */
+  tid: number | null
+  /** The nice value of the calling thread, see `getPriority`. */
+  nice: number
+  /** The priority class of the process, see `getProcessPriorityClass`. */
+  priorityClass: ProcessPriorityClass | null
+  /** The scheduling policy of the calling thread, only available on Linux. */
+  policy: SchedulerPolicy | null
+  /** The real-time priority of the calling thread, only available on Linux. */
+  rtPriority: number | null
+  /** Whether `SCHED_RESET_ON_FORK` is set, only available on Linux. */
+  resetOnFork: boolean | null
+  /** The latency nice value of the calling thread, only available on Linux kernels with the latency-nice patches. */
+  latencyNice: number | null
+  /** The CPUs the calling thread may run on, see `getCpuAffinity`. */
+  affinity: Array<number> | null
+  /** The I/O scheduling class of the calling thread, only available on Linux. */
+  ioClass: IoPriorityClass | null
+  /** The level within `ioClass`, only available on Linux. */
+  ioLevel: number | null
+  /** The I/O priority of the process, only available on Windows. */
+  windowsIoPriority: WindowsIoPriority | null
+  /** The memory priority of the process from `1` (very low) to `5` (normal), only available on Windows. */
+  memoryPriority: number | null
+  /** The OOM score adjustment of the process, only available on Linux. */
+  oomScoreAdj: number | null
+  /** The QoS class of the calling thread, only available on macOS. */
+  qosClass: QosClass | null
+  /** The CPU weight of the cgroup of the process, see `getCgroupCpuWeight`. */
+  cgroupCpuWeight: number | null
+  /** The soft `RLIMIT_NICE`, `null` when unlimited, only available on Linux. */
+  rlimitNice: number | null
+  /** The soft `RLIMIT_RTPRIO`, `null` when unlimited, only available on Linux. */
+  rlimitRtprio: number | null
+}
+
+/** The scheduling state of the calling thread, fields which are not supported on the current platform are `null`. */
+export interface SchedulingState {
+  /** The nice value, see `getPriority`. */
+  nice: number
+  /** The scheduling policy, only available on Linux. */
+  policy: SchedulerPolicy | null
+  /** The real-time priority, only available on Linux. */
+  rtPriority: number | null
+  /** The CPUs the thread may run on, only available on Linux. */
+  affinity: Array<number> | null
+  /** The I/O scheduling class, only available on Linux. */
+  ioClass: IoPriorityClass | null
+  /** The level within `ioClass`, only available on Linux. */
+  ioLevel: number | null
+}
+
+/**
+ * This function set the priority of the current process to the absolute nice value `value`, clamped to `-20..19`.
+ *
+ * On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function. On Linux the nice value belongs to the calling thread.
+ *
+ * On Windows, `value` is mapped onto the nearest `WindowsThreadPriority` (see `niceToWindowsThreadPriority`) and applied to the calling thread.
+ */
+export declare function setAbsolutePriority(value: number): void
+
+/**
+ * This function set the priority of every thread of the current process, including the libuv and V8 worker threads, and returns the number of updated threads.
+ *
+ * On Linux, every thread has its own nice value, it walks `/proc/self/task` and applies [`setpriority(2)`](https://linux.die.net/man/2/setpriority) to each thread id.
+ *
+ * On Windows, it enumerates the threads with [`CreateToolhelp32Snapshot`](https://learn.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-createtoolhelp32snapshot) and applies [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) to each of them, `value` must be a `WindowsThreadPriority`.
+ *
+ * On other Unix platforms, the nice value belongs to the process, so it uses `setpriority(2)` with `PRIO_PROCESS` once and returns `1`.
+ */
+export declare function setAllThreadsPriority(value: number): number
+
+/**
+ * This function limits the CPU bandwidth of the cgroup of the process identified by `pid`, it defaults to the current process.
+ * The cgroup may use `quota` microseconds of CPU time every `period` microseconds, so a quota of twice the period allows two CPUs worth of time. `null` removes the limit, and the period is kept when it's omitted.
+ *
+ * On Linux, it writes the [`cpu.max`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, which requires the cgroup to be delegated to the current user.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setCgroupCpuMax(quota?: number | undefined | null, period?: number | undefined | null, pid?: number | undefined | null): void
+
+/**
+ * This function set the CPU weight of the cgroup of the process identified by `pid`, it defaults to the current process.
+ * `weight` ranges from `1` to `10000`, and `100` is the default. CPU time is shared between sibling cgroups in proportion to their weights, while nice values mostly only matter within a cgroup.
+ *
+ * On Linux, it writes the [`cpu.weight`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, which requires the cgroup to be delegated to the current user.
+ * On cgroup v1 hosts, it writes the weight converted to [`cpu.shares`](https://docs.kernel.org/scheduler/sched-design-CFS.html#group-scheduler-extensions-to-cfs) instead, `100` is `1024` shares.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setCgroupCpuWeight(weight: number, pid?: number | undefined | null): void
+
+/**
+ * This function marks the cgroup of the process identified by `pid` as idle, it defaults to the current process.
+ * The tasks of an idle cgroup only get the CPU time the sibling cgroups don't use, like `SCHED_IDLE` threads.
+ *
+ * On Linux, it writes the [`cpu.idle`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, which requires Linux 5.15 and the cgroup to be delegated to the current user.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setCgroupIdle(enabled: boolean, pid?: number | undefined | null): void
+
+/**
+ * This function restricts the calling thread to the CPUs listed in `cpus`, either CPU indexes or a Linux-style CPU list such as `0-3,8`.
+ * Pass `{ target: 'process' }` to use the whole process, `{ pid }` to use another process, or `{ tid }` to use another thread.
+ *
+ * On Linux, it uses the [`sched_setaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_setaffinity.2.html) function. For a process, every thread of `/proc/{pid}/task` is updated.
+ *
+ * On Windows, it uses the [`SetThreadAffinityMask`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-setthreadaffinitymask) or [`SetProcessAffinityMask`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-setprocessaffinitymask) function.
+ * Only the CPUs of the current processor group can be used.
+ *
+ * On FreeBSD, it uses the [`cpuset_setaffinity(2)`](https://man.freebsd.org/cgi/man.cgi?query=cpuset_setaffinity) function. For a process, every thread is updated.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setCpuAffinity(cpus: Array<number> | string, options?: AffinityOptions | undefined | null): void
+
+/**
+ * This function puts the process identified by `pid` into or out of the macOS background state, it defaults to the current process.
+ * In the background state the CPU, I/O and timers of every thread of the process are throttled.
+ *
+ * On macOS, it uses the [`setpriority(2)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/setpriority.2.html) function with `PRIO_DARWIN_PROCESS` and `PRIO_DARWIN_BG`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setDarwinBackground(enabled: boolean, pid?: number | undefined | null): void
+
+/**
+ * This function set the disk I/O policy of the current process or of the calling thread, the macOS equivalent of `ionice`.
+ *
+ * On macOS, it uses the [`setiopolicy_np(3)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/setiopolicy_np.3.html) function with `IOPOL_TYPE_DISK`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setDarwinIoPolicy(scope: 'thread' | 'process', policy: DarwinIoPolicy): void
+
+/**
+ * This function opts the current process into or out of EcoQoS, the "Efficiency mode" of the Task Manager.
+ * With EcoQoS the system runs the process at a lower clock speed and prefers efficiency cores on hybrid CPUs, to save power.
+ *
+ * On Windows, it uses the [`SetProcessInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setprocessinformation) function with `ProcessPowerThrottling` and `PROCESS_POWER_THROTTLING_EXECUTION_SPEED`.
+ * The Task Manager also lowers the priority class to `Idle` when it enables Efficiency mode, use `setProcessPriorityClass` for that.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setEfficiencyMode(enabled: boolean): void
+
+/**
+ * This function set the highest priority the current process is allowed to set, and returns the new nice value.
+ *
+ * On Unix, it sets the nice value `-20` when the process has root or CAP_SYS_NICE. Otherwise it sets the lowest nice value allowed by `RLIMIT_NICE` on Linux, and keeps the current priority elsewhere.
+ *
+ * On Windows, it sets the `High` priority class. `Realtime` is never used, because it can starve the system and silently falls back to `High` without the required privilege anyway.
+ */
+export declare function setHighestAllowed(): number
+
+/**
+ * This function makes the current process idle with the best mechanism available, and returns which one was used.
+ *
+ * On Linux, it marks the cgroup as idle with `setCgroupIdle`, which affects every process in the cgroup, so the process should have a cgroup of its own.
+ * When cgroup control isn't available, it falls back to `SCHED_IDLE` for the calling thread, see `setScheduler`. Passing `false` undoes either of them, leaving `SCHED_IDLE` requires root, CAP_SYS_NICE or an `RLIMIT_NICE` which allows the current nice value.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setIdlePriority(enabled: boolean): 'cgroup' | 'thread'
+
+/**
+ * This function set the I/O scheduling class and level of the calling thread, like `ionice`.
+ * `level` goes from `0` (the highest priority) to `7` and defaults to `4`, it is ignored for the `Idle` and `None` classes.
+ * The `Realtime` class requires `CAP_SYS_ADMIN`.
+ * Pass `{ pid }` to update every thread of another process, or `{ pgid }` to update every process of a process group.
+ *
+ * On Linux, it uses the [`ioprio_set(2)`](https://man7.org/linux/man-pages/man2/ioprio_set.2.html) syscall, with `IOPRIO_WHO_PGRP` for a process group.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setIoPriority(class: IoPriorityClass, level?: number | undefined | null, options?: IoPriorityOptions | undefined | null): void
+
+/**
+ * This function set the latency nice value of the calling thread, from `-20` (the most latency sensitive) to `19`, without changing its policy or nice value.
+ * It returns `false` when the kernel doesn't support latency nice.
+ *
+ * On Linux, it uses the [`sched_setattr(2)`](https://man7.org/linux/man-pages/man2/sched_setattr.2.html) syscall with the `sched_latency_nice` field, which is only available on kernels with the latency-nice patches.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setLatencyNice(value: number): boolean
+
+/**
+ * This function set the lowest priority of the current process, and returns the new nice value.
+ *
+ * On Unix, it sets the nice value `19`. On Windows, it sets the `Idle` priority class.
+ */
+export declare function setLowestPossible(): number
+
+/**
+ * This function set the priority of the current process on a normalized scale, from `0` (the lowest priority) to `100` (the highest priority).
+ *
+ * On Unix, `priority` is mapped linearly onto the nice values `19..-20`, there are only 40 nice values so nearby priorities may be the same.
+ * Raising the priority requires root or CAP_SYS_NICE.
+ *
+ * On Windows, `priority` is mapped onto a nice value the same way, then onto the nearest process priority class the same way as `setPriority`.
+ */
+export declare function setNormalizedPriority(priority: number): void
+
+/**
+ * This function keeps the calling thread on the NUMA node `node`, both its CPUs and its memory, so memory-bound workers stay NUMA-local.
+ *
+ * On Linux, it restricts the thread to the CPUs of `/sys/devices/system/node/node{node}/cpulist` with [`sched_setaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_setaffinity.2.html), and sets its memory policy with [`set_mempolicy(2)`](https://man7.org/linux/man-pages/man2/set_mempolicy.2.html), `MPOL_PREFERRED` or `MPOL_BIND` when `strict` is set.
+ *
+ * On Windows, it restricts the thread to the CPUs of the node with the [`GetNumaNodeProcessorMaskEx`](https://learn.microsoft.com/en-us/windows/win32/api/systemtopologyapi/nf-systemtopologyapi-getnumanodeprocessormaskex) and [`SetThreadGroupAffinity`](https://learn.microsoft.com/en-us/windows/win32/api/processtopologyapi/nf-processtopologyapi-setthreadgroupaffinity) functions.
+ * Windows allocates memory from the node of the CPU the thread runs on by default, use [`VirtualAllocExNuma`](https://learn.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualallocexnuma) for explicit placement.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setNumaNode(node: number, options?: NumaOptions | undefined | null): void
+
+/**
+ * This function set the OOM score adjustment of the process identified by `pid`, it defaults to the current process.
+ * `value` ranges from `-1000`, which never kills the process, to `1000`, which kills it first when the system runs out of memory.
+ *
+ * On Linux, it writes [`/proc/<pid>/oom_score_adj`](https://man7.org/linux/man-pages/man5/proc_pid_oom_score_adj.5.html).
+ * Lowering the value below its previous minimum requires root or `CAP_SYS_RESOURCE`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setOomScoreAdj(value: number, pid?: number | undefined | null): void
+
+/**
+ * This function set the priority of the process identified by `pid`.
+ * `pid` `0` means the current process.
+ *
+ * On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_PROCESS`, `value` is the new nice value.
+ *
+ * On Windows, it uses the [`SetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setpriorityclass) function.
+ * `value` is a nice value, it's mapped onto the nearest priority class the same way as Node.js `os.setPriority`.
+ */
+export declare function setPriority(pid: number, value: number): void
+
+/**
+ * This function set the priority of the current process to a cross-platform `PriorityLevel`.
+ *
+ * On Unix, the level is mapped onto a nice value (Idle: 19, Low: 15, BelowNormal: 10, Normal: 0, AboveNormal: -5, High: -10, Realtime: -20) and applied with [`setpriority(2)`](https://linux.die.net/man/2/setpriority).
+ * Raising the priority requires root or CAP_SYS_NICE.
+ *
+ * On macOS, the calling thread's QoS class is also updated with `pthread_set_qos_class_self_np`, because nice values are largely ignored under QoS.
+ *
+ * On Windows, the level is mapped onto a process priority class and the calling thread priority.
+ */
+export declare function setPriorityLevel(level: PriorityLevel): void
+
+/**
+ * This function set the priority of the process identified by `pid` and all of its descendants, and returns the number of updated processes.
+ * `value` has the same meaning as in `setPriority`.
+ *
+ * The descendants are found by walking the `/proc/*\/stat` parent pids on Linux, [`proc_listchildpids`](https://opensource.apple.com/source/Libc/Libc-498/darwin/libproc.h) on macOS and [`CreateToolhelp32Snapshot`](https://learn.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-createtoolhelp32snapshot) on Windows.
+ * Descendants which exit while the tree is being walked are skipped.
+ *
+ * This function always throws on other platforms.
+ */
+export declare function setPriorityTree(pid: number, value: number): number
+
+/**
+ * This function make the current process enter or leave background processing mode.
+ * In background mode the system lowers the CPU, I/O and memory priority of the whole process, so it can perform background work without significantly affecting activity in the foreground.
+ *
+ * On Windows, it uses the [`SetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setpriorityclass) function with `PROCESS_MODE_BACKGROUND_BEGIN` or `PROCESS_MODE_BACKGROUND_END`.
+ * It fails if the process is already in the requested mode.
+ *
+ * This function always throws on other platforms.
+ */
+export declare function setProcessBackgroundMode(enabled: boolean): void
+
+/**
+ * This function sets the default CPU Sets of the threads of the current process which have no selection, an empty list clears them.
+ *
+ * On Windows, it uses the [`SetProcessDefaultCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setprocessdefaultcpusets) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setProcessDefaultCpuSets(ids: Array<number>): void
+
+/**
+ * This function set the priority of every process in the process group `pgid`.
+ * `pgid` `0` means the process group of the current process.
+ *
+ * On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_PGRP`.
+ *
+ * Windows doesn't have process groups, this function always throws on Windows.
+ */
+export declare function setProcessGroupPriority(pgid: number, value: number): void
+
+/**
+ * This function set the I/O priority hint of the current process, so background services stop starving the disk access of foreground apps.
+ *
+ * On Windows, it uses the [`NtSetInformationProcess`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntsetinformationprocess) function with `ProcessIoPriority`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setProcessIoPriority(priority: WindowsIoPriority): void
+
+/**
+ * This function enables or disables the dynamic priority boost of the threads of the process identified by `pid`, it defaults to the current process.
+ * Windows temporarily raises the priority of threads woken up by I/O or a GUI event, disabling it reduces the latency jitter of realtime-ish workloads.
+ *
+ * On Windows, it uses the [`SetProcessPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setprocesspriorityboost) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setProcessPriorityBoost(enabled: boolean, pid?: number | undefined | null): void
+
+/**
+ * This function set the priority class of the process identified by `pid`, it defaults to the current process.
+ *
+ * On Windows, it uses the [`SetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setpriorityclass) function.
+ * Unlike `nice`, which changes the priority of the calling thread, this affects every thread of the process.
+ *
+ * On Unix, the priority class is mapped onto a nice value (Idle: 19, BelowNormal: 10, Normal: 0, AboveNormal: -7, High: -14, Realtime: -20) and applied with `setpriority(2)`.
+ */
+export declare function setProcessPriorityClass(priorityClass: ProcessPriorityClass, pid?: number | undefined | null): void
+
+/**
+ * This function set the QoS class of the calling thread, the idiomatic way to prioritize work on macOS, where nice values are largely ignored under QoS.
+ * `relativePriority` lowers the priority within the class, from `0` (the default) to `-15`.
+ *
+ * On macOS, it uses the [`pthread_set_qos_class_self_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setQosClass(qosClass: QosClass, relativePriority?: number | undefined | null): void
+
+/**
+ * This function gives the calling thread realtime scheduling with the given time constraints, which is the only supported way to get audio-grade scheduling on macOS.
+ * The scheduler guarantees `computationNs` of CPU time within `constraintNs` of the start of each `periodNs`, and demotes the thread if it uses more.
+ *
+ * On macOS, it uses the [`thread_policy_set`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/thread_policy.h) function with `THREAD_TIME_CONSTRAINT_POLICY`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setRealtimeThreadPolicy(policy: RealtimeThreadPolicy): void
+
+/**
+ * This function set the scheduling policy and the real-time priority of the calling thread, or of the thread identified by the native thread id `tid`.
+ *
+ * On Linux, it uses the [`sched_setscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_setscheduler.2.html) function.
+ * `rt_priority` must be `0` for `Other`, `Batch` and `Idle`, and between `1` and `99` for `Fifo` and `RoundRobin`.
+ * Real-time policies require root or CAP_SYS_NICE, or a non-zero `RLIMIT_RTPRIO`.
+ * Pass `{ resetOnFork: true }` to prevent child processes from inheriting a real-time policy.
+ *
+ * On Windows, there are no scheduling policies, it uses the [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) function instead.
+ * `Fifo` and `RoundRobin` are mapped to `ThreadPriorityTimeCritical`, `Other` to `ThreadPriorityNormal`, `Batch` to `ThreadPriorityBelowNormal` and `Idle` to `ThreadPriorityIdle`. `rt_priority` and `resetOnFork` are ignored.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setScheduler(policy: SchedulerPolicy, rtPriority?: number | undefined | null, options?: SchedulerOptions | undefined | null): void
+
+/**
+ * This function sets the `CPUWeight` of a systemd unit at runtime, it defaults to the unit of the current process, so services can adjust their own share of CPU time without writing to the cgroup filesystem.
+ * `weight` ranges from `1` to `10000`, and `100` is the default.
+ *
+ * On Linux, it calls the [`SetUnitProperties`](https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.systemd1.html) D-Bus method of the systemd manager with `busctl` on a libuv thread pool thread, which is allowed for the units of the user manager, and requires root or a polkit authorization for the units of the system manager. Interactive polkit authorization is disabled, so the returned `Promise` rejects instead of waiting for a password prompt.
+ *
+ * On other platforms, the returned `Promise` rejects.
+ */
+export declare function setSystemdUnitCpuWeight(weight: number, options?: SystemdUnitOptions | undefined | null): Promise<void>
+
+/**
+ * This function sets the `IOWeight` of a systemd unit at runtime, it defaults to the unit of the current process.
+ * `weight` ranges from `1` to `10000`, and `100` is the default, it only applies to block devices using an I/O scheduler that supports weights, such as BFQ.
+ *
+ * On Linux, it calls the [`SetUnitProperties`](https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.systemd1.html) D-Bus method of the systemd manager with `busctl` on a libuv thread pool thread, with the same permissions as `setSystemdUnitCpuWeight`.
+ *
+ * On other platforms, the returned `Promise` rejects.
+ */
+export declare function setSystemdUnitIoWeight(weight: number, options?: SystemdUnitOptions | undefined | null): Promise<void>
+
+/**
+ * This function set the affinity tag of the calling thread, threads sharing the same non-zero tag are hinted to run on CPUs sharing an L2 cache, and `0` removes the hint.
+ * It returns `false` when the machine doesn't support affinity tags, which is the case on Apple silicon.
+ *
+ * On macOS, it uses the [`thread_policy_set`](https://developer.apple.com/library/archive/releasenotes/Performance/RN-AffinityAPI/) function with `THREAD_AFFINITY_POLICY`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setThreadAffinityTag(tag: number): boolean
+
+/**
+ * This function opts the calling thread into or out of EcoQoS, so a worker thread can save power while the main thread stays at normal QoS.
+ * With EcoQoS the system runs the thread at a lower clock speed and prefers efficiency cores on hybrid CPUs.
+ *
+ * On Windows, it uses the [`SetThreadInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadinformation) function with `ThreadPowerThrottling` and `THREAD_POWER_THROTTLING_EXECUTION_SPEED`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setThreadEfficiencyMode(enabled: boolean): void
+
+/**
+ * This function restricts the calling thread to the CPUs listed in `cpus` of the processor group `group`, so every CPU of machines with more than 64 logical CPUs can be used.
+ *
+ * On Windows, it uses the [`SetThreadGroupAffinity`](https://learn.microsoft.com/en-us/windows/win32/api/processtopologyapi/nf-processtopologyapi-setthreadgroupaffinity) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setThreadGroupAffinity(group: number, cpus: Array<number>): void
+
+/**
+ * This function sets the ideal processor of the calling thread, a soft placement hint the scheduler prefers without restricting the thread to it, and returns the previous one.
+ *
+ * On Windows, it uses the [`SetThreadIdealProcessorEx`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadidealprocessorex) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setThreadIdealProcessor(processor: ProcessorNumber): ProcessorNumber
+
+/**
+ * This function set the I/O priority hint of the calling thread only, so a background worker thread can use `VeryLow` while the rest of the process stays normal.
+ *
+ * On Windows, it uses the [`NtSetInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/ntifs/nf-ntifs-ntsetinformationthread) function with `ThreadIoPriority`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setThreadIoPriority(priority: WindowsIoPriority): void
+
+/**
+ * This function set the priority of the thread identified by the native thread id `tid`.
+ *
+ * On Linux, threads have their own nice value, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_PROCESS` and the thread id, `value` is the new nice value.
+ *
+ * On Windows, it uses the [`OpenThread`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-openthread) and [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) functions, `value` must be a `WindowsThreadPriority`.
+ *
+ * This function always throws on other platforms.
+ */
+export declare function setThreadPriority(tid: number, value: number): void
+
+/**
+ * This function enables or disables the dynamic priority boost of the thread identified by `tid`, it defaults to the calling thread.
+ *
+ * On Windows, it uses the [`SetThreadPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriorityboost) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setThreadPriorityBoost(enabled: boolean, tid?: number | undefined | null): void
+
+/**
+ * This function selects the CPU Sets the calling thread should run on, an empty list clears the selection.
+ * Unlike hard affinity, the scheduler may still use other CPUs when the selected ones are unavailable.
+ *
+ * On Windows, it uses the [`SetThreadSelectedCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadselectedcpusets) function.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setThreadSelectedCpuSets(ids: Array<number>): void
+
+/**
+ * This function requests a finer timer resolution for the current process, so sleeps and timers of latency-sensitive threads don't quantize to 15.6ms, and returns the resolution in effect in milliseconds.
+ * The system rounds `milliseconds` to a supported resolution, and a finer resolution increases the power usage.
+ *
+ * On Windows, it uses the `NtSetTimerResolution` function, the finer-grained equivalent of [`timeBeginPeriod`](https://learn.microsoft.com/en-us/windows/win32/api/timeapi/nf-timeapi-timebeginperiod).
+ * Since Windows 10 version 2004, the resolution only affects the current process.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setTimerResolution(milliseconds: number): number
+
+/**
+ * This function set the timer slack of the calling thread in nanoseconds, how late the kernel may fire its timers to coalesce wake-ups.
+ * Latency-sensitive threads can reduce it, while background threads can increase it to save power. `0` restores the default slack of the thread, usually 50 microseconds.
+ *
+ * On Linux, it uses the [`prctl(2)`](https://man7.org/linux/man-pages/man2/prctl.2.html) function with `PR_SET_TIMERSLACK`.
+ *
+ * On other platforms, it throws.
+ */
+export declare function setTimerSlack(nanoseconds: number): void
+
+/**
+ * This function set the priority of every process owned by the user `user`.
+ * `user` can be either a numeric uid or a user name, `0` means the real user id of the current process.
+ *
+ * On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_USER`, user names are resolved with [`getpwnam_r(3)`](https://linux.die.net/man/3/getpwnam_r).
+ *
+ * Windows doesn't support per-user priority, this function always throws on Windows.
+ */
+export declare function setUserPriority(user: number | string, value: number): void
+
+export interface SystemdUnitOptions {
+  /** The unit to change, such as `worker.service`, it defaults to the unit of the current process. */
+  unit?: string
+  /** Whether the unit is managed by the user manager instead of the system manager, it defaults to whether the current process runs under the user manager. */
+  user?: boolean
+}
+
+export interface SystemLoad {
+  /** The average number of runnable tasks over the last minute. */
+  avg1: number
+  /** The average number of runnable tasks over the last 5 minutes. */
+  avg5: number
+  /** The average number of runnable tasks over the last 15 minutes. */
+  avg15: number
+  /** Whether the averages are emulated from the CPU utilization, which is the case on Windows. */
+  emulated: boolean
+}
+
+/** How hot the machine is, from the coolest to the hottest, mirroring [`NSProcessInfo.ThermalState`](https://developer.apple.com/documentation/foundation/processinfo/thermalstate). */
+export declare const enum ThermalState {
+  Nominal = 0,
+  /** Slightly elevated, the system starts to take measures such as spinning up the fans. */
+  Fair = 1,
+  /** High, the performance is throttled and the work should be reduced. */
+  Serious = 2,
+  /** Significantly impacting the performance, the work should be stopped. */
+  Critical = 3
+}
+
+export interface ThreadContextSwitches {
+  /** The native id of the thread, see `getNativeThreadId`. */
+  tid: number
+  /** The context switches because the thread blocked, for example on I/O or a lock, `undefined` on Windows. */
+  voluntary?: number
+  /** The context switches because the thread was preempted, `undefined` on Windows. */
+  involuntary?: number
+  total: number
+}
+
+export interface ThreadCpuSample {
+  /** The native id of the thread, see `getNativeThreadId`. */
+  tid: number
+  name: string | null
+  /** The CPU time consumed by the thread during the window in microseconds. */
+  cpuTime: number
+  /** The share of one CPU used by the thread during the window, from `0` to `100`. */
+  cpuPercent: number
+}
+
+/** A thread of a process, fields which are not supported on the current platform, or which can't be read, are `null`. */
+export interface ThreadInfo {
+  /** The native id of the thread, see `getNativeThreadId`. */
+  tid: number
+  name: string | null
+  /** The nice value of the thread, only available on Linux. */
+  nice: number | null
+  /** The scheduling policy of the thread, on Linux and macOS. */
+  policy: SchedulerPolicy | null
+  /** The real-time priority of the thread, only available on Linux. */
+  rtPriority: number | null
+  /** The priority of the thread relative to the priority class of the process, see `WindowsThreadPriority`, only available on Windows. */
+  priority: number | null
+  /** What the thread is doing, on Linux and macOS. */
+  state: 'running' | 'sleeping' | 'uninterruptible' | 'stopped' | 'zombie' | 'dead' | 'idle' | null | null
+  /** The CPU time consumed by the thread in microseconds, in user and kernel mode. */
+  cpuTime: number | null
+}
+
+export interface ThreadSchedulingInfo {
+  /** Whether the dynamic priority boost is enabled, see `setThreadPriorityBoost`. */
+  boostEnabled: boolean
+  /** Whether the thread is in background processing mode, see `isInBackgroundMode`. */
+  backgroundMode: boolean
+  /** The base priority from `0` to `31`, which combines the priority class of the process and the relative priority of the thread. */
+  basePriority: number
+  /** The current priority from `0` to `31`, which is higher than `basePriority` while the thread is boosted. */
+  currentPriority: number
+}
+
+/** Windows timer resolutions in milliseconds, a smaller value is a finer resolution. */
+export interface TimerResolution {
+  /** The finest resolution the system supports, usually `0.5`. */
+  finest: number
+  /** The coarsest resolution, which is the default, usually `15.625`. */
+  coarsest: number
+  /** The resolution currently in effect. */
+  current: number
+}
+
+/**
+ * This function calls `callback` with the current CPU pressure whenever runnable tasks wait for a CPU more than `threshold` percent of `window`, at most once per window.
+ *
+ * On Linux, it registers a [PSI trigger](https://docs.kernel.org/accounting/psi.html#monitoring-for-pressure-thresholds) on `/proc/pressure/cpu` and waits for it with `poll(2)`.
+ * Unprivileged triggers require Linux 6.5.
+ *
+ * On other platforms, it throws.
+ */
+export declare function watchCpuPressure(threshold: number, callback: (arg: CpuPressure) => void, options?: CpuPressureOptions | undefined | null): Watcher
+
+/**
+ * Calls a callback from a background thread when a watched value changes, until `stop()` is called.
+ * It doesn't keep the process alive, and it keeps watching even when it's garbage collected, so it must be stopped when it's no longer needed.
+ *
+ * It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+ */
+export declare class Watcher {
+  /** Stops watching, calling it more than once has no effect. */
+  stop(): void
+  /** Whether `stop()` has already been called. */
+  get stopped(): boolean
+}
+
+export interface WatchOptions {
+  /** How often the value is checked in milliseconds, it defaults to `1000` and must be at least `1`. */
+  interval?: number
+}
+
+/**
+ * This function calls `callback` whenever the priority of the process identified by `pid` changes, for example when an administrator renices it, checking it every `interval` on a background thread.
+ * The priority of the current process is the nice value of its main thread on Unix.
+ *
+ * On Unix, it polls [`getpriority(2)`](https://linux.die.net/man/2/getpriority) with `PRIO_PROCESS`.
+ *
+ * On Windows, it polls the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function, so only changes of the priority class are reported.
+ */
+export declare function watchPriority(callback: (arg: PriorityChangeEvent) => void, options?: PriorityWatchOptions | undefined | null): Watcher
+
+/** Windows I/O priority hints, see [I/O prioritization](https://learn.microsoft.com/en-us/windows-hardware/drivers/kernel/i-o-prioritization-in-windows-vista). */
+export declare const enum WindowsIoPriority {
+  /** Background I/O, such as indexing or defragmentation. */
+  VeryLow = 0,
+  Low = 1,
+  /** The default priority. */
+  Normal = 2,
+  /** Reserved for the system, it requires administrator privileges. */
+  High = 3
+}
+
+export declare const enum WindowsThreadPriority {
+  ThreadModeBackgroundBegin = 65536,
+  ThreadModeBackgroundEnd = 131072,
+  ThreadPriorityAboveNormal = 1,
+  ThreadPriorityBelowNormal = -1,
+  ThreadPriorityHighest = 2,
+  ThreadPriorityIdle = -15,
+  ThreadPriorityLowest = -2,
+  ThreadPriorityNormal = 0,
+  ThreadPriorityTimeCritical = 15
+}
+
+/** One row of the mapping between nice values and `WindowsThreadPriority`. */
+export interface WindowsThreadPriorityMapping {
+  windowsThreadPriority: WindowsThreadPriority
+  /** The nice value `windowsThreadPriority` is translated to. */
+  nice: number
+  /** The lowest nice value translated to `windowsThreadPriority`. */
+  minNice: number
+  /** The highest nice value translated to `windowsThreadPriority`. */
+  maxNice: number
+}
+
+/**
+ * This function translate a `WindowsThreadPriority` into a nice value.
+ * `ThreadModeBackgroundBegin` and `ThreadModeBackgroundEnd` are modes rather than priorities, they have no nice equivalent and throw.
+ */
+export declare function windowsThreadPriorityToNice(priority: number): number
+
+/**
+ * This function set the priority of the current process to `level`, invokes `callback`, and restores the previous priority afterwards, even if `callback` throws.
+ * If `callback` returns a `Promise`, the priority is restored once the `Promise` settles, and the returned `Promise` rejects if restoring fails.
+ *
+ * Restoring a higher priority than `level` requires the same privileges as raising the priority (root or CAP_SYS_NICE on Unix).
+ */
+export declare function withPriority<T>(level: PriorityLevel, callback: () => T): T
+
+/**
+ * Joins the calling thread to an `os_workgroup`, such as the workgroup of an audio device, so threads created in Node.js are scheduled with the deadline of the workgroup on Apple silicon.
+ * The membership lasts until `leave()` is called or the object is garbage collected, and it must be created on the thread to join, such as a worker thread.
+ *
+ * It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+ *
+ * On macOS, it uses the [`os_workgroup_join` and `os_workgroup_leave`](https://github.com/apple-oss-distributions/libdispatch/blob/main/os/workgroup_object.h) functions.
+ *
+ * On other platforms, the constructor throws.
+ */
+export declare class WorkgroupJoin {
+  /**
+   * `workgroup` is an external wrapping an `os_workgroup_t`, for example the `kAudioDevicePropertyIOThreadOSWorkgroup` of an audio device exposed by a CoreAudio addon.
+   * The workgroup is retained while the thread is a member.
+   */
+  constructor(workgroup: object)
+  /** Leaves the workgroup, calling it more than once has no effect. */
+  leave(): void
+  /** Whether `leave()` has already been called. */
+  get left(): boolean
+}
+
+/**
+ * This function yields the processor to another thread which is ready to run, so spin-wait loops can cooperate with the scheduler.
+ *
+ * On Unix, it uses the [`sched_yield(2)`](https://man7.org/linux/man-pages/man2/sched_yield.2.html) function.
+ *
+ * On Windows, it uses the [`SwitchToThread`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-switchtothread) function.
+ *
+ * It returns `false` when there was no other thread to switch to. On Unix, it always returns `true`.
+ */
+export declare function yieldNow(): boolean
 
@@ -361,6 +361,153 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
+module.exports.adjustPriority = nativeBinding.adjustPriority
+module.exports.AudioThreadPriority = nativeBinding.AudioThreadPriority
+module.exports.beginActivity = nativeBinding.beginActivity
+module.exports.boostFor = nativeBinding.boostFor
+module.exports.canRaisePriority = nativeBinding.canRaisePriority
+module.exports.canSetPriority = nativeBinding.canSetPriority
+module.exports.clearTimerResolution = nativeBinding.clearTimerResolution
+module.exports.DarwinIoPolicy = nativeBinding.DarwinIoPolicy
+module.exports.enableAudioThreadPriority = nativeBinding.enableAudioThreadPriority
+module.exports.enableIncreaseBasePriorityPrivilege = nativeBinding.enableIncreaseBasePriorityPrivilege
+module.exports.endActivity = nativeBinding.endActivity
+module.exports.enterBackgroundMode = nativeBinding.enterBackgroundMode
+module.exports.exitBackgroundMode = nativeBinding.exitBackgroundMode
+module.exports.formatCpuList = nativeBinding.formatCpuList
+module.exports.getAllowedCpus = nativeBinding.getAllowedCpus
+module.exports.getCgroupCpuMax = nativeBinding.getCgroupCpuMax
+module.exports.getCgroupCpuWeight = nativeBinding.getCgroupCpuWeight
+module.exports.getCgroupIdle = nativeBinding.getCgroupIdle
+module.exports.getContextSwitches = nativeBinding.getContextSwitches
+module.exports.getCoreClasses = nativeBinding.getCoreClasses
+module.exports.getCpuAffinity = nativeBinding.getCpuAffinity
+module.exports.getCpuPressure = nativeBinding.getCpuPressure
+module.exports.getCpuTopology = nativeBinding.getCpuTopology
+module.exports.getCurrentCpu = nativeBinding.getCurrentCpu
 module.exports.getCurrentProcessPriority = nativeBinding.getCurrentProcessPriority
+module.exports.getDarwinIoPolicy = nativeBinding.getDarwinIoPolicy
+module.exports.getEffectiveCpuLimit = nativeBinding.getEffectiveCpuLimit
+module.exports.getEffectivePriorityRange = nativeBinding.getEffectivePriorityRange
+module.exports.getIoPriority = nativeBinding.getIoPriority
+module.exports.getLatencyNice = nativeBinding.getLatencyNice
+module.exports.getMetricsText = nativeBinding.getMetricsText
+module.exports.getNativeThreadId = nativeBinding.getNativeThreadId
+module.exports.getNormalizedPriority = nativeBinding.getNormalizedPriority
+module.exports.getOomScore = nativeBinding.getOomScore
+module.exports.getOomScoreAdj = nativeBinding.getOomScoreAdj
+module.exports.getPlatformPriorityInfo = nativeBinding.getPlatformPriorityInfo
+module.exports.getPowerSource = nativeBinding.getPowerSource
+module.exports.getPriority = nativeBinding.getPriority
+module.exports.getPriorityLevel = nativeBinding.getPriorityLevel
+module.exports.getProcessCycleTime = nativeBinding.getProcessCycleTime
+module.exports.getProcessDefaultCpuSets = nativeBinding.getProcessDefaultCpuSets
+module.exports.getProcessGroupPriority = nativeBinding.getProcessGroupPriority
+module.exports.getProcessIoPriority = nativeBinding.getProcessIoPriority
+module.exports.getProcessorGroups = nativeBinding.getProcessorGroups
+module.exports.getProcessPriorityBoost = nativeBinding.getProcessPriorityBoost
+module.exports.getProcessPriorityClass = nativeBinding.getProcessPriorityClass
+module.exports.getQosClass = nativeBinding.getQosClass
+module.exports.getResourceUsage = nativeBinding.getResourceUsage
+module.exports.getRoundRobinInterval = nativeBinding.getRoundRobinInterval
+module.exports.getSchedStats = nativeBinding.getSchedStats
+module.exports.getScheduler = nativeBinding.getScheduler
+module.exports.getSchedulerPriorityRange = nativeBinding.getSchedulerPriorityRange
+module.exports.getSchedulingSnapshot = nativeBinding.getSchedulingSnapshot
+module.exports.getSchedulingState = nativeBinding.getSchedulingState
+module.exports.getStats = nativeBinding.getStats
+module.exports.getSystemCpuSets = nativeBinding.getSystemCpuSets
+module.exports.getSystemLoad = nativeBinding.getSystemLoad
+module.exports.getThermalState = nativeBinding.getThermalState
+module.exports.getThreadAffinityTag = nativeBinding.getThreadAffinityTag
+module.exports.getThreadCycleTime = nativeBinding.getThreadCycleTime
+module.exports.getThreadGroupAffinity = nativeBinding.getThreadGroupAffinity
+module.exports.getThreadIdealProcessor = nativeBinding.getThreadIdealProcessor
+module.exports.getThreadIoPriority = nativeBinding.getThreadIoPriority
+module.exports.getThreadPriorityBoost = nativeBinding.getThreadPriorityBoost
+module.exports.getThreadSchedulingInfo = nativeBinding.getThreadSchedulingInfo
+module.exports.getThreadSelectedCpuSets = nativeBinding.getThreadSelectedCpuSets
+module.exports.getTimerResolution = nativeBinding.getTimerResolution
+module.exports.getTimerSlack = nativeBinding.getTimerSlack
+module.exports.getWindowsThreadPriorityMapping = nativeBinding.getWindowsThreadPriorityMapping
+module.exports.IoPriorityClass = nativeBinding.IoPriorityClass
+module.exports.isInBackgroundMode = nativeBinding.isInBackgroundMode
+module.exports.JobObject = nativeBinding.JobObject
+module.exports.listThreads = nativeBinding.listThreads
+module.exports.lockPriorityCeiling = nativeBinding.lockPriorityCeiling
+module.exports.measureSchedulingJitter = nativeBinding.measureSchedulingJitter
+module.exports.MmcssPriority = nativeBinding.MmcssPriority
+module.exports.MmcssTask = nativeBinding.MmcssTask
+module.exports.moveToCgroup = nativeBinding.moveToCgroup
 module.exports.nice = nativeBinding.nice
+module.exports.niceToWindowsThreadPriority = nativeBinding.niceToWindowsThreadPriority
+module.exports.niceWithPrevious = nativeBinding.niceWithPrevious
+module.exports.onExternalPriorityChange = nativeBinding.onExternalPriorityChange
+module.exports.onPowerSourceChange = nativeBinding.onPowerSourceChange
+module.exports.onThermalStateChange = nativeBinding.onThermalStateChange
+module.exports.parseCpuList = nativeBinding.parseCpuList
+module.exports.pinToEfficiencyCores = nativeBinding.pinToEfficiencyCores
+module.exports.pinToPerformanceCores = nativeBinding.pinToPerformanceCores
+module.exports.PowerSource = nativeBinding.PowerSource
+module.exports.PriorityGuard = nativeBinding.PriorityGuard
+module.exports.PriorityLevel = nativeBinding.PriorityLevel
+module.exports.ProcessPriorityClass = nativeBinding.ProcessPriorityClass
+module.exports.QosClass = nativeBinding.QosClass
+module.exports.QosOverride = nativeBinding.QosOverride
+module.exports.reniceBy = nativeBinding.reniceBy
+module.exports.resetStats = nativeBinding.resetStats
+module.exports.restoreDefaultPriority = nativeBinding.restoreDefaultPriority
+module.exports.sampleThreadCpu = nativeBinding.sampleThreadCpu
+module.exports.SchedulerPolicy = nativeBinding.SchedulerPolicy
+module.exports.setAbsolutePriority = nativeBinding.setAbsolutePriority
+module.exports.setAllThreadsPriority = nativeBinding.setAllThreadsPriority
+module.exports.setCgroupCpuMax = nativeBinding.setCgroupCpuMax
+module.exports.setCgroupCpuWeight = nativeBinding.setCgroupCpuWeight
+module.exports.setCgroupIdle = nativeBinding.setCgroupIdle
+module.exports.setCpuAffinity = nativeBinding.setCpuAffinity
+module.exports.setDarwinBackground = nativeBinding.setDarwinBackground
+module.exports.setDarwinIoPolicy = nativeBinding.setDarwinIoPolicy
+module.exports.setEfficiencyMode = nativeBinding.setEfficiencyMode
+module.exports.setHighestAllowed = nativeBinding.setHighestAllowed
+module.exports.setIdlePriority = nativeBinding.setIdlePriority
+module.exports.setIoPriority = nativeBinding.setIoPriority
+module.exports.setLatencyNice = nativeBinding.setLatencyNice
+module.exports.setLowestPossible = nativeBinding.setLowestPossible
+module.exports.setNormalizedPriority = nativeBinding.setNormalizedPriority
+module.exports.setNumaNode = nativeBinding.setNumaNode
+module.exports.setOomScoreAdj = nativeBinding.setOomScoreAdj
+module.exports.setPriority = nativeBinding.setPriority
+module.exports.setPriorityLevel = nativeBinding.setPriorityLevel
+module.exports.setPriorityTree = nativeBinding.setPriorityTree
+module.exports.setProcessBackgroundMode = nativeBinding.setProcessBackgroundMode
+module.exports.setProcessDefaultCpuSets = nativeBinding.setProcessDefaultCpuSets
+module.exports.setProcessGroupPriority = nativeBinding.setProcessGroupPriority
+module.exports.setProcessIoPriority = nativeBinding.setProcessIoPriority
+module.exports.setProcessPriorityBoost = nativeBinding.setProcessPriorityBoost
+module.exports.setProcessPriorityClass = nativeBinding.setProcessPriorityClass
+module.exports.setQosClass = nativeBinding.setQosClass
+module.exports.setRealtimeThreadPolicy = nativeBinding.setRealtimeThreadPolicy
+module.exports.setScheduler = nativeBinding.setScheduler
+module.exports.setSystemdUnitCpuWeight = nativeBinding.setSystemdUnitCpuWeight
+module.exports.setSystemdUnitIoWeight = nativeBinding.setSystemdUnitIoWeight
+module.exports.setThreadAffinityTag = nativeBinding.setThreadAffinityTag
+module.exports.setThreadEfficiencyMode = nativeBinding.setThreadEfficiencyMode
+module.exports.setThreadGroupAffinity = nativeBinding.setThreadGroupAffinity
+module.exports.setThreadIdealProcessor = nativeBinding.setThreadIdealProcessor
+module.exports.setThreadIoPriority = nativeBinding.setThreadIoPriority
+module.exports.setThreadPriority = nativeBinding.setThreadPriority
+module.exports.setThreadPriorityBoost = nativeBinding.setThreadPriorityBoost
+module.exports.setThreadSelectedCpuSets = nativeBinding.setThreadSelectedCpuSets
+module.exports.setTimerResolution = nativeBinding.setTimerResolution
+module.exports.setTimerSlack = nativeBinding.setTimerSlack
+module.exports.setUserPriority = nativeBinding.setUserPriority
+module.exports.ThermalState = nativeBinding.ThermalState
+module.exports.watchCpuPressure = nativeBinding.watchCpuPressure
+module.exports.Watcher = nativeBinding.Watcher
+module.exports.watchPriority = nativeBinding.watchPriority
+module.exports.WindowsIoPriority = nativeBinding.WindowsIoPriority
 module.exports.WindowsThreadPriority = nativeBinding.WindowsThreadPriority
+module.exports.windowsThreadPriorityToNice = nativeBinding.windowsThreadPriorityToNice
+module.exports.withPriority = nativeBinding.withPriority
+module.exports.WorkgroupJoin = nativeBinding.WorkgroupJoin
+module.exports.yieldNow = nativeBinding.yieldNow
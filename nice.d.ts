@@ -0,0 +1,28 @@
+export * from './index'
+
+declare module './index' {
+  interface AudioThreadPriority {
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+  }
+  interface MmcssTask {
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+  }
+  interface PriorityGuard {
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+  }
+  interface QosOverride {
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+  }
+  interface Watcher {
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+  }
+  interface WorkgroupJoin {
+    [Symbol.dispose](): void
+    [Symbol.asyncDispose](): Promise<void>
+  }
+}
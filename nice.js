@@ -1,18 +1,45 @@
-let nice
-let getCurrentProcessPriority
+let binding
 
 try {
-  const { nice: niceNative, getCurrentProcessPriority: getCurrentProcessPriorityNative } = require('./index.js')
-  nice = niceNative
-  getCurrentProcessPriority = getCurrentProcessPriorityNative
+  binding = require('./index.js')
 } catch (e) {
   if (process.platform !== 'win32') {
     throw e
   }
   // fallback on Windows
-  nice = function nice(incr) { return incr }
-  getCurrentProcessPriority = function getCurrentProcessPriority() { return 1 }
+  binding = {
+    nice: function nice(incr) {
+      return incr
+    },
+    getCurrentProcessPriority: function getCurrentProcessPriority() {
+      return 1
+    },
+  }
+}
+
+// napi-rs can't define symbol keyed methods, install the disposable protocol here
+for (const [name, release] of [
+  ['AudioThreadPriority', 'revert'],
+  ['MmcssTask', 'revert'],
+  ['PriorityGuard', 'restore'],
+  ['QosOverride', 'end'],
+  ['Watcher', 'stop'],
+  ['WorkgroupJoin', 'leave'],
+]) {
+  const Class = binding[name]
+  if (!Class) {
+    continue
+  }
+  if (typeof Symbol.dispose === 'symbol') {
+    Class.prototype[Symbol.dispose] = function dispose() {
+      this[release]()
+    }
+  }
+  if (typeof Symbol.asyncDispose === 'symbol') {
+    Class.prototype[Symbol.asyncDispose] = async function asyncDispose() {
+      this[release]()
+    }
+  }
 }
 
-module.exports.nice = nice
-module.exports.getCurrentProcessPriority = getCurrentProcessPriority
+module.exports = binding
@@ -0,0 +1,154 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+#[cfg(target_vendor = "apple")]
+use crate::objc::{self, msg_send};
+
+#[cfg(target_vendor = "apple")]
+/// Calls `-[NSProcessInfo beginActivityWithOptions:reason:]` and returns the retained activity, or null on failure.
+fn objc_begin_activity(options: u64, reason: &std::ffi::CStr) -> objc::Id {
+  objc::with_autorelease_pool(|| unsafe {
+    let reason: objc::Id = msg_send!(
+      objc::objc_getClass(c"NSString".as_ptr()),
+      c"stringWithUTF8String:",
+      reason.as_ptr() => *const std::ffi::c_char;
+      objc::Id
+    );
+    if reason.is_null() {
+      return std::ptr::null_mut();
+    }
+    let activity: objc::Id = msg_send!(
+      objc::process_info(),
+      c"beginActivityWithOptions:reason:",
+      options => u64,
+      reason => objc::Id;
+      objc::Id
+    );
+    if activity.is_null() {
+      return activity;
+    }
+    msg_send!(activity, c"retain"; objc::Id)
+  })
+}
+
+#[cfg(target_vendor = "apple")]
+/// Calls `-[NSProcessInfo endActivity:]` and releases the activity returned by `objc_begin_activity`.
+fn objc_end_activity(activity: objc::Id) {
+  objc::with_autorelease_pool(|| unsafe {
+    msg_send!(objc::process_info(), c"endActivity:", activity => objc::Id; ());
+    msg_send!(activity, c"release"; ());
+  })
+}
+
+#[cfg(target_vendor = "apple")]
+/// The retained activities with their token.
+static ACTIVITIES: std::sync::Mutex<Vec<(u32, usize)>> = std::sync::Mutex::new(Vec::new());
+
+#[cfg(target_vendor = "apple")]
+/// Tokens are never reused, so ending an activity twice can't end another one.
+static NEXT_TOKEN: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(1);
+
+/// Maps an [`NSActivityOptions`](https://developer.apple.com/documentation/foundation/nsactivityoptions) name onto its value.
+fn activity_option(name: &str) -> Result<u64> {
+  const IDLE_SYSTEM_SLEEP_DISABLED: u64 = 1 << 20;
+  const USER_INITIATED: u64 = 0x00FF_FFFF | IDLE_SYSTEM_SLEEP_DISABLED;
+  match name {
+    "idleDisplaySleepDisabled" => Ok(1 << 40),
+    "idleSystemSleepDisabled" => Ok(IDLE_SYSTEM_SLEEP_DISABLED),
+    "suddenTerminationDisabled" => Ok(1 << 14),
+    "automaticTerminationDisabled" => Ok(1 << 15),
+    "userInitiated" => Ok(USER_INITIATED),
+    "userInitiatedAllowingIdleSystemSleep" => Ok(USER_INITIATED & !IDLE_SYSTEM_SLEEP_DISABLED),
+    "background" => Ok(0xFF),
+    "latencyCritical" => Ok(0xFF_0000_0000),
+    _ => Err(Error::new(
+      Status::InvalidArg,
+      format!("{name} is not a valid activity option"),
+    )),
+  }
+}
+
+#[napi]
+/// This function tells macOS that the process is performing a long-running task, so it isn't App Napped or timer-throttled while the app is in the background, and returns a token for `endActivity`.
+/// `options` are the names of the [`NSActivityOptions`](https://developer.apple.com/documentation/foundation/nsactivityoptions) to combine, and `reason` is shown in diagnostic tools.
+///
+/// On macOS, it uses the [`beginActivityWithOptions:reason:`](https://developer.apple.com/documentation/foundation/nsprocessinfo/1415995-beginactivitywithoptions) method of `NSProcessInfo`.
+///
+/// On other platforms, it throws.
+pub fn begin_activity(
+  #[napi(
+    ts_arg_type = "Array<'idleDisplaySleepDisabled' | 'idleSystemSleepDisabled' | 'suddenTerminationDisabled' | 'automaticTerminationDisabled' | 'userInitiated' | 'userInitiatedAllowingIdleSystemSleep' | 'background' | 'latencyCritical'>"
+  )]
+  options: Vec<String>,
+  reason: String,
+) -> Result<u32> {
+  let mut flags = 0;
+  for name in &options {
+    flags |= activity_option(name)?;
+  }
+  if flags == 0 {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "At least one activity option is required",
+    ));
+  }
+  #[cfg(target_vendor = "apple")]
+  {
+    let reason = std::ffi::CString::new(reason).map_err(|_| {
+      Error::new(
+        Status::InvalidArg,
+        "The activity reason can't contain NUL characters",
+      )
+    })?;
+    let activity = objc_begin_activity(flags, &reason);
+    if activity.is_null() {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "Failed to begin the activity",
+      ));
+    }
+    let token = NEXT_TOKEN.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    ACTIVITIES
+      .lock()
+      .unwrap_or_else(|err| err.into_inner())
+      .push((token, activity as usize));
+    Ok(token)
+  }
+  #[cfg(not(target_vendor = "apple"))]
+  {
+    let _ = (flags, reason);
+    Err(crate::sys::unsupported("Activities"))
+  }
+}
+
+#[napi]
+/// This function ends the activity identified by `token`, returned by `beginActivity`.
+///
+/// On macOS, it uses the [`endActivity:`](https://developer.apple.com/documentation/foundation/nsprocessinfo/1411091-endactivity) method of `NSProcessInfo`.
+///
+/// On other platforms, it throws.
+pub fn end_activity(token: u32) -> Result<()> {
+  #[cfg(target_vendor = "apple")]
+  {
+    let activity = {
+      let mut activities = ACTIVITIES.lock().unwrap_or_else(|err| err.into_inner());
+      let index = activities
+        .iter()
+        .position(|&(id, _)| id == token)
+        .ok_or_else(|| {
+          Error::new(
+            Status::InvalidArg,
+            format!("{token} is not an active activity"),
+          )
+        })?;
+      activities.swap_remove(index).1
+    };
+    objc_end_activity(activity as objc::Id);
+    Ok(())
+  }
+  #[cfg(not(target_vendor = "apple"))]
+  {
+    let _ = token;
+    Err(crate::sys::unsupported("Activities"))
+  }
+}
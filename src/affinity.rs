@@ -0,0 +1,765 @@
+use napi::bindgen_prelude::Either;
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi(object)]
+pub struct AffinityOptions {
+  /// Whether the affinity of the calling thread or of the whole process is used, it defaults to `thread`.
+  #[napi(ts_type = "'thread' | 'process'")]
+  pub target: Option<String>,
+  /// The process to use instead of the current one, `target` must be `process` or omitted.
+  pub pid: Option<u32>,
+  /// The native id of the thread to use instead of the calling one (see `getNativeThreadId`), `target` must be `thread` or omitted.
+  pub tid: Option<u32>,
+}
+
+enum AffinityTarget {
+  CurrentThread,
+  CurrentProcess,
+  Thread(u32),
+  Process(u32),
+}
+
+impl AffinityOptions {
+  fn resolve(options: Option<&Self>) -> Result<AffinityTarget> {
+    let Some(options) = options else {
+      return Ok(AffinityTarget::CurrentThread);
+    };
+    let invalid = |message: &str| Err(Error::new(Status::InvalidArg, message.to_owned()));
+    match (options.target.as_deref(), options.pid, options.tid) {
+      (_, Some(_), Some(_)) => invalid("pid and tid can't be used together"),
+      (None | Some("process"), Some(pid), None) => Ok(AffinityTarget::Process(pid)),
+      (Some(_), Some(_), None) => invalid("target must be process when pid is set"),
+      (None | Some("thread"), None, Some(tid)) => Ok(AffinityTarget::Thread(tid)),
+      (Some(_), None, Some(_)) => invalid("target must be thread when tid is set"),
+      (None | Some("thread"), None, None) => Ok(AffinityTarget::CurrentThread),
+      (Some("process"), None, None) => Ok(AffinityTarget::CurrentProcess),
+      (Some(target), None, None) => Err(Error::new(
+        Status::InvalidArg,
+        format!("{target} is not a valid target, expected thread or process"),
+      )),
+    }
+  }
+}
+
+impl AffinityTarget {
+  fn describe(&self) -> String {
+    match self {
+      Self::CurrentThread | Self::CurrentProcess => String::new(),
+      Self::Thread(tid) => format!(" of thread {tid}"),
+      Self::Process(pid) => format!(" of process {pid}"),
+    }
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Lists the CPUs the thread or process `pid` may run on, `0` means the calling thread.
+pub(crate) fn sched_getaffinity(pid: libc::pid_t) -> std::io::Result<Vec<u32>> {
+  let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+  if unsafe { libc::sched_getaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &mut set) } == -1
+  {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(
+    (0..libc::CPU_SETSIZE as usize)
+      .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+      .map(|cpu| cpu as u32)
+      .collect(),
+  )
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Restricts the thread or process `pid` to `cpus`, `0` means the calling thread.
+pub(crate) fn sched_setaffinity(pid: libc::pid_t, cpus: &[u32]) -> std::io::Result<()> {
+  let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+  for &cpu in cpus {
+    unsafe { libc::CPU_SET(cpu as usize, &mut set) };
+  }
+  if unsafe { libc::sched_setaffinity(pid, std::mem::size_of::<libc::cpu_set_t>(), &set) } == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+/// The number of CPUs an affinity mask can address on the current platform, the size of a Linux `cpu_set_t` elsewhere.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+const MAX_CPUS: u32 = libc::CPU_SETSIZE as u32;
+#[cfg(windows)]
+const MAX_CPUS: u32 = usize::BITS;
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  target_os = "freebsd",
+  windows
+)))]
+const MAX_CPUS: u32 = 1024;
+
+/// Parses a Linux CPU list such as `0-3,8`, as found in sysfs, `None` means the list is malformed or lists a CPU from `MAX_CPUS` on, so a hostile range can't exhaust the memory.
+pub(crate) fn try_parse_cpu_list(list: &str) -> Option<Vec<u32>> {
+  let mut cpus = Vec::new();
+  for range in list.trim().split(',').map(str::trim) {
+    if range.is_empty() {
+      continue;
+    }
+    match range.split_once('-') {
+      Some((start, end)) => {
+        let (start, end) = (start.trim().parse::<u32>().ok()?, end.trim().parse().ok()?);
+        if start > end || end >= MAX_CPUS {
+          return None;
+        }
+        cpus.extend(start..=end);
+      }
+      None => cpus.push(range.parse().ok().filter(|&cpu| cpu < MAX_CPUS)?),
+    }
+  }
+  cpus.sort_unstable();
+  cpus.dedup();
+  Some(cpus)
+}
+
+#[napi]
+/// This function parses a Linux-style CPU list such as `0-3,8,10-11`, as used by `taskset`, cgroups and Kubernetes, into CPU indexes.
+/// The CPUs must be lower than the number of CPUs an affinity mask can address, `CPU_SETSIZE` on Linux and FreeBSD and the number of bits of a processor group mask on Windows.
+pub fn parse_cpu_list(list: String) -> Result<Vec<u32>> {
+  try_parse_cpu_list(&list).ok_or_else(|| {
+    Error::new(
+      Status::InvalidArg,
+      format!("{list:?} is not a valid CPU list, expected a list such as 0-3,8"),
+    )
+  })
+}
+
+#[napi]
+/// This function formats CPU indexes as a Linux-style CPU list such as `0-3,8,10-11`, the reverse of `parseCpuList`.
+pub fn format_cpu_list(cpus: Vec<u32>) -> String {
+  let mut cpus = cpus;
+  cpus.sort_unstable();
+  cpus.dedup();
+  let mut ranges = Vec::new();
+  let mut iter = cpus.into_iter().peekable();
+  while let Some(start) = iter.next() {
+    let mut end = start;
+    while iter.peek() == Some(&(end + 1)) {
+      end += 1;
+      iter.next();
+    }
+    ranges.push(if start == end {
+      start.to_string()
+    } else {
+      format!("{start}-{end}")
+    });
+  }
+  ranges.join(",")
+}
+
+#[cfg(target_os = "freebsd")]
+/// The `which` and `id` arguments of `cpuset_setaffinity(2)` for `target`, `-1` means the caller.
+fn cpuset_which(target: &AffinityTarget) -> (libc::cpuwhich_t, libc::id_t) {
+  match *target {
+    AffinityTarget::CurrentThread => (libc::CPU_WHICH_TID, -1),
+    AffinityTarget::CurrentProcess => (libc::CPU_WHICH_PID, -1),
+    AffinityTarget::Thread(tid) => (libc::CPU_WHICH_TID, tid as libc::id_t),
+    AffinityTarget::Process(pid) => (libc::CPU_WHICH_PID, pid as libc::id_t),
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "android",
+  target_os = "freebsd",
+  windows
+))]
+pub(crate) fn validate_cpus(cpus: &[u32]) -> Result<()> {
+  if cpus.is_empty() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "cpus must contain at least one CPU".to_owned(),
+    ));
+  }
+  if let Some(cpu) = cpus.iter().find(|&&cpu| cpu >= MAX_CPUS) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("{cpu} is not a valid CPU, expected a value lower than {MAX_CPUS}"),
+    ));
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+pub(crate) fn cpus_to_mask(cpus: &[u32]) -> usize {
+  cpus.iter().fold(0, |mask, cpu| mask | (1 << cpu))
+}
+
+#[cfg(windows)]
+pub(crate) fn mask_to_cpus(mask: usize) -> Vec<u32> {
+  (0..usize::BITS)
+    .filter(|cpu| mask & (1 << cpu) != 0)
+    .collect()
+}
+
+#[cfg(windows)]
+fn process_affinity_mask(process: windows::Win32::Foundation::HANDLE) -> std::io::Result<usize> {
+  use windows::Win32::System::Threading::GetProcessAffinityMask;
+
+  let mut process_mask = 0;
+  let mut system_mask = 0;
+  unsafe { GetProcessAffinityMask(process, &mut process_mask, &mut system_mask) }
+    .map_err(sys::win32_error)?;
+  Ok(process_mask)
+}
+
+#[cfg(windows)]
+fn set_thread_affinity_mask(
+  thread: windows::Win32::Foundation::HANDLE,
+  mask: usize,
+) -> std::io::Result<usize> {
+  match unsafe { windows::Win32::System::Threading::SetThreadAffinityMask(thread, mask) } {
+    0 => Err(std::io::Error::last_os_error()),
+    previous => Ok(previous),
+  }
+}
+
+#[cfg(windows)]
+/// Reads the affinity of a thread from `ThreadBasicInformation`, since there is no documented function to read it without setting it.
+fn thread_affinity_mask(thread: windows::Win32::Foundation::HANDLE) -> std::io::Result<usize> {
+  Ok(crate::thread::thread_basic_information(thread)?.affinity_mask)
+}
+
+#[napi]
+/// This function restricts the calling thread to the CPUs listed in `cpus`, either CPU indexes or a Linux-style CPU list such as `0-3,8`.
+/// Pass `{ target: 'process' }` to use the whole process, `{ pid }` to use another process, or `{ tid }` to use another thread.
+///
+/// On Linux, it uses the [`sched_setaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_setaffinity.2.html) function. For a process, every thread of `/proc/{pid}/task` is updated.
+///
+/// On Windows, it uses the [`SetThreadAffinityMask`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-setthreadaffinitymask) or [`SetProcessAffinityMask`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-setprocessaffinitymask) function.
+/// Only the CPUs of the current processor group can be used.
+///
+/// On FreeBSD, it uses the [`cpuset_setaffinity(2)`](https://man.freebsd.org/cgi/man.cgi?query=cpuset_setaffinity) function. For a process, every thread is updated.
+///
+/// On other platforms, it throws.
+pub fn set_cpu_affinity(
+  cpus: Either<Vec<u32>, String>,
+  options: Option<AffinityOptions>,
+) -> Result<()> {
+  crate::stats::record("setCpuAffinity", || apply_cpu_affinity(cpus, options))
+}
+
+pub(crate) fn apply_cpu_affinity(
+  cpus: Either<Vec<u32>, String>,
+  options: Option<AffinityOptions>,
+) -> Result<()> {
+  let cpus = match cpus {
+    Either::A(cpus) => cpus,
+    Either::B(list) => parse_cpu_list(list)?,
+  };
+  let target = AffinityOptions::resolve(options.as_ref())?;
+  let action = || format!("set the CPU affinity{}", target.describe());
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    validate_cpus(&cpus)?;
+    let tids = match target {
+      AffinityTarget::CurrentThread => vec![0],
+      AffinityTarget::Thread(tid) => vec![tid],
+      AffinityTarget::CurrentProcess => crate::thread::list_thread_ids(0)
+        .map_err(|err| sys::priority_error(err, "list the threads of the current process"))?,
+      AffinityTarget::Process(pid) => crate::thread::list_thread_ids(pid)
+        .map_err(|err| sys::priority_error(err, &format!("list the threads of process {pid}")))?,
+    };
+    let listed = matches!(
+      target,
+      AffinityTarget::CurrentProcess | AffinityTarget::Process(_)
+    );
+    for tid in tids {
+      match sched_setaffinity(tid as libc::pid_t, &cpus) {
+        // the thread exited after it was listed
+        Err(err) if listed && err.raw_os_error() == Some(libc::ESRCH) => {}
+        Err(err) if listed => {
+          return Err(sys::priority_error(
+            err,
+            &format!("set the CPU affinity of thread {tid}"),
+          ))
+        }
+        Err(err) => return Err(sys::priority_error(err, &action())),
+        Ok(()) => {}
+      }
+    }
+    Ok(())
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{
+      GetCurrentProcess, GetCurrentThread, SetProcessAffinityMask, PROCESS_SET_INFORMATION,
+      THREAD_QUERY_INFORMATION, THREAD_SET_INFORMATION,
+    };
+
+    validate_cpus(&cpus)?;
+    let mask = cpus_to_mask(&cpus);
+    let ret = match target {
+      AffinityTarget::CurrentThread => {
+        set_thread_affinity_mask(unsafe { GetCurrentThread() }, mask).map(|_| ())
+      }
+      AffinityTarget::Thread(tid) => {
+        sys::open_thread(THREAD_SET_INFORMATION | THREAD_QUERY_INFORMATION, tid)
+          .and_then(|thread| set_thread_affinity_mask(thread.0, mask))
+          .map(|_| ())
+      }
+      AffinityTarget::CurrentProcess => {
+        unsafe { SetProcessAffinityMask(GetCurrentProcess(), mask) }.map_err(sys::win32_error)
+      }
+      AffinityTarget::Process(pid) => {
+        sys::open_process(PROCESS_SET_INFORMATION, pid).and_then(|process| {
+          unsafe { SetProcessAffinityMask(process.0, mask) }.map_err(sys::win32_error)
+        })
+      }
+    };
+    ret.map_err(|err| sys::priority_error(err, &action()))
+  }
+  #[cfg(target_os = "freebsd")]
+  {
+    validate_cpus(&cpus)?;
+    let mut set: libc::cpuset_t = unsafe { std::mem::zeroed() };
+    for &cpu in &cpus {
+      unsafe { libc::CPU_SET(cpu as usize, &mut set) };
+    }
+    let (which, id) = cpuset_which(&target);
+    if unsafe {
+      libc::cpuset_setaffinity(
+        libc::CPU_LEVEL_WHICH,
+        which,
+        id,
+        std::mem::size_of::<libc::cpuset_t>(),
+        &set,
+      )
+    } == -1
+    {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        &action(),
+      ));
+    }
+    Ok(())
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    windows
+  )))]
+  {
+    let _ = (cpus, action);
+    Err(sys::unsupported("Setting the CPU affinity"))
+  }
+}
+
+#[napi]
+/// This function returns the CPUs the calling thread may run on.
+/// Pass `{ target: 'process' }` to use the whole process, `{ pid }` to use another process, or `{ tid }` to use another thread.
+///
+/// On Linux, it uses the [`sched_getaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_getaffinity.2.html) function. For a process, the affinity of its main thread is returned.
+///
+/// On Windows, it uses the [`GetProcessAffinityMask`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getprocessaffinitymask) function. The affinity of a thread is read with the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadBasicInformation`, without changing it.
+///
+/// On FreeBSD, it uses the [`cpuset_getaffinity(2)`](https://man.freebsd.org/cgi/man.cgi?query=cpuset_getaffinity) function.
+///
+/// On other platforms, it throws.
+pub fn get_cpu_affinity(options: Option<AffinityOptions>) -> Result<Vec<u32>> {
+  let target = AffinityOptions::resolve(options.as_ref())?;
+  let action = || format!("get the CPU affinity{}", target.describe());
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let pid = match target {
+      AffinityTarget::CurrentThread => 0,
+      AffinityTarget::CurrentProcess => unsafe { libc::getpid() },
+      AffinityTarget::Thread(id) | AffinityTarget::Process(id) => id as libc::pid_t,
+    };
+    sched_getaffinity(pid).map_err(|err| sys::priority_error(err, &action()))
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{
+      GetCurrentProcess, GetCurrentThread, PROCESS_QUERY_LIMITED_INFORMATION,
+      THREAD_QUERY_LIMITED_INFORMATION,
+    };
+
+    let mask = match target {
+      AffinityTarget::CurrentThread => thread_affinity_mask(unsafe { GetCurrentThread() }),
+      AffinityTarget::Thread(tid) => sys::open_thread(THREAD_QUERY_LIMITED_INFORMATION, tid)
+        .and_then(|thread| thread_affinity_mask(thread.0)),
+      AffinityTarget::CurrentProcess => process_affinity_mask(unsafe { GetCurrentProcess() }),
+      AffinityTarget::Process(pid) => sys::open_process(PROCESS_QUERY_LIMITED_INFORMATION, pid)
+        .and_then(|process| process_affinity_mask(process.0)),
+    };
+    mask
+      .map(mask_to_cpus)
+      .map_err(|err| sys::priority_error(err, &action()))
+  }
+  #[cfg(target_os = "freebsd")]
+  {
+    let mut set: libc::cpuset_t = unsafe { std::mem::zeroed() };
+    let (which, id) = cpuset_which(&target);
+    if unsafe {
+      libc::cpuset_getaffinity(
+        libc::CPU_LEVEL_WHICH,
+        which,
+        id,
+        std::mem::size_of::<libc::cpuset_t>(),
+        &mut set,
+      )
+    } == -1
+    {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        &action(),
+      ));
+    }
+    Ok(
+      (0..libc::CPU_SETSIZE as usize)
+        .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+        .map(|cpu| cpu as u32)
+        .collect(),
+    )
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    windows
+  )))]
+  {
+    let _ = action;
+    Err(sys::unsupported("Getting the CPU affinity"))
+  }
+}
+
+#[napi(object)]
+/// A processor group, Windows machines with more than 64 logical CPUs have several of them.
+pub struct ProcessorGroup {
+  pub group: u32,
+  /// The number of CPUs the group can hold.
+  pub maximum_processor_count: u32,
+  /// The number of CPUs currently available in the group.
+  pub active_processor_count: u32,
+  /// The indexes of the available CPUs within the group.
+  pub active_processors: Vec<u32>,
+}
+
+#[napi(object)]
+pub struct GroupAffinity {
+  pub group: u32,
+  /// The indexes of the CPUs within `group`.
+  pub cpus: Vec<u32>,
+}
+
+#[cfg(windows)]
+/// Calls `visit` with each entry of [`GetLogicalProcessorInformationEx`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getlogicalprocessorinformationex), the entries have different sizes.
+pub(crate) fn logical_processor_information(
+  relationship: windows::Win32::System::SystemInformation::LOGICAL_PROCESSOR_RELATIONSHIP,
+  mut visit: impl FnMut(
+    &windows::Win32::System::SystemInformation::SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX,
+  ),
+) -> std::io::Result<()> {
+  use windows::Win32::System::SystemInformation::{
+    GetLogicalProcessorInformationEx, SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX,
+  };
+
+  let mut length = 0;
+  // the first call only reports the required length
+  let _ = unsafe { GetLogicalProcessorInformationEx(relationship, None, &mut length) };
+  // `u64`s keep the buffer aligned for `SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX`
+  let mut buffer = vec![0u64; (length as usize).div_ceil(8)];
+  let info = buffer.as_mut_ptr() as *mut SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX;
+  unsafe { GetLogicalProcessorInformationEx(relationship, Some(info), &mut length) }
+    .map_err(sys::win32_error)?;
+  let mut offset = 0;
+  while offset < length as usize {
+    let entry = unsafe {
+      &*((buffer.as_ptr() as *const u8).add(offset)
+        as *const SYSTEM_LOGICAL_PROCESSOR_INFORMATION_EX)
+    };
+    visit(entry);
+    offset += entry.Size as usize;
+  }
+  Ok(())
+}
+
+#[napi]
+/// This function returns the processor groups of the machine.
+///
+/// On Windows, it uses the [`GetLogicalProcessorInformationEx`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getlogicalprocessorinformationex) function with `RelationGroup`.
+///
+/// On other platforms, it throws.
+pub fn get_processor_groups() -> Result<Vec<ProcessorGroup>> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::SystemInformation::RelationGroup;
+
+    let mut processor_groups = Vec::new();
+    logical_processor_information(RelationGroup, |info| {
+      let groups = unsafe { &info.Anonymous.Group };
+      let infos = unsafe {
+        std::slice::from_raw_parts(groups.GroupInfo.as_ptr(), groups.ActiveGroupCount as usize)
+      };
+      processor_groups.extend(
+        infos
+          .iter()
+          .enumerate()
+          .map(|(group, info)| ProcessorGroup {
+            group: group as u32,
+            maximum_processor_count: info.MaximumProcessorCount as u32,
+            active_processor_count: info.ActiveProcessorCount as u32,
+            active_processors: mask_to_cpus(info.ActiveProcessorMask),
+          }),
+      );
+    })
+    .map_err(|err| sys::priority_error(err, "get the processor groups"))?;
+    Ok(processor_groups)
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the processor groups"))
+  }
+}
+
+#[napi]
+/// This function restricts the calling thread to the CPUs listed in `cpus` of the processor group `group`, so every CPU of machines with more than 64 logical CPUs can be used.
+///
+/// On Windows, it uses the [`SetThreadGroupAffinity`](https://learn.microsoft.com/en-us/windows/win32/api/processtopologyapi/nf-processtopologyapi-setthreadgroupaffinity) function.
+///
+/// On other platforms, it throws.
+pub fn set_thread_group_affinity(group: u32, cpus: Vec<u32>) -> Result<()> {
+  crate::stats::record("setThreadGroupAffinity", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::SystemInformation::GROUP_AFFINITY;
+      use windows::Win32::System::Threading::{GetCurrentThread, SetThreadGroupAffinity};
+
+      validate_cpus(&cpus)?;
+      let group = u16::try_from(group).map_err(|_| {
+        Error::new(
+          Status::InvalidArg,
+          format!("{group} is not a valid processor group"),
+        )
+      })?;
+      let affinity = GROUP_AFFINITY {
+        Mask: cpus_to_mask(&cpus),
+        Group: group,
+        Reserved: [0; 3],
+      };
+      if !unsafe { SetThreadGroupAffinity(GetCurrentThread(), &affinity, None) }.as_bool() {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "set the group affinity",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = (group, cpus);
+      Err(sys::unsupported("Setting the group affinity"))
+    }
+  })
+}
+
+#[napi]
+/// This function returns the processor group of the calling thread, and the CPUs it may run on within that group.
+///
+/// On Windows, it uses the [`GetThreadGroupAffinity`](https://learn.microsoft.com/en-us/windows/win32/api/processtopologyapi/nf-processtopologyapi-getthreadgroupaffinity) function.
+///
+/// On other platforms, it throws.
+pub fn get_thread_group_affinity() -> Result<GroupAffinity> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::SystemInformation::GROUP_AFFINITY;
+    use windows::Win32::System::Threading::{GetCurrentThread, GetThreadGroupAffinity};
+
+    let mut affinity = GROUP_AFFINITY::default();
+    if !unsafe { GetThreadGroupAffinity(GetCurrentThread(), &mut affinity) }.as_bool() {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get the group affinity",
+      ));
+    }
+    Ok(GroupAffinity {
+      group: affinity.Group as u32,
+      cpus: mask_to_cpus(affinity.Mask),
+    })
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the group affinity"))
+  }
+}
+
+#[napi(object)]
+/// A CPU identified by its processor group and its index within that group.
+pub struct ProcessorNumber {
+  pub group: u32,
+  pub number: u32,
+}
+
+#[napi]
+/// This function sets the ideal processor of the calling thread, a soft placement hint the scheduler prefers without restricting the thread to it, and returns the previous one.
+///
+/// On Windows, it uses the [`SetThreadIdealProcessorEx`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadidealprocessorex) function.
+///
+/// On other platforms, it throws.
+pub fn set_thread_ideal_processor(processor: ProcessorNumber) -> Result<ProcessorNumber> {
+  crate::stats::record("setThreadIdealProcessor", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Kernel::PROCESSOR_NUMBER;
+      use windows::Win32::System::Threading::{GetCurrentThread, SetThreadIdealProcessorEx};
+
+      let (group, number) = match (
+        u16::try_from(processor.group),
+        u8::try_from(processor.number),
+      ) {
+        (Ok(group), Ok(number)) => (group, number),
+        _ => {
+          return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+              "CPU {} of processor group {} is not a valid processor",
+              processor.number, processor.group
+            ),
+          ))
+        }
+      };
+      let ideal = PROCESSOR_NUMBER {
+        Group: group,
+        Number: number,
+        Reserved: 0,
+      };
+      let mut previous = PROCESSOR_NUMBER::default();
+      unsafe { SetThreadIdealProcessorEx(GetCurrentThread(), &ideal, Some(&mut previous)) }
+        .map_err(|err| sys::priority_error(sys::win32_error(err), "set the ideal processor"))?;
+      Ok(ProcessorNumber {
+        group: previous.Group as u32,
+        number: previous.Number as u32,
+      })
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = processor;
+      Err(sys::unsupported("Setting the ideal processor"))
+    }
+  })
+}
+
+#[napi]
+/// This function returns the ideal processor of the calling thread.
+///
+/// On Windows, it uses the [`GetThreadIdealProcessorEx`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadidealprocessorex) function.
+///
+/// On other platforms, it throws.
+pub fn get_thread_ideal_processor() -> Result<ProcessorNumber> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Kernel::PROCESSOR_NUMBER;
+    use windows::Win32::System::Threading::{GetCurrentThread, GetThreadIdealProcessorEx};
+
+    let mut ideal = PROCESSOR_NUMBER::default();
+    unsafe { GetThreadIdealProcessorEx(GetCurrentThread(), &mut ideal) }
+      .map_err(|err| sys::priority_error(sys::win32_error(err), "get the ideal processor"))?;
+    Ok(ProcessorNumber {
+      group: ideal.Group as u32,
+      number: ideal.Number as u32,
+    })
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the ideal processor"))
+  }
+}
+
+#[napi]
+/// This function returns the CPUs the current process may actually run on, which differs from the CPU topology when running under `taskset`, cgroups or Kubernetes.
+///
+/// On Linux, it reads `Cpus_allowed_list` from `/proc/self/status`, which also reflects the cpuset cgroup.
+///
+/// On Windows and FreeBSD, it returns the affinity of the current process, see `getCpuAffinity`.
+///
+/// On macOS, the CPUs can't be restricted, so it returns every logical CPU from the `hw.logicalcpu` `sysctl` value.
+///
+/// On other platforms, it throws.
+pub fn get_allowed_cpus() -> Result<Vec<u32>> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let status = std::fs::read_to_string("/proc/self/status")
+      .map_err(|err| sys::priority_error(err, "read /proc/self/status"))?;
+    status
+      .lines()
+      .find_map(|line| line.strip_prefix("Cpus_allowed_list:"))
+      .and_then(try_parse_cpu_list)
+      .ok_or_else(|| {
+        Error::new(
+          Status::GenericFailure,
+          "Failed to read Cpus_allowed_list from /proc/self/status".to_owned(),
+        )
+      })
+  }
+  #[cfg(any(windows, target_os = "freebsd"))]
+  {
+    get_cpu_affinity(Some(AffinityOptions {
+      target: Some("process".to_owned()),
+      pid: None,
+      tid: None,
+    }))
+  }
+  #[cfg(target_vendor = "apple")]
+  {
+    let logical = sys::sysctl_numbers(c"hw.logicalcpu")
+      .map_err(|err| sys::priority_error(err, "get the number of CPUs"))?;
+    Ok((0..logical.first().copied().unwrap_or(1) as u32).collect())
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_vendor = "apple",
+    windows
+  )))]
+  {
+    Err(sys::unsupported("Getting the allowed CPUs"))
+  }
+}
+
+#[napi]
+/// This function returns the CPU the calling thread is running on, to verify that pinning took effect or to shard work per CPU.
+/// The thread may be moved to another CPU right after the call unless it is pinned.
+///
+/// On Linux and FreeBSD, it uses the [`sched_getcpu(3)`](https://man7.org/linux/man-pages/man3/sched_getcpu.3.html) function.
+///
+/// On Windows, it uses the [`GetCurrentProcessorNumberEx`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getcurrentprocessornumberex) function, and returns `group * bits + index within the group`, where `bits` is the number of bits of a processor group mask (64 on 64-bit Windows, 32 on 32-bit Windows), like `getCpuTopology`.
+///
+/// On other platforms, it throws.
+pub fn get_current_cpu() -> Result<u32> {
+  #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+  {
+    let cpu = unsafe { libc::sched_getcpu() };
+    if cpu == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get the current CPU",
+      ));
+    }
+    Ok(cpu as u32)
+  }
+  #[cfg(windows)]
+  {
+    let processor = unsafe { windows::Win32::System::Threading::GetCurrentProcessorNumberEx() };
+    Ok(processor.Group as u32 * usize::BITS + processor.Number as u32)
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    windows
+  )))]
+  {
+    Err(sys::unsupported("Getting the current CPU"))
+  }
+}
@@ -0,0 +1,212 @@
+use napi::Result;
+use napi_derive::napi;
+
+#[cfg(not(any(target_vendor = "apple", windows)))]
+use crate::sys;
+
+#[napi(object)]
+#[derive(Default)]
+pub struct AudioThreadOptions {
+  /// On macOS, the time between two audio callbacks in nanoseconds, it defaults to `10000000` (10ms).
+  pub period_ns: Option<u32>,
+  /// On macOS, the CPU time needed by each callback in nanoseconds, it defaults to half of `periodNs`.
+  pub computation_ns: Option<u32>,
+  /// On macOS, the time within which each callback must complete in nanoseconds, it defaults to `periodNs`.
+  pub constraint_ns: Option<u32>,
+  /// On Windows, the MMCSS task to register with, it defaults to `Pro Audio`.
+  pub task_name: Option<String>,
+  /// On Linux, the `SCHED_FIFO` priority between `1` and `99`, it defaults to `10`, which stays below the kernel threads.
+  /// Without root or CAP_SYS_NICE, it's lowered to `RLIMIT_RTPRIO`.
+  pub rt_priority: Option<u32>,
+}
+
+/// What `revert()` needs to restore the calling thread.
+struct SavedState {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  policy: libc::c_int,
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  param: libc::sched_param,
+  #[cfg(windows)]
+  task: crate::mmcss::MmcssTask,
+  #[cfg(windows)]
+  priority_boost: bool,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn enable(options: AudioThreadOptions) -> Result<SavedState> {
+  let rt_priority = options.rt_priority.unwrap_or(10);
+  if !(1..=99).contains(&rt_priority) {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!("{rt_priority} is not a valid real-time priority, expected a value between 1 and 99"),
+    ));
+  }
+  let rt_priority = if crate::privilege::can_raise_priority()? {
+    rt_priority
+  } else {
+    let mut limit: libc::rlimit = unsafe { std::mem::zeroed() };
+    if unsafe { libc::getrlimit(libc::RLIMIT_RTPRIO, &mut limit) } == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get RLIMIT_RTPRIO",
+      ));
+    }
+    if limit.rlim_cur == 0 {
+      return Err(napi::Error::new(
+        napi::Status::GenericFailure,
+        "Permission denied to enable the audio thread priority, SCHED_FIFO requires root or CAP_SYS_NICE, or a non-zero RLIMIT_RTPRIO",
+      ));
+    }
+    rt_priority.min(limit.rlim_cur.min(99) as u32)
+  };
+  let policy = unsafe { libc::sched_getscheduler(0) };
+  let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+  if policy == -1 || unsafe { libc::sched_getparam(0, &mut param) } == -1 {
+    return Err(sys::priority_error(
+      std::io::Error::last_os_error(),
+      "get the scheduling parameters",
+    ));
+  }
+  let realtime = libc::sched_param {
+    sched_priority: rt_priority as libc::c_int,
+  };
+  // child processes must not inherit the real-time policy
+  if unsafe { libc::sched_setscheduler(0, libc::SCHED_FIFO | libc::SCHED_RESET_ON_FORK, &realtime) }
+    == -1
+  {
+    return Err(sys::priority_error(
+      std::io::Error::last_os_error(),
+      "set the scheduling policy",
+    ));
+  }
+  Ok(SavedState { policy, param })
+}
+
+#[cfg(target_vendor = "apple")]
+fn enable(options: AudioThreadOptions) -> Result<SavedState> {
+  let period_ns = options.period_ns.unwrap_or(10_000_000);
+  crate::thread::apply_realtime_thread_policy(crate::thread::RealtimeThreadPolicy {
+    period_ns,
+    computation_ns: options.computation_ns.unwrap_or(period_ns / 2),
+    constraint_ns: options.constraint_ns.unwrap_or(period_ns),
+    preemptible: None,
+  })?;
+  Ok(SavedState {})
+}
+
+#[cfg(windows)]
+fn enable(options: AudioThreadOptions) -> Result<SavedState> {
+  use crate::thread::{apply_thread_priority_boost, get_thread_priority_boost};
+
+  // dynamic boosts would make the priority of the thread fluctuate
+  let priority_boost = get_thread_priority_boost(None)?;
+  apply_thread_priority_boost(false, None)?;
+  match crate::mmcss::MmcssTask::new(options.task_name) {
+    Ok(task) => Ok(SavedState {
+      task,
+      priority_boost,
+    }),
+    Err(err) => {
+      let _ = apply_thread_priority_boost(priority_boost, None);
+      Err(err)
+    }
+  }
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  target_vendor = "apple",
+  windows
+)))]
+fn enable(_options: AudioThreadOptions) -> Result<SavedState> {
+  Err(sys::unsupported("The audio thread priority"))
+}
+
+impl SavedState {
+  fn restore(self) -> Result<()> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if unsafe { libc::sched_setscheduler(0, self.policy, &self.param) } == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "restore the scheduling policy",
+      ));
+    }
+    #[cfg(target_vendor = "apple")]
+    {
+      let mut policy: libc::integer_t = 0;
+      let ret = unsafe {
+        libc::thread_policy_set(
+          libc::pthread_mach_thread_np(libc::pthread_self()),
+          libc::THREAD_STANDARD_POLICY as libc::thread_policy_flavor_t,
+          &mut policy as *mut _ as libc::thread_policy_t,
+          libc::THREAD_STANDARD_POLICY_COUNT as libc::mach_msg_type_number_t,
+        )
+      };
+      if ret != libc::KERN_SUCCESS {
+        return Err(crate::thread::kern_error(
+          ret,
+          "restore the standard thread policy",
+        ));
+      }
+    }
+    #[cfg(windows)]
+    {
+      let mut task = self.task;
+      task.revert()?;
+      crate::thread::apply_thread_priority_boost(self.priority_boost, None)?;
+    }
+    Ok(())
+  }
+}
+
+#[napi]
+/// The audio priority of the calling thread, which lasts until `revert()` is called or the object is garbage collected, so keep a reference to it.
+///
+/// It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+pub struct AudioThreadPriority {
+  saved: Option<SavedState>,
+}
+
+#[napi]
+impl AudioThreadPriority {
+  #[napi]
+  /// Restores the scheduling of the thread, calling it more than once has no effect.
+  pub fn revert(&mut self) -> Result<()> {
+    if let Some(saved) = self.saved.take() {
+      saved.restore()?;
+    }
+    Ok(())
+  }
+
+  #[napi(getter)]
+  /// Whether `revert()` has already been called.
+  pub fn reverted(&self) -> bool {
+    self.saved.is_none()
+  }
+}
+
+impl Drop for AudioThreadPriority {
+  fn drop(&mut self) {
+    let _ = self.revert();
+  }
+}
+
+#[napi]
+/// This function gives the calling thread the scheduling audio work needs, composing the right pieces for each platform, and must be called on the audio thread, such as a worker thread.
+///
+/// On Linux, it sets `SCHED_FIFO` with `SCHED_RESET_ON_FORK` and a conservative real-time priority, which is lowered to `RLIMIT_RTPRIO` without root or CAP_SYS_NICE, see `setScheduler`.
+///
+/// On macOS, it sets the time-constraint policy, see `setRealtimeThreadPolicy`.
+///
+/// On Windows, it registers the thread with MMCSS and disables the dynamic priority boost of the thread, see `MmcssTask` and `setThreadPriorityBoost`.
+///
+/// On other platforms, it throws.
+pub fn enable_audio_thread_priority(
+  options: Option<AudioThreadOptions>,
+) -> Result<AudioThreadPriority> {
+  crate::stats::record("enableAudioThreadPriority", || {
+    let saved = enable(options.unwrap_or_default())?;
+    Ok(AudioThreadPriority { saved: Some(saved) })
+  })
+}
@@ -0,0 +1,250 @@
+use std::cell::RefCell;
+
+use napi::Result;
+use napi_derive::napi;
+
+use crate::sys;
+
+/// What `exitBackgroundMode` needs to restore the calling thread.
+struct SavedState {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  policy: libc::c_int,
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  param: libc::sched_param,
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  io_priority: (crate::io_priority::IoPriorityClass, u32),
+}
+
+thread_local! {
+  /// `Some` while the calling thread is in background mode.
+  static SAVED_STATE: RefCell<Option<SavedState>> = const { RefCell::new(None) };
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl SavedState {
+  fn capture() -> std::io::Result<Self> {
+    use crate::io_priority::{ioprio_get, IOPRIO_WHO_PROCESS};
+
+    let policy = unsafe { libc::sched_getscheduler(0) };
+    if policy == -1 {
+      return Err(std::io::Error::last_os_error());
+    }
+    let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sched_getparam(0, &mut param) } == -1 {
+      return Err(std::io::Error::last_os_error());
+    }
+    Ok(Self {
+      policy,
+      param,
+      io_priority: ioprio_get(IOPRIO_WHO_PROCESS, 0)?,
+    })
+  }
+
+  fn restore(&self) -> std::io::Result<()> {
+    use crate::io_priority::{ioprio_set, IOPRIO_WHO_PROCESS};
+
+    if unsafe { libc::sched_setscheduler(0, self.policy, &self.param) } == -1 {
+      return Err(std::io::Error::last_os_error());
+    }
+    let (class, level) = self.io_priority;
+    ioprio_set(IOPRIO_WHO_PROCESS, 0, class, level)
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn enter() -> std::io::Result<SavedState> {
+  use crate::io_priority::{ioprio_set, IoPriorityClass, IOPRIO_WHO_PROCESS};
+
+  let saved = SavedState::capture()?;
+  ioprio_set(IOPRIO_WHO_PROCESS, 0, IoPriorityClass::Idle, 0)?;
+  let param: libc::sched_param = unsafe { std::mem::zeroed() };
+  if unsafe { libc::sched_setscheduler(0, libc::SCHED_IDLE, &param) } == -1 {
+    let err = std::io::Error::last_os_error();
+    let _ = saved.restore();
+    return Err(err);
+  }
+  Ok(saved)
+}
+
+#[cfg(target_vendor = "apple")]
+fn enter() -> std::io::Result<SavedState> {
+  sys::setpriority(libc::PRIO_DARWIN_THREAD, 0, libc::PRIO_DARWIN_BG)?;
+  Ok(SavedState {})
+}
+
+#[cfg(windows)]
+fn enter() -> std::io::Result<SavedState> {
+  use windows::Win32::System::Threading::{
+    GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_BEGIN,
+  };
+
+  unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_BEGIN) }
+    .map_err(sys::win32_error)?;
+  Ok(SavedState {})
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  target_vendor = "apple",
+  windows
+)))]
+fn enter() -> std::io::Result<SavedState> {
+  Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+}
+
+fn exit(saved: &SavedState) -> std::io::Result<()> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    saved.restore()
+  }
+  #[cfg(target_vendor = "apple")]
+  {
+    let _ = saved;
+    sys::setpriority(libc::PRIO_DARWIN_THREAD, 0, 0)
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{
+      GetCurrentThread, SetThreadPriority, THREAD_MODE_BACKGROUND_END,
+    };
+
+    let _ = saved;
+    unsafe { SetThreadPriority(GetCurrentThread(), THREAD_MODE_BACKGROUND_END) }
+      .map_err(sys::win32_error)
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    windows
+  )))]
+  {
+    let _ = saved;
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+  }
+}
+
+#[napi]
+/// This function lowers the CPU, I/O and memory priority of the calling thread at once, using the best mechanism of each platform.
+/// It returns `false` without changing anything when the thread is already in background mode.
+///
+/// On Linux, it uses the `SCHED_IDLE` policy with [`sched_setscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_setscheduler.2.html) and the idle I/O class with [`ioprio_set(2)`](https://man7.org/linux/man-pages/man2/ioprio_set.2.html). Linux has no memory priority for threads.
+///
+/// On macOS, it uses the [`setpriority(2)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/setpriority.2.html) function with `PRIO_DARWIN_THREAD` and `PRIO_DARWIN_BG`.
+///
+/// On Windows, it uses the [`SetThreadPriority`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) function with `THREAD_MODE_BACKGROUND_BEGIN`.
+///
+/// On other platforms, it throws.
+pub fn enter_background_mode() -> Result<bool> {
+  crate::stats::record("enterBackgroundMode", || {
+    SAVED_STATE.with(|state| {
+      let mut state = state.borrow_mut();
+      if state.is_some() {
+        return Ok(false);
+      }
+      let saved = enter().map_err(|err| {
+        if err.kind() == std::io::ErrorKind::Unsupported {
+          sys::unsupported("Background mode")
+        } else {
+          sys::priority_error(err, "enter background mode")
+        }
+      })?;
+      *state = Some(saved);
+      Ok(true)
+    })
+  })
+}
+
+#[napi]
+/// This function restores the priorities the calling thread had before `enterBackgroundMode`.
+/// It returns `false` without changing anything when the thread is not in background mode.
+///
+/// On Linux, leaving `SCHED_IDLE` requires `CAP_SYS_NICE` or a `RLIMIT_NICE` allowing the previous nice value, like raising the priority.
+///
+/// On other platforms, it throws.
+pub fn exit_background_mode() -> Result<bool> {
+  crate::stats::record("exitBackgroundMode", || {
+    SAVED_STATE.with(|state| {
+      let mut state = state.borrow_mut();
+      let Some(saved) = state.as_ref() else {
+        return Ok(false);
+      };
+      exit(saved).map_err(|err| sys::priority_error(err, "exit background mode"))?;
+      *state = None;
+      Ok(true)
+    })
+  })
+}
+
+#[cfg(windows)]
+/// Windows has no query for the background processing mode, it's inferred from both the I/O priority and the memory priority of the thread being very low, which is what `THREAD_MODE_BACKGROUND_BEGIN` sets.
+pub(crate) fn thread_background_mode(
+  thread: windows::Win32::Foundation::HANDLE,
+) -> std::io::Result<bool> {
+  use windows::Wdk::System::Threading::{
+    NtQueryInformationThread, ThreadIoPriority, ThreadPagePriority,
+  };
+
+  let query = |class| {
+    let mut value = 0u32;
+    let mut length = 0;
+    sys::nt_result(unsafe {
+      NtQueryInformationThread(
+        thread,
+        class,
+        &mut value as *mut u32 as *mut _,
+        std::mem::size_of::<u32>() as u32,
+        &mut length,
+      )
+    })
+    .map(|_| value)
+  };
+  // `IoPriorityVeryLow` and `MEMORY_PRIORITY_VERY_LOW`
+  Ok(query(ThreadIoPriority)? == 0 && query(ThreadPagePriority)? == 1)
+}
+
+#[napi]
+/// This function returns whether the calling thread is in background mode, whether it was entered with `enterBackgroundMode` or by other code, such as native addons, so callers can avoid entering it twice, which fails on Windows.
+///
+/// On Linux, it checks whether the policy is `SCHED_IDLE` with [`sched_getscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_getscheduler.2.html).
+///
+/// On macOS, it checks the [`getpriority(2)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/getpriority.2.html) function with `PRIO_DARWIN_THREAD`, and whether the QoS class is `Background` with the [`pthread_get_qos_class_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) function.
+///
+/// On Windows, it uses the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadIoPriority` and `ThreadPagePriority`, because the background processing mode can't be queried directly, it's reported when both are very low.
+///
+/// On other platforms, it throws.
+pub fn is_in_background_mode() -> Result<bool> {
+  let action = "get the background mode of the current thread";
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let policy = unsafe { libc::sched_getscheduler(0) };
+    if policy == -1 {
+      return Err(sys::priority_error(std::io::Error::last_os_error(), action));
+    }
+    Ok(policy == libc::SCHED_IDLE)
+  }
+  #[cfg(target_vendor = "apple")]
+  {
+    let darwin_background = sys::getpriority(libc::PRIO_DARWIN_THREAD, 0)
+      .map_err(|err| sys::priority_error(err, action))?;
+    Ok(darwin_background != 0 || crate::qos::get_qos_class()? == crate::qos::QosClass::Background)
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::GetCurrentThread;
+
+    thread_background_mode(unsafe { GetCurrentThread() })
+      .map_err(|err| sys::priority_error(err, action))
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    windows
+  )))]
+  {
+    let _ = action;
+    Err(sys::unsupported("Background mode"))
+  }
+}
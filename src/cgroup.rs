@@ -0,0 +1,545 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Finds the cgroup v2 mount, or the cgroup v1 mount of `controller`, in `/proc/self/mountinfo`, and returns its root within the hierarchy and its mount point.
+fn cgroup_mount(controller: Option<&str>) -> std::io::Result<Option<(String, std::path::PathBuf)>> {
+  let mountinfo = std::fs::read_to_string("/proc/self/mountinfo")?;
+  Ok(mountinfo.lines().find_map(|line| {
+    // `36 25 0:30 / /sys/fs/cgroup rw,nosuid - cgroup2 cgroup2 rw`
+    // `33 32 0:29 / /sys/fs/cgroup/cpu rw,relatime - cgroup cgroup rw,cpu,cpuacct`
+    let (mount, filesystem) = line.split_once(" - ")?;
+    let mut filesystem = filesystem.split(' ');
+    let matches = match (filesystem.next()?, controller) {
+      ("cgroup2", None) => true,
+      ("cgroup", Some(controller)) => filesystem
+        .nth(1)?
+        .split(',')
+        .any(|option| option == controller),
+      _ => false,
+    };
+    if !matches {
+      return None;
+    }
+    let mut fields = mount.split(' ').skip(3);
+    let root = fields.next()?;
+    let mount_point = fields.next()?;
+    Some((root.to_owned(), mount_point.into()))
+  }))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Resolves the mount point and the cgroup directory of the process identified by `pid` from `/proc/<pid>/cgroup`, it defaults to the current process.
+/// `controller` selects a cgroup v1 hierarchy, and `None` the cgroup v2 hierarchy.
+fn resolve_cgroup(
+  pid: Option<u32>,
+  controller: Option<&str>,
+) -> std::io::Result<(std::path::PathBuf, std::path::PathBuf)> {
+  let not_found = |message: &str| std::io::Error::new(std::io::ErrorKind::NotFound, message);
+  let hierarchy = controller.map_or_else(
+    || "cgroup v2".to_owned(),
+    |controller| format!("cgroup v1 {controller}"),
+  );
+  let path = crate::oom::proc_file(pid, "cgroup");
+  let cgroups = std::fs::read_to_string(&path)?;
+  // `hierarchy-ID:controller-list:cgroup-path`, the unified hierarchy is listed as `0::/path`
+  let cgroup = cgroups
+    .lines()
+    .find_map(|line| {
+      let mut fields = line.splitn(3, ':');
+      let id = fields.next()?;
+      let controllers = fields.next()?;
+      let cgroup = fields.next()?;
+      let matches = match controller {
+        None => id == "0" && controllers.is_empty(),
+        Some(controller) => controllers.split(',').any(|name| name == controller),
+      };
+      matches.then_some(cgroup)
+    })
+    .ok_or_else(|| not_found(&format!("{path} doesn't list a {hierarchy} hierarchy")))?;
+  let (root, mount_point) =
+    cgroup_mount(controller)?.ok_or_else(|| not_found(&format!("{hierarchy} is not mounted")))?;
+  // the cgroup is relative to the root of the mount, which isn't `/` inside some containers
+  let relative = cgroup
+    .strip_prefix(root.as_str())
+    .unwrap_or(cgroup)
+    .trim_start_matches('/');
+  let dir = mount_point.join(relative);
+  Ok((mount_point, dir))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Resolves the cgroup v2 directory of the process identified by `pid`, it defaults to the current process.
+pub(crate) fn cgroup2_dir(pid: Option<u32>) -> std::io::Result<std::path::PathBuf> {
+  resolve_cgroup(pid, None).map(|(_, dir)| dir)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Explains why a cgroup interface file can't be accessed.
+pub(crate) fn cgroup_error(err: std::io::Error, file: &std::path::Path, action: &str) -> Error {
+  let file = file.display();
+  match err.raw_os_error() {
+    Some(libc::ENOENT) => Error::new(
+      Status::GenericFailure,
+      format!("Failed to {action}, {file} doesn't exist, the controller is not enabled for the cgroup: {err}"),
+    ),
+    Some(libc::EACCES | libc::EPERM | libc::EROFS) => Error::new(
+      Status::GenericFailure,
+      format!("Permission denied to {action}, {file} is only writable when the cgroup is delegated to the current user: {err}"),
+    ),
+    _ => sys::priority_error(err, action),
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_interface_file(file: &std::path::Path) -> Result<String> {
+  let name = file.file_name().unwrap_or_default().to_string_lossy();
+  std::fs::read_to_string(file)
+    .map(|content| content.trim().to_owned())
+    .map_err(|err| cgroup_error(err, file, &format!("read {name}")))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn write_interface_file(file: &std::path::Path, content: &str) -> Result<()> {
+  let name = file.file_name().unwrap_or_default().to_string_lossy();
+  // interface files can't be created, opening without `create` reports missing files as such
+  std::fs::OpenOptions::new()
+    .write(true)
+    .open(file)
+    .and_then(|mut handle| std::io::Write::write_all(&mut handle, content.as_bytes()))
+    .map_err(|err| cgroup_error(err, file, &format!("write {name}")))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn read_cgroup_file(pid: Option<u32>, name: &str) -> Result<String> {
+  let dir = cgroup2_dir(pid).map_err(|err| sys::priority_error(err, "resolve the cgroup"))?;
+  read_interface_file(&dir.join(name))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn write_cgroup_file(pid: Option<u32>, name: &str, content: &str) -> Result<()> {
+  let dir = cgroup2_dir(pid).map_err(|err| sys::priority_error(err, "resolve the cgroup"))?;
+  write_interface_file(&dir.join(name), content)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Resolves the file holding the CPU weight of the cgroup of the process identified by `pid`, `cpu.weight` in the cgroup v2 hierarchy, or `cpu.shares` in the cgroup v1 `cpu` hierarchy when the cgroup v2 hierarchy doesn't have the `cpu` controller.
+/// The second value is `true` for `cpu.shares`.
+fn cpu_weight_file(pid: Option<u32>) -> Result<(std::path::PathBuf, bool)> {
+  let weight = cgroup2_dir(pid).map(|dir| dir.join("cpu.weight"));
+  if let Ok(file) = &weight {
+    if file.exists() {
+      return Ok((file.clone(), false));
+    }
+  }
+  if let Ok((_, dir)) = resolve_cgroup(pid, Some("cpu")) {
+    return Ok((dir.join("cpu.shares"), true));
+  }
+  weight
+    .map(|file| (file, false))
+    .map_err(|err| sys::priority_error(err, "resolve the cgroup"))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Converts a cgroup v2 weight to cgroup v1 shares, the same way as the kernel and systemd, the default weight `100` is `1024` shares.
+fn weight_to_shares(weight: u32) -> u64 {
+  ((u64::from(weight) * 1024 + 50) / 100).clamp(2, 262_144)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn shares_to_weight(shares: u64) -> u32 {
+  ((shares * 100 + 512) / 1024).clamp(1, 10_000) as u32
+}
+
+#[napi]
+/// This function set the CPU weight of the cgroup of the process identified by `pid`, it defaults to the current process.
+/// `weight` ranges from `1` to `10000`, and `100` is the default. CPU time is shared between sibling cgroups in proportion to their weights, while nice values mostly only matter within a cgroup.
+///
+/// On Linux, it writes the [`cpu.weight`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, which requires the cgroup to be delegated to the current user.
+/// On cgroup v1 hosts, it writes the weight converted to [`cpu.shares`](https://docs.kernel.org/scheduler/sched-design-CFS.html#group-scheduler-extensions-to-cfs) instead, `100` is `1024` shares.
+///
+/// On other platforms, it throws.
+pub fn set_cgroup_cpu_weight(weight: u32, pid: Option<u32>) -> Result<()> {
+  crate::stats::record("setCgroupCpuWeight", || {
+    if !(1..=10_000).contains(&weight) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("{weight} is not a valid CPU weight, expected a value between 1 and 10000"),
+      ));
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      let (file, shares) = cpu_weight_file(pid)?;
+      let content = if shares {
+        weight_to_shares(weight).to_string()
+      } else {
+        weight.to_string()
+      };
+      write_interface_file(&file, &content)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let _ = pid;
+      Err(sys::unsupported("cgroups"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the CPU weight of the cgroup of the process identified by `pid`, it defaults to the current process.
+///
+/// On Linux, it reads the [`cpu.weight`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, or converts [`cpu.shares`](https://docs.kernel.org/scheduler/sched-design-CFS.html#group-scheduler-extensions-to-cfs) on cgroup v1 hosts.
+///
+/// On other platforms, it throws.
+pub fn get_cgroup_cpu_weight(pid: Option<u32>) -> Result<u32> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let (file, shares) = cpu_weight_file(pid)?;
+    let content = read_interface_file(&file)?;
+    let invalid = || {
+      Error::new(
+        Status::GenericFailure,
+        format!(
+          "Failed to read {}: unexpected content {content:?}",
+          file.display()
+        ),
+      )
+    };
+    if shares {
+      content.parse().map(shares_to_weight).map_err(|_| invalid())
+    } else {
+      content.parse().map_err(|_| invalid())
+    }
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("cgroups"))
+  }
+}
+
+#[napi(object)]
+/// The CPU bandwidth limit of a cgroup, the cgroup may use `quota` microseconds of CPU time every `period` microseconds.
+pub struct CgroupCpuMax {
+  /// The CPU time in microseconds, `undefined` means unlimited.
+  pub quota: Option<u32>,
+  /// The length of a period in microseconds.
+  pub period: u32,
+}
+
+#[napi]
+/// This function limits the CPU bandwidth of the cgroup of the process identified by `pid`, it defaults to the current process.
+/// The cgroup may use `quota` microseconds of CPU time every `period` microseconds, so a quota of twice the period allows two CPUs worth of time. `null` removes the limit, and the period is kept when it's omitted.
+///
+/// On Linux, it writes the [`cpu.max`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, which requires the cgroup to be delegated to the current user.
+///
+/// On other platforms, it throws.
+pub fn set_cgroup_cpu_max(quota: Option<u32>, period: Option<u32>, pid: Option<u32>) -> Result<()> {
+  crate::stats::record("setCgroupCpuMax", || {
+    if let Some(quota) = quota {
+      if quota < 1000 {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("{quota} is not a valid CPU quota, expected at least 1000 microseconds"),
+        ));
+      }
+    }
+    if let Some(period) = period {
+      if !(1000..=1_000_000).contains(&period) {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!(
+            "{period} is not a valid CPU period, expected a value between 1000 and 1000000 microseconds"
+          ),
+        ));
+      }
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      let quota = quota.map_or_else(|| "max".to_owned(), |quota| quota.to_string());
+      let content = match period {
+        Some(period) => format!("{quota} {period}"),
+        None => quota,
+      };
+      write_cgroup_file(pid, "cpu.max", &content)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let _ = pid;
+      Err(sys::unsupported("cgroups"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the CPU bandwidth limit of the cgroup of the process identified by `pid`, it defaults to the current process.
+///
+/// On Linux, it reads the [`cpu.max`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy.
+///
+/// On other platforms, it throws.
+pub fn get_cgroup_cpu_max(pid: Option<u32>) -> Result<CgroupCpuMax> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let content = read_cgroup_file(pid, "cpu.max")?;
+    // `max 100000` or `50000 100000`
+    let parse = || {
+      let (quota, period) = content.split_once(' ')?;
+      let quota = match quota {
+        "max" => None,
+        quota => Some(quota.parse().ok()?),
+      };
+      Some(CgroupCpuMax {
+        quota,
+        period: period.parse().ok()?,
+      })
+    };
+    parse().ok_or_else(|| {
+      Error::new(
+        Status::GenericFailure,
+        format!("Failed to read cpu.max: unexpected content {content:?}"),
+      )
+    })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("cgroups"))
+  }
+}
+
+#[napi]
+/// This function marks the cgroup of the process identified by `pid` as idle, it defaults to the current process.
+/// The tasks of an idle cgroup only get the CPU time the sibling cgroups don't use, like `SCHED_IDLE` threads.
+///
+/// On Linux, it writes the [`cpu.idle`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy, which requires Linux 5.15 and the cgroup to be delegated to the current user.
+///
+/// On other platforms, it throws.
+pub fn set_cgroup_idle(enabled: bool, pid: Option<u32>) -> Result<()> {
+  crate::stats::record("setCgroupIdle", || apply_cgroup_idle(enabled, pid))
+}
+
+pub(crate) fn apply_cgroup_idle(enabled: bool, pid: Option<u32>) -> Result<()> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    write_cgroup_file(pid, "cpu.idle", if enabled { "1" } else { "0" })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = (enabled, pid);
+    Err(sys::unsupported("cgroups"))
+  }
+}
+
+#[napi]
+/// This function returns whether the cgroup of the process identified by `pid` is idle, it defaults to the current process.
+///
+/// On Linux, it reads the [`cpu.idle`](https://docs.kernel.org/admin-guide/cgroup-v2.html#cpu-interface-files) file of the cgroup v2 hierarchy.
+///
+/// On other platforms, it throws.
+pub fn get_cgroup_idle(pid: Option<u32>) -> Result<bool> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    Ok(read_cgroup_file(pid, "cpu.idle")? == "1")
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("cgroups"))
+  }
+}
+
+#[napi(ts_return_type = "'cgroup' | 'thread'")]
+/// This function makes the current process idle with the best mechanism available, and returns which one was used.
+///
+/// On Linux, it marks the cgroup as idle with `setCgroupIdle`, which affects every process in the cgroup, so the process should have a cgroup of its own.
+/// When cgroup control isn't available, it falls back to `SCHED_IDLE` for the calling thread, see `setScheduler`. Passing `false` undoes either of them, leaving `SCHED_IDLE` requires root, CAP_SYS_NICE or an `RLIMIT_NICE` which allows the current nice value.
+///
+/// On other platforms, it throws.
+pub fn set_idle_priority(enabled: bool) -> Result<String> {
+  crate::stats::record("setIdlePriority", || {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      use crate::sched::{apply_scheduler, SchedulerPolicy};
+
+      if apply_cgroup_idle(enabled, None).is_ok() {
+        return Ok("cgroup".to_owned());
+      }
+      let policy = if enabled {
+        SchedulerPolicy::Idle
+      } else {
+        SchedulerPolicy::Other
+      };
+      apply_scheduler(policy, None, None)?;
+      Ok("thread".to_owned())
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let _ = enabled;
+      Err(sys::unsupported("Setting the idle priority"))
+    }
+  })
+}
+
+#[napi(object)]
+/// How much CPU the current process can really use.
+pub struct EffectiveCpuLimit {
+  /// The number of CPUs worth of bandwidth, which is the lower of `quota` and `cpuset` and may be fractional.
+  pub cpus: f64,
+  /// The number of CPUs worth of bandwidth allowed by the CPU quota of the cgroup and its ancestors, `undefined` when unlimited.
+  pub quota: Option<f64>,
+  /// The number of CPUs the process may run on, which reflects the cpuset of the cgroup on Linux.
+  pub cpuset: u32,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Returns the lowest `quota / period` of the cgroup of the current process and its ancestors, reading both the cgroup v2 and the cgroup v1 hierarchies.
+fn cgroup_quota() -> Option<f64> {
+  let read = |path: std::path::PathBuf| std::fs::read_to_string(path).ok();
+  let mut quota: Option<f64> = None;
+  let mut limit = |cpus: f64| quota = Some(quota.map_or(cpus, |quota| quota.min(cpus)));
+  if let Ok((mount_point, dir)) = resolve_cgroup(None, None) {
+    // a parent limit applies even when the cpu controller isn't enabled for its children
+    for dir in dir
+      .ancestors()
+      .take_while(|dir| dir.starts_with(&mount_point))
+    {
+      let Some(content) = read(dir.join("cpu.max")) else {
+        continue;
+      };
+      let mut fields = content.split_whitespace();
+      if let (Some(Ok(max)), Some(Ok(period))) = (
+        fields.next().map(str::parse::<f64>),
+        fields.next().map(str::parse::<f64>),
+      ) {
+        limit(max / period);
+      }
+    }
+  }
+  if let Ok((mount_point, dir)) = resolve_cgroup(None, Some("cpu")) {
+    for dir in dir
+      .ancestors()
+      .take_while(|dir| dir.starts_with(&mount_point))
+    {
+      let parse = |name: &str| read(dir.join(name))?.trim().parse::<f64>().ok();
+      // `cpu.cfs_quota_us` is `-1` when unlimited
+      if let (Some(max), Some(period)) = (parse("cpu.cfs_quota_us"), parse("cpu.cfs_period_us")) {
+        if max > 0.0 && period > 0.0 {
+          limit(max / period);
+        }
+      }
+    }
+  }
+  quota
+}
+
+#[napi]
+/// This function returns how many CPUs worth of bandwidth the current process really has, which is what thread pools should be sized by inside containers.
+///
+/// On Linux, it combines the CPU quota of the cgroup and its ancestors, from `cpu.max` on cgroup v2 and `cpu.cfs_quota_us` / `cpu.cfs_period_us` on cgroup v1, with the CPUs the process may run on, which reflects the cpuset of the cgroup.
+///
+/// On other platforms, there is no quota, and `cpuset` is the number of CPUs available to the process.
+pub fn get_effective_cpu_limit() -> Result<EffectiveCpuLimit> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let cpuset = crate::affinity::sched_getaffinity(0)
+      .map_err(|err| sys::priority_error(err, "get the CPU affinity"))?
+      .len() as u32;
+    let quota = cgroup_quota();
+    Ok(EffectiveCpuLimit {
+      cpus: quota.map_or(f64::from(cpuset), |quota| quota.min(f64::from(cpuset))),
+      quota,
+      cpuset,
+    })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let cpuset = std::thread::available_parallelism()
+      .map_err(|err| sys::priority_error(err, "get the available parallelism"))?
+      .get() as u32;
+    Ok(EffectiveCpuLimit {
+      cpus: f64::from(cpuset),
+      quota: None,
+      cpuset,
+    })
+  }
+}
+
+#[napi]
+/// This function moves the process identified by `pid` into the cgroup at `path`, it defaults to the current process, so a supervisor can group its workers under different weights.
+/// `path` is either an absolute path in the cgroup v2 mount, such as `/sys/fs/cgroup/app/workers`, or a path relative to the cgroup of the current process, such as `workers`. The cgroup must already exist, it can be created with `fs.mkdirSync`.
+///
+/// On Linux, it writes the pid to the [`cgroup.procs`](https://docs.kernel.org/admin-guide/cgroup-v2.html#core-interface-files) file of the cgroup v2 hierarchy, which requires write access to the `cgroup.procs` of the common ancestor of both cgroups, which is the case when it's delegated to the current user.
+///
+/// On other platforms, it throws.
+pub fn move_to_cgroup(path: String, pid: Option<u32>) -> Result<()> {
+  crate::stats::record("moveToCgroup", || {
+    if path.is_empty() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "path must not be empty".to_owned(),
+      ));
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      let (mount_point, current) =
+        resolve_cgroup(None, None).map_err(|err| sys::priority_error(err, "resolve the cgroup"))?;
+      // resolve `..` lexically, so the path can't escape the mount through it
+      let mut dir = std::path::PathBuf::new();
+      for component in current.join(&path).components() {
+        match component {
+          std::path::Component::ParentDir => {
+            dir.pop();
+          }
+          std::path::Component::CurDir => {}
+          component => dir.push(component),
+        }
+      }
+      if !dir.starts_with(&mount_point) {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!(
+            "{path} is not a cgroup, expected a path in {}",
+            mount_point.display()
+          ),
+        ));
+      }
+      let procs = dir.join("cgroup.procs");
+      if !procs.is_file() {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("{} is not an existing cgroup", dir.display()),
+        ));
+      }
+      let pid = pid.unwrap_or_else(std::process::id);
+      std::fs::OpenOptions::new()
+        .write(true)
+        .open(&procs)
+        .and_then(|mut handle| std::io::Write::write_all(&mut handle, pid.to_string().as_bytes()))
+        .map_err(|err| {
+          let action = format!("move process {pid} to {}", dir.display());
+          match err.raw_os_error() {
+            Some(libc::EBUSY) => Error::new(
+              Status::GenericFailure,
+              format!("Failed to {action}, processes can only live in cgroups without enabled controllers in cgroup.subtree_control, move them to a leaf cgroup instead: {err}"),
+            ),
+            Some(libc::EACCES | libc::EPERM | libc::EROFS) => Error::new(
+              Status::GenericFailure,
+              format!("Permission denied to {action}, the cgroup.procs file of the common ancestor of both cgroups must be writable, which is the case when it's delegated to the current user: {err}"),
+            ),
+            Some(libc::ESRCH) => Error::new(
+              Status::InvalidArg,
+              format!("Failed to {action}, process {pid} doesn't exist: {err}"),
+            ),
+            _ => sys::priority_error(err, &action),
+          }
+        })
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let _ = pid;
+      Err(sys::unsupported("cgroups"))
+    }
+  })
+}
@@ -0,0 +1,205 @@
+use napi::Result;
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi(object)]
+/// A Windows CPU Set, which identifies a logical CPU independently of processor groups.
+pub struct CpuSet {
+  /// The id passed to `setThreadSelectedCpuSets` and `setProcessDefaultCpuSets`.
+  pub id: u32,
+  pub group: u32,
+  /// The index of the CPU within `group`.
+  pub logical_processor_index: u32,
+  pub core_index: u32,
+  pub last_level_cache_index: u32,
+  pub numa_node_index: u32,
+  /// Higher values mean more performant cores on hybrid CPUs.
+  pub efficiency_class: u32,
+  /// Whether the CPU is parked by the power manager.
+  pub parked: bool,
+  /// Whether the CPU is allocated to a specific process.
+  pub allocated: bool,
+  /// Whether the CPU is reserved for real-time work.
+  pub realtime: bool,
+}
+
+#[cfg(windows)]
+/// Reads a list of CPU Set ids with the usual two calls, the first one only reports the required count.
+fn read_cpu_set_ids(
+  read: impl Fn(Option<&mut [u32]>, &mut u32) -> windows::Win32::Foundation::BOOL,
+) -> std::io::Result<Vec<u32>> {
+  let mut count = 0;
+  if read(None, &mut count).as_bool() {
+    return Ok(Vec::new());
+  }
+  let mut ids = vec![0; count as usize];
+  if !read(Some(&mut ids), &mut count).as_bool() {
+    return Err(std::io::Error::last_os_error());
+  }
+  ids.truncate(count as usize);
+  Ok(ids)
+}
+
+#[napi]
+/// This function returns the CPU Sets of the machine.
+///
+/// On Windows, it uses the [`GetSystemCpuSetInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getsystemcpusetinformation) function.
+///
+/// On other platforms, it throws.
+pub fn get_system_cpu_sets() -> Result<Vec<CpuSet>> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::SystemInformation::{
+      GetSystemCpuSetInformation, SYSTEM_CPU_SET_INFORMATION, SYSTEM_CPU_SET_INFORMATION_ALLOCATED,
+      SYSTEM_CPU_SET_INFORMATION_PARKED, SYSTEM_CPU_SET_INFORMATION_REALTIME,
+    };
+
+    let mut length = 0;
+    // the first call only reports the required length
+    let _ = unsafe { GetSystemCpuSetInformation(None, 0, &mut length, HANDLE::default(), 0) };
+    // `u64`s keep the buffer aligned for `SYSTEM_CPU_SET_INFORMATION`
+    let mut buffer = vec![0u64; (length as usize).div_ceil(8)];
+    let information = buffer.as_mut_ptr() as *mut SYSTEM_CPU_SET_INFORMATION;
+    if !unsafe {
+      GetSystemCpuSetInformation(Some(information), length, &mut length, HANDLE::default(), 0)
+    }
+    .as_bool()
+    {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get the CPU Sets",
+      ));
+    }
+    let mut cpu_sets = Vec::new();
+    let mut offset = 0;
+    while offset < length as usize {
+      let entry = unsafe {
+        &*((buffer.as_ptr() as *const u8).add(offset) as *const SYSTEM_CPU_SET_INFORMATION)
+      };
+      let cpu_set = unsafe { &entry.Anonymous.CpuSet };
+      let flags = unsafe { cpu_set.Anonymous1.AllFlags } as u32;
+      cpu_sets.push(CpuSet {
+        id: cpu_set.Id,
+        group: cpu_set.Group as u32,
+        logical_processor_index: cpu_set.LogicalProcessorIndex as u32,
+        core_index: cpu_set.CoreIndex as u32,
+        last_level_cache_index: cpu_set.LastLevelCacheIndex as u32,
+        numa_node_index: cpu_set.NumaNodeIndex as u32,
+        efficiency_class: cpu_set.EfficiencyClass as u32,
+        parked: flags & SYSTEM_CPU_SET_INFORMATION_PARKED != 0,
+        allocated: flags & SYSTEM_CPU_SET_INFORMATION_ALLOCATED != 0,
+        realtime: flags & SYSTEM_CPU_SET_INFORMATION_REALTIME != 0,
+      });
+      offset += entry.Size as usize;
+    }
+    Ok(cpu_sets)
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the CPU Sets"))
+  }
+}
+
+#[napi]
+/// This function selects the CPU Sets the calling thread should run on, an empty list clears the selection.
+/// Unlike hard affinity, the scheduler may still use other CPUs when the selected ones are unavailable.
+///
+/// On Windows, it uses the [`SetThreadSelectedCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadselectedcpusets) function.
+///
+/// On other platforms, it throws.
+pub fn set_thread_selected_cpu_sets(ids: Vec<u32>) -> Result<()> {
+  crate::stats::record("setThreadSelectedCpuSets", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{GetCurrentThread, SetThreadSelectedCpuSets};
+
+      if !unsafe { SetThreadSelectedCpuSets(GetCurrentThread(), &ids) }.as_bool() {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "set the selected CPU Sets",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = ids;
+      Err(sys::unsupported("Setting the selected CPU Sets"))
+    }
+  })
+}
+
+#[napi]
+/// This function returns the CPU Sets selected for the calling thread, an empty list means there is no selection.
+///
+/// On Windows, it uses the [`GetThreadSelectedCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadselectedcpusets) function.
+///
+/// On other platforms, it throws.
+pub fn get_thread_selected_cpu_sets() -> Result<Vec<u32>> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{GetCurrentThread, GetThreadSelectedCpuSets};
+
+    read_cpu_set_ids(|ids, count| unsafe {
+      GetThreadSelectedCpuSets(GetCurrentThread(), ids, count)
+    })
+    .map_err(|err| sys::priority_error(err, "get the selected CPU Sets"))
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the selected CPU Sets"))
+  }
+}
+
+#[napi]
+/// This function sets the default CPU Sets of the threads of the current process which have no selection, an empty list clears them.
+///
+/// On Windows, it uses the [`SetProcessDefaultCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setprocessdefaultcpusets) function.
+///
+/// On other platforms, it throws.
+pub fn set_process_default_cpu_sets(ids: Vec<u32>) -> Result<()> {
+  crate::stats::record("setProcessDefaultCpuSets", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{GetCurrentProcess, SetProcessDefaultCpuSets};
+
+      let ids = (!ids.is_empty()).then_some(ids.as_slice());
+      if !unsafe { SetProcessDefaultCpuSets(GetCurrentProcess(), ids) }.as_bool() {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "set the default CPU Sets",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = ids;
+      Err(sys::unsupported("Setting the default CPU Sets"))
+    }
+  })
+}
+
+#[napi]
+/// This function returns the default CPU Sets of the current process, an empty list means there are none.
+///
+/// On Windows, it uses the [`GetProcessDefaultCpuSets`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocessdefaultcpusets) function.
+///
+/// On other platforms, it throws.
+pub fn get_process_default_cpu_sets() -> Result<Vec<u32>> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{GetCurrentProcess, GetProcessDefaultCpuSets};
+
+    read_cpu_set_ids(|ids, count| unsafe {
+      GetProcessDefaultCpuSets(GetCurrentProcess(), ids, count)
+    })
+    .map_err(|err| sys::priority_error(err, "get the default CPU Sets"))
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the default CPU Sets"))
+  }
+}
@@ -0,0 +1,95 @@
+use napi::bindgen_prelude::Object;
+use napi::{Either, Env, JsDeferred, Result};
+use napi_derive::napi;
+
+use crate::level::{apply_priority_level, PriorityLevel, PrioritySnapshot};
+
+#[napi]
+/// Changes the priority of the current process when constructed, and restores the previous priority with `restore()`.
+///
+/// It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations:
+///
+/// ```ts
+/// {
+///   using _ = new PriorityGuard('low')
+///   // runs with low priority
+/// }
+/// // the previous priority is restored here
+/// ```
+pub struct PriorityGuard {
+  snapshot: Option<PrioritySnapshot>,
+}
+
+#[napi]
+impl PriorityGuard {
+  #[napi(constructor)]
+  pub fn new(
+    #[napi(
+      ts_arg_type = "PriorityLevel | 'idle' | 'low' | 'belowNormal' | 'normal' | 'aboveNormal' | 'high' | 'realtime'"
+    )]
+    level: Either<PriorityLevel, String>,
+  ) -> Result<Self> {
+    crate::stats::record("PriorityGuard", || {
+      let level = match level {
+        Either::A(level) => level,
+        Either::B(name) => name.parse()?,
+      };
+      let snapshot = PrioritySnapshot::capture()?;
+      apply_priority_level(level)?;
+      Ok(Self {
+        snapshot: Some(snapshot),
+      })
+    })
+  }
+
+  #[napi]
+  /// Restores the priority captured by the constructor, calling it more than once has no effect.
+  pub fn restore(&mut self) -> Result<()> {
+    if let Some(snapshot) = self.snapshot.take() {
+      snapshot.restore()?;
+    }
+    Ok(())
+  }
+
+  #[napi(getter)]
+  /// Whether `restore()` has already been called.
+  pub fn restored(&self) -> bool {
+    self.snapshot.is_none()
+  }
+}
+
+/// Restores the priority captured by `boostFor`, on the JavaScript thread.
+type Restore = Box<dyn FnOnce(Env) -> Result<()>>;
+
+#[napi(ts_return_type = "Promise<void>")]
+/// This function set the priority of the current process to `level`, and restores the previous priority automatically after `milliseconds`.
+/// The returned `Promise` resolves once the priority is restored, and rejects if restoring fails.
+///
+/// The timer runs on a dedicated thread, so it doesn't occupy the libuv thread pool, and the priority is restored on the JavaScript thread, which is the thread that was boosted.
+pub fn boost_for<'env>(
+  env: &'env Env,
+  level: PriorityLevel,
+  milliseconds: u32,
+) -> Result<Object<'env>> {
+  crate::stats::record("boostFor", || {
+    let snapshot = PrioritySnapshot::capture()?;
+    let duration = std::time::Duration::from_millis(milliseconds as u64);
+    let restored = snapshot.clone();
+    let (start, started) = std::sync::mpsc::channel::<JsDeferred<(), Restore>>();
+    // the thread is started first, a deferred which is never settled would keep the event loop alive
+    std::thread::Builder::new()
+      .name("nice-boost".to_owned())
+      .spawn(move || {
+        if let Ok(deferred) = started.recv() {
+          std::thread::sleep(duration);
+          deferred.resolve(Box::new(move |_| restored.restore()));
+        }
+      })?;
+    apply_priority_level(level)?;
+    let (deferred, promise) = env.create_deferred().inspect_err(|_| {
+      let _ = snapshot.restore();
+    })?;
+    let _ = start.send(deferred);
+    Ok(promise)
+  })
+}
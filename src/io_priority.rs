@@ -0,0 +1,444 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Linux I/O scheduling classes, see [`ioprio_set(2)`](https://man7.org/linux/man-pages/man2/ioprio_set.2.html).
+pub enum IoPriorityClass {
+  /// No class was set, the I/O priority is derived from the nice value.
+  None = 0,
+  /// Real-time I/O, served first regardless of other processes.
+  Realtime = 1,
+  /// The default class, with levels from `0` (the highest priority) to `7`.
+  BestEffort = 2,
+  /// Only served when no other process needs the disk.
+  Idle = 3,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const IOPRIO_WHO_PGRP: libc::c_int = 2;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Reads the I/O class and level of `who`, a thread for `IOPRIO_WHO_PROCESS` or a process group for `IOPRIO_WHO_PGRP`, `0` means the caller.
+pub(crate) fn ioprio_get(
+  which: libc::c_int,
+  who: libc::c_int,
+) -> std::io::Result<(IoPriorityClass, u32)> {
+  let ret = unsafe { libc::syscall(libc::SYS_ioprio_get, which, who) };
+  if ret == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  let ret = ret as libc::c_int;
+  let class = match ret >> IOPRIO_CLASS_SHIFT {
+    1 => IoPriorityClass::Realtime,
+    2 => IoPriorityClass::BestEffort,
+    3 => IoPriorityClass::Idle,
+    _ => IoPriorityClass::None,
+  };
+  Ok((class, (ret & ((1 << IOPRIO_CLASS_SHIFT) - 1)) as u32))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Sets the I/O class and level of `who`, a thread for `IOPRIO_WHO_PROCESS` or a process group for `IOPRIO_WHO_PGRP`, `0` means the caller.
+pub(crate) fn ioprio_set(
+  which: libc::c_int,
+  who: libc::c_int,
+  class: IoPriorityClass,
+  level: u32,
+) -> std::io::Result<()> {
+  let ioprio = ((class as libc::c_int) << IOPRIO_CLASS_SHIFT) | level as libc::c_int;
+  if unsafe { libc::syscall(libc::SYS_ioprio_set, which, who, ioprio) } == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+#[napi(object)]
+pub struct IoPriority {
+  pub class: IoPriorityClass,
+  /// From `0` (the highest priority) to `7`, only meaningful for the `Realtime` and `BestEffort` classes.
+  pub level: u32,
+}
+
+#[napi(object)]
+pub struct IoPriorityOptions {
+  /// The process to use instead of the calling thread, every thread of the process is updated.
+  pub pid: Option<u32>,
+  /// The process group to use instead of the calling thread, `0` means the process group of the current process.
+  pub pgid: Option<u32>,
+}
+
+enum IoPriorityTarget {
+  CurrentThread,
+  Process(u32),
+  ProcessGroup(u32),
+}
+
+impl IoPriorityTarget {
+  fn resolve(options: Option<&IoPriorityOptions>) -> Result<Self> {
+    match options.map(|options| (options.pid, options.pgid)) {
+      Some((Some(_), Some(_))) => Err(Error::new(
+        Status::InvalidArg,
+        "pid and pgid can't be used together".to_owned(),
+      )),
+      Some((Some(pid), None)) => Ok(Self::Process(pid)),
+      Some((None, Some(pgid))) => Ok(Self::ProcessGroup(pgid)),
+      _ => Ok(Self::CurrentThread),
+    }
+  }
+
+  fn describe(&self) -> String {
+    match self {
+      Self::CurrentThread => String::new(),
+      Self::Process(pid) => format!(" of process {pid}"),
+      Self::ProcessGroup(pgid) => format!(" of process group {pgid}"),
+    }
+  }
+}
+
+#[napi]
+/// This function set the I/O scheduling class and level of the calling thread, like `ionice`.
+/// `level` goes from `0` (the highest priority) to `7` and defaults to `4`, it is ignored for the `Idle` and `None` classes.
+/// The `Realtime` class requires `CAP_SYS_ADMIN`.
+/// Pass `{ pid }` to update every thread of another process, or `{ pgid }` to update every process of a process group.
+///
+/// On Linux, it uses the [`ioprio_set(2)`](https://man7.org/linux/man-pages/man2/ioprio_set.2.html) syscall, with `IOPRIO_WHO_PGRP` for a process group.
+///
+/// On other platforms, it throws.
+pub fn set_io_priority(
+  class: IoPriorityClass,
+  level: Option<u32>,
+  options: Option<IoPriorityOptions>,
+) -> Result<()> {
+  crate::stats::record("setIoPriority", || {
+    let target = IoPriorityTarget::resolve(options.as_ref())?;
+    let level = match class {
+      IoPriorityClass::Idle | IoPriorityClass::None => 0,
+      IoPriorityClass::Realtime | IoPriorityClass::BestEffort => level.unwrap_or(4),
+    };
+    if level > 7 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("{level} is not a valid I/O priority level, expected a value from 0 to 7"),
+      ));
+    }
+    let action = || format!("set the I/O priority{}", target.describe());
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      match target {
+        IoPriorityTarget::CurrentThread => ioprio_set(IOPRIO_WHO_PROCESS, 0, class, level),
+        IoPriorityTarget::ProcessGroup(pgid) => {
+          ioprio_set(IOPRIO_WHO_PGRP, pgid as libc::c_int, class, level)
+        }
+        // the I/O priority belongs to each thread
+        IoPriorityTarget::Process(pid) => crate::thread::list_thread_ids(pid).and_then(|tids| {
+          for tid in tids {
+            match ioprio_set(IOPRIO_WHO_PROCESS, tid as libc::c_int, class, level) {
+              // the thread exited after it was listed
+              Err(err) if err.raw_os_error() == Some(libc::ESRCH) => {}
+              ret => ret?,
+            }
+          }
+          Ok(())
+        }),
+      }
+      .map_err(|err| sys::priority_error(err, &action()))
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let _ = action;
+      Err(sys::unsupported("Setting the I/O priority"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the I/O scheduling class and level of the calling thread.
+/// Pass `{ pid }` to use the main thread of another process, or `{ pgid }` to use the highest priority of a process group.
+///
+/// On Linux, it uses the [`ioprio_get(2)`](https://man7.org/linux/man-pages/man2/ioprio_get.2.html) syscall.
+///
+/// On other platforms, it throws.
+pub fn get_io_priority(options: Option<IoPriorityOptions>) -> Result<IoPriority> {
+  let target = IoPriorityTarget::resolve(options.as_ref())?;
+  let action = || format!("get the I/O priority{}", target.describe());
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let (which, who) = match target {
+      IoPriorityTarget::CurrentThread => (IOPRIO_WHO_PROCESS, 0),
+      IoPriorityTarget::Process(pid) => (IOPRIO_WHO_PROCESS, pid as libc::c_int),
+      IoPriorityTarget::ProcessGroup(pgid) => (IOPRIO_WHO_PGRP, pgid as libc::c_int),
+    };
+    let (class, level) =
+      ioprio_get(which, who).map_err(|err| sys::priority_error(err, &action()))?;
+    Ok(IoPriority { class, level })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = action;
+    Err(sys::unsupported("Getting the I/O priority"))
+  }
+}
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Windows I/O priority hints, see [I/O prioritization](https://learn.microsoft.com/en-us/windows-hardware/drivers/kernel/i-o-prioritization-in-windows-vista).
+pub enum WindowsIoPriority {
+  /// Background I/O, such as indexing or defragmentation.
+  VeryLow = 0,
+  Low = 1,
+  /// The default priority.
+  Normal = 2,
+  /// Reserved for the system, it requires administrator privileges.
+  High = 3,
+}
+
+#[cfg(windows)]
+impl WindowsIoPriority {
+  fn from_raw(value: u32) -> Self {
+    match value {
+      0 => Self::VeryLow,
+      1 => Self::Low,
+      2 => Self::Normal,
+      _ => Self::High,
+    }
+  }
+}
+
+#[napi]
+/// This function set the I/O priority hint of the current process, so background services stop starving the disk access of foreground apps.
+///
+/// On Windows, it uses the [`NtSetInformationProcess`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntsetinformationprocess) function with `ProcessIoPriority`.
+///
+/// On other platforms, it throws.
+pub fn set_process_io_priority(priority: WindowsIoPriority) -> Result<()> {
+  crate::stats::record("setProcessIoPriority", || {
+    #[cfg(windows)]
+    {
+      use windows::Wdk::System::Threading::ProcessIoPriority;
+      use windows::Win32::System::Threading::GetCurrentProcess;
+
+      let value = priority as u32;
+      sys::nt_result(unsafe {
+        sys::NtSetInformationProcess(
+          GetCurrentProcess(),
+          ProcessIoPriority,
+          &value as *const u32 as *const _,
+          std::mem::size_of::<u32>() as u32,
+        )
+      })
+      .map_err(|err| sys::priority_error(err, "set the I/O priority of the current process"))
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = priority;
+      Err(sys::unsupported("Setting the process I/O priority"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the I/O priority hint of the current process.
+///
+/// On Windows, it uses the [`NtQueryInformationProcess`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationprocess) function with `ProcessIoPriority`.
+///
+/// On other platforms, it throws.
+pub fn get_process_io_priority() -> Result<WindowsIoPriority> {
+  #[cfg(windows)]
+  {
+    use windows::Wdk::System::Threading::{NtQueryInformationProcess, ProcessIoPriority};
+    use windows::Win32::System::Threading::GetCurrentProcess;
+
+    let mut value = 0u32;
+    let mut length = 0;
+    sys::nt_result(unsafe {
+      NtQueryInformationProcess(
+        GetCurrentProcess(),
+        ProcessIoPriority,
+        &mut value as *mut u32 as *mut _,
+        std::mem::size_of::<u32>() as u32,
+        &mut length,
+      )
+    })
+    .map_err(|err| sys::priority_error(err, "get the I/O priority of the current process"))?;
+    Ok(WindowsIoPriority::from_raw(value))
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the process I/O priority"))
+  }
+}
+
+#[cfg(target_vendor = "apple")]
+extern "C" {
+  fn setiopolicy_np(iotype: libc::c_int, scope: libc::c_int, policy: libc::c_int) -> libc::c_int;
+  fn getiopolicy_np(iotype: libc::c_int, scope: libc::c_int) -> libc::c_int;
+}
+
+#[cfg(target_vendor = "apple")]
+const IOPOL_TYPE_DISK: libc::c_int = 0;
+#[cfg(target_vendor = "apple")]
+const IOPOL_SCOPE_PROCESS: libc::c_int = 0;
+#[cfg(target_vendor = "apple")]
+const IOPOL_SCOPE_THREAD: libc::c_int = 1;
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// macOS disk I/O policies, see [`setiopolicy_np(3)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/setiopolicy_np.3.html).
+pub enum DarwinIoPolicy {
+  /// Inherit the policy of the process, or of the system for a process.
+  Default = 0,
+  /// The normal policy, I/O is not throttled.
+  Important = 1,
+  /// Passive I/O doesn't throttle other I/O, for example for backups reading files.
+  Passive = 2,
+  /// Throttled I/O, the macOS equivalent of the idle `ionice` class.
+  Throttle = 3,
+  /// Throttled less aggressively than `Throttle`.
+  Utility = 4,
+  Standard = 5,
+}
+
+#[cfg(target_vendor = "apple")]
+fn darwin_io_scope(scope: &str) -> Result<libc::c_int> {
+  match scope {
+    "process" => Ok(IOPOL_SCOPE_PROCESS),
+    "thread" => Ok(IOPOL_SCOPE_THREAD),
+    _ => Err(Error::new(
+      Status::InvalidArg,
+      format!("{scope} is not a valid scope, expected thread or process"),
+    )),
+  }
+}
+
+#[napi]
+/// This function set the disk I/O policy of the current process or of the calling thread, the macOS equivalent of `ionice`.
+///
+/// On macOS, it uses the [`setiopolicy_np(3)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/setiopolicy_np.3.html) function with `IOPOL_TYPE_DISK`.
+///
+/// On other platforms, it throws.
+pub fn set_darwin_io_policy(
+  #[napi(ts_arg_type = "'thread' | 'process'")] scope: String,
+  policy: DarwinIoPolicy,
+) -> Result<()> {
+  crate::stats::record("setDarwinIoPolicy", || {
+    #[cfg(target_vendor = "apple")]
+    {
+      let scope = darwin_io_scope(&scope)?;
+      if unsafe { setiopolicy_np(IOPOL_TYPE_DISK, scope, policy as libc::c_int) } == -1 {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "set the I/O policy",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    {
+      let _ = (scope, policy);
+      Err(sys::unsupported("Setting the Darwin I/O policy"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the disk I/O policy of the current process or of the calling thread.
+///
+/// On macOS, it uses the [`getiopolicy_np(3)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man3/getiopolicy_np.3.html) function with `IOPOL_TYPE_DISK`.
+///
+/// On other platforms, it throws.
+pub fn get_darwin_io_policy(
+  #[napi(ts_arg_type = "'thread' | 'process'")] scope: String,
+) -> Result<DarwinIoPolicy> {
+  #[cfg(target_vendor = "apple")]
+  {
+    let scope = darwin_io_scope(&scope)?;
+    match unsafe { getiopolicy_np(IOPOL_TYPE_DISK, scope) } {
+      -1 => Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get the I/O policy",
+      )),
+      1 => Ok(DarwinIoPolicy::Important),
+      2 => Ok(DarwinIoPolicy::Passive),
+      3 => Ok(DarwinIoPolicy::Throttle),
+      4 => Ok(DarwinIoPolicy::Utility),
+      5 => Ok(DarwinIoPolicy::Standard),
+      _ => Ok(DarwinIoPolicy::Default),
+    }
+  }
+  #[cfg(not(target_vendor = "apple"))]
+  {
+    let _ = scope;
+    Err(sys::unsupported("Getting the Darwin I/O policy"))
+  }
+}
+
+#[napi]
+/// This function set the I/O priority hint of the calling thread only, so a background worker thread can use `VeryLow` while the rest of the process stays normal.
+///
+/// On Windows, it uses the [`NtSetInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/ntifs/nf-ntifs-ntsetinformationthread) function with `ThreadIoPriority`.
+///
+/// On other platforms, it throws.
+pub fn set_thread_io_priority(priority: WindowsIoPriority) -> Result<()> {
+  crate::stats::record("setThreadIoPriority", || {
+    #[cfg(windows)]
+    {
+      use windows::Wdk::System::Threading::{NtSetInformationThread, ThreadIoPriority};
+      use windows::Win32::System::Threading::GetCurrentThread;
+
+      let value = priority as u32;
+      sys::nt_result(unsafe {
+        NtSetInformationThread(
+          GetCurrentThread(),
+          ThreadIoPriority,
+          &value as *const u32 as *const _,
+          std::mem::size_of::<u32>() as u32,
+        )
+      })
+      .map_err(|err| sys::priority_error(err, "set the I/O priority of the current thread"))
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = priority;
+      Err(sys::unsupported("Setting the thread I/O priority"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the I/O priority hint of the calling thread.
+///
+/// On Windows, it uses the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadIoPriority`.
+///
+/// On other platforms, it throws.
+pub fn get_thread_io_priority() -> Result<WindowsIoPriority> {
+  #[cfg(windows)]
+  {
+    use windows::Wdk::System::Threading::{NtQueryInformationThread, ThreadIoPriority};
+    use windows::Win32::System::Threading::GetCurrentThread;
+
+    let mut value = 0u32;
+    let mut length = 0;
+    sys::nt_result(unsafe {
+      NtQueryInformationThread(
+        GetCurrentThread(),
+        ThreadIoPriority,
+        &mut value as *mut u32 as *mut _,
+        std::mem::size_of::<u32>() as u32,
+        &mut length,
+      )
+    })
+    .map_err(|err| sys::priority_error(err, "get the I/O priority of the current thread"))?;
+    Ok(WindowsIoPriority::from_raw(value))
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the thread I/O priority"))
+  }
+}
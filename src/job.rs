@@ -0,0 +1,471 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::process::ProcessPriorityClass;
+use crate::sys;
+
+#[napi(object)]
+/// The scheduling limits of a job, every process in the job is constrained to them, `undefined` means no limit.
+pub struct JobLimits {
+  /// The priority class of every process in the job, the processes can't change it.
+  pub priority_class: Option<ProcessPriorityClass>,
+  /// The scheduling class between `0` and `9`, which sets the length of the time slices of the threads in the job relative to other jobs, `5` is the default.
+  /// Classes above `5` require the `SeIncreaseBasePriorityPrivilege` privilege.
+  pub scheduling_class: Option<u32>,
+  /// The CPUs the processes in the job may run on, it must be a subset of the CPUs of the current processor group.
+  pub affinity: Option<Vec<u32>>,
+}
+
+#[napi(object)]
+/// The memory limits of a job in bytes, `undefined` means no limit.
+pub struct JobMemoryLimits {
+  /// The memory each process in the job can commit, allocations beyond it fail.
+  pub process_memory: Option<f64>,
+  /// The memory all the processes in the job can commit together, allocations beyond it fail.
+  pub job_memory: Option<f64>,
+  /// The minimum working set of each process in the job, it must be set together with `maxWorkingSet`.
+  pub min_working_set: Option<f64>,
+  /// The maximum working set of each process in the job, pages beyond it are trimmed to the page file rather than failing allocations.
+  pub max_working_set: Option<f64>,
+}
+
+#[napi(object)]
+/// The I/O rate limits of a job on every volume, `undefined` means no limit.
+pub struct JobIoRateLimits {
+  /// The maximum I/O operations per second of the job, normalized to 8 KiB operations.
+  pub max_iops: Option<u32>,
+  /// The maximum bytes per second the job can read and write.
+  pub max_bandwidth: Option<f64>,
+  /// The I/O operations per second reserved for the job.
+  pub reservation_iops: Option<u32>,
+}
+
+/// Validates an amount of bytes passed from JavaScript.
+fn validate_bytes(value: Option<f64>, name: &str) -> Result<Option<usize>> {
+  value
+    .map(|value| {
+      if value.is_finite() && value >= 1.0 && value.fract() == 0.0 && value <= usize::MAX as f64 {
+        Ok(value as usize)
+      } else {
+        Err(Error::new(
+          Status::InvalidArg,
+          format!("{value} is not a valid {name}, expected a positive integer number of bytes"),
+        ))
+      }
+    })
+    .transpose()
+}
+
+#[cfg(windows)]
+fn query_extended_limits(
+  job: &sys::OwnedHandle,
+) -> std::io::Result<windows::Win32::System::JobObjects::JOBOBJECT_EXTENDED_LIMIT_INFORMATION> {
+  use windows::Win32::System::JobObjects::{
+    JobObjectExtendedLimitInformation, QueryInformationJobObject,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+  };
+
+  let mut information = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+  unsafe {
+    QueryInformationJobObject(
+      job.0,
+      JobObjectExtendedLimitInformation,
+      &mut information as *mut _ as *mut core::ffi::c_void,
+      std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+      None,
+    )
+  }
+  .map_err(sys::win32_error)?;
+  Ok(information)
+}
+
+#[cfg(windows)]
+/// Sets the extended limits, which include the basic limits, so changing one kind of limits keeps the others.
+fn set_extended_limits(
+  job: &sys::OwnedHandle,
+  information: &windows::Win32::System::JobObjects::JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+) -> std::io::Result<()> {
+  use windows::Win32::System::JobObjects::{
+    JobObjectExtendedLimitInformation, SetInformationJobObject,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+  };
+
+  unsafe {
+    SetInformationJobObject(
+      job.0,
+      JobObjectExtendedLimitInformation,
+      information as *const _ as *const core::ffi::c_void,
+      std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+    )
+  }
+  .map_err(sys::win32_error)
+}
+
+#[napi]
+/// A Windows [Job Object](https://learn.microsoft.com/en-us/windows/win32/procthread/job-objects), which constrains every process assigned to it, including the child processes they launch.
+/// The job is closed when the object is garbage collected, the limits still apply to the processes in the job until they exit.
+///
+/// On other platforms, the constructor throws.
+pub struct JobObject {
+  #[cfg(windows)]
+  job: sys::OwnedHandle,
+}
+
+#[napi]
+impl JobObject {
+  #[napi(constructor)]
+  /// Creates an anonymous job, or the job named `name` which other processes can open.
+  ///
+  /// On Windows, it uses the [`CreateJobObjectW`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-createjobobjectw) function.
+  pub fn new(name: Option<String>) -> Result<Self> {
+    #[cfg(windows)]
+    {
+      use windows::core::{HSTRING, PCWSTR};
+      use windows::Win32::System::JobObjects::CreateJobObjectW;
+
+      let name = name.map(HSTRING::from);
+      let job = unsafe {
+        CreateJobObjectW(
+          None,
+          name
+            .as_ref()
+            .map_or_else(PCWSTR::null, |name| PCWSTR(name.as_ptr())),
+        )
+      }
+      .map_err(|err| sys::priority_error(sys::win32_error(err), "create the job object"))?;
+      Ok(Self {
+        job: sys::OwnedHandle(job),
+      })
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = name;
+      Err(sys::unsupported("Job objects"))
+    }
+  }
+
+  #[napi]
+  /// Assigns the process identified by `pid` to the job, it defaults to the current process.
+  /// A process can't leave a job, and child processes launched afterwards are assigned to the job as well.
+  ///
+  /// On Windows, it uses the [`AssignProcessToJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-assignprocesstojobobject) function.
+  pub fn assign_process(&self, pid: Option<u32>) -> Result<()> {
+    crate::stats::record("JobObject.assignProcess", || {
+      #[cfg(windows)]
+      {
+        use windows::Win32::System::JobObjects::AssignProcessToJobObject;
+        use windows::Win32::System::Threading::{
+          GetCurrentProcess, PROCESS_SET_QUOTA, PROCESS_TERMINATE,
+        };
+
+        let assign = || {
+          let owned = pid
+            .map(|pid| sys::open_process(PROCESS_SET_QUOTA | PROCESS_TERMINATE, pid))
+            .transpose()?;
+          let process = owned
+            .as_ref()
+            .map_or_else(|| unsafe { GetCurrentProcess() }, |process| process.0);
+          unsafe { AssignProcessToJobObject(self.job.0, process) }.map_err(sys::win32_error)
+        };
+        assign().map_err(|err| {
+          sys::priority_error(
+            err,
+            &match pid {
+              Some(pid) => format!("assign process {pid} to the job"),
+              None => "assign the current process to the job".to_owned(),
+            },
+          )
+        })
+      }
+      #[cfg(not(windows))]
+      {
+        let _ = pid;
+        Err(sys::unsupported("Job objects"))
+      }
+    })
+  }
+
+  #[napi]
+  /// Replaces the priority class, scheduling class and affinity limits of the job, the other limits are kept.
+  ///
+  /// On Windows, it uses the [`SetInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setinformationjobobject) function with `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`.
+  pub fn set_limits(&self, limits: JobLimits) -> Result<()> {
+    crate::stats::record("JobObject.setLimits", || {
+      if let Some(scheduling_class) = limits.scheduling_class {
+        if scheduling_class > 9 {
+          return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+              "{scheduling_class} is not a valid scheduling class, expected a value between 0 and 9"
+            ),
+          ));
+        }
+      }
+      #[cfg(windows)]
+      {
+        use windows::Win32::System::JobObjects::{
+          JOB_OBJECT_LIMIT_AFFINITY, JOB_OBJECT_LIMIT_PRIORITY_CLASS,
+          JOB_OBJECT_LIMIT_SCHEDULING_CLASS,
+        };
+        use windows::Win32::System::Threading::PROCESS_CREATION_FLAGS;
+
+        if let Some(cpus) = &limits.affinity {
+          crate::affinity::validate_cpus(cpus)?;
+        }
+        let mut extended = query_extended_limits(&self.job)
+          .map_err(|err| sys::priority_error(err, "query the job limits"))?;
+        let information = &mut extended.BasicLimitInformation;
+        information.LimitFlags &= !(JOB_OBJECT_LIMIT_PRIORITY_CLASS
+          | JOB_OBJECT_LIMIT_SCHEDULING_CLASS
+          | JOB_OBJECT_LIMIT_AFFINITY);
+        if let Some(priority_class) = limits.priority_class {
+          information.LimitFlags |= JOB_OBJECT_LIMIT_PRIORITY_CLASS;
+          information.PriorityClass = PROCESS_CREATION_FLAGS::from(priority_class).0;
+        }
+        if let Some(scheduling_class) = limits.scheduling_class {
+          information.LimitFlags |= JOB_OBJECT_LIMIT_SCHEDULING_CLASS;
+          information.SchedulingClass = scheduling_class;
+        }
+        if let Some(cpus) = &limits.affinity {
+          information.LimitFlags |= JOB_OBJECT_LIMIT_AFFINITY;
+          information.Affinity = crate::affinity::cpus_to_mask(cpus);
+        }
+        set_extended_limits(&self.job, &extended)
+          .map_err(|err| sys::priority_error(err, "set the job limits"))
+      }
+      #[cfg(not(windows))]
+      {
+        Err(sys::unsupported("Job objects"))
+      }
+    })
+  }
+
+  #[napi]
+  /// Returns the priority class, scheduling class and affinity limits of the job.
+  ///
+  /// On Windows, it uses the [`QueryInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryinformationjobobject) function with `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`.
+  pub fn get_limits(&self) -> Result<JobLimits> {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::JobObjects::{
+        JOB_OBJECT_LIMIT_AFFINITY, JOB_OBJECT_LIMIT_PRIORITY_CLASS,
+        JOB_OBJECT_LIMIT_SCHEDULING_CLASS,
+      };
+      use windows::Win32::System::Threading::PROCESS_CREATION_FLAGS;
+
+      let information = query_extended_limits(&self.job)
+        .map_err(|err| sys::priority_error(err, "query the job limits"))?
+        .BasicLimitInformation;
+      let flags = information.LimitFlags;
+      Ok(JobLimits {
+        priority_class: flags
+          .contains(JOB_OBJECT_LIMIT_PRIORITY_CLASS)
+          .then(|| ProcessPriorityClass::from(PROCESS_CREATION_FLAGS(information.PriorityClass))),
+        scheduling_class: flags
+          .contains(JOB_OBJECT_LIMIT_SCHEDULING_CLASS)
+          .then_some(information.SchedulingClass),
+        affinity: flags
+          .contains(JOB_OBJECT_LIMIT_AFFINITY)
+          .then(|| crate::affinity::mask_to_cpus(information.Affinity)),
+      })
+    }
+    #[cfg(not(windows))]
+    {
+      Err(sys::unsupported("Job objects"))
+    }
+  }
+
+  #[napi]
+  /// Replaces the memory limits of the job, the other limits are kept.
+  ///
+  /// On Windows, it uses the [`SetInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setinformationjobobject) function with [`JOBOBJECT_EXTENDED_LIMIT_INFORMATION`](https://learn.microsoft.com/en-us/windows/win32/api/winnt/ns-winnt-jobobject_extended_limit_information).
+  pub fn set_memory_limits(&self, limits: JobMemoryLimits) -> Result<()> {
+    crate::stats::record("JobObject.setMemoryLimits", || {
+      let process_memory = validate_bytes(limits.process_memory, "process memory limit")?;
+      let job_memory = validate_bytes(limits.job_memory, "job memory limit")?;
+      let min_working_set = validate_bytes(limits.min_working_set, "minimum working set")?;
+      let max_working_set = validate_bytes(limits.max_working_set, "maximum working set")?;
+      let working_set = match (min_working_set, max_working_set) {
+        (Some(min), Some(max)) if min <= max => Some((min, max)),
+        (None, None) => None,
+        (Some(_), Some(_)) => {
+          return Err(Error::new(
+            Status::InvalidArg,
+            "minWorkingSet must not be greater than maxWorkingSet".to_owned(),
+          ))
+        }
+        _ => {
+          return Err(Error::new(
+            Status::InvalidArg,
+            "minWorkingSet and maxWorkingSet must be set together".to_owned(),
+          ))
+        }
+      };
+      #[cfg(windows)]
+      {
+        use windows::Win32::System::JobObjects::{
+          JOB_OBJECT_LIMIT_JOB_MEMORY, JOB_OBJECT_LIMIT_PROCESS_MEMORY, JOB_OBJECT_LIMIT_WORKINGSET,
+        };
+
+        let mut information = query_extended_limits(&self.job)
+          .map_err(|err| sys::priority_error(err, "query the job limits"))?;
+        let basic = &mut information.BasicLimitInformation;
+        basic.LimitFlags &= !(JOB_OBJECT_LIMIT_PROCESS_MEMORY
+          | JOB_OBJECT_LIMIT_JOB_MEMORY
+          | JOB_OBJECT_LIMIT_WORKINGSET);
+        if let Some(process_memory) = process_memory {
+          basic.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+          information.ProcessMemoryLimit = process_memory;
+        }
+        if let Some(job_memory) = job_memory {
+          basic.LimitFlags |= JOB_OBJECT_LIMIT_JOB_MEMORY;
+          information.JobMemoryLimit = job_memory;
+        }
+        if let Some((min, max)) = working_set {
+          basic.LimitFlags |= JOB_OBJECT_LIMIT_WORKINGSET;
+          basic.MinimumWorkingSetSize = min;
+          basic.MaximumWorkingSetSize = max;
+        }
+        set_extended_limits(&self.job, &information)
+          .map_err(|err| sys::priority_error(err, "set the job memory limits"))
+      }
+      #[cfg(not(windows))]
+      {
+        let _ = (process_memory, job_memory, working_set);
+        Err(sys::unsupported("Job objects"))
+      }
+    })
+  }
+
+  #[napi]
+  /// Returns the memory limits of the job.
+  ///
+  /// On Windows, it uses the [`QueryInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryinformationjobobject) function with `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`.
+  pub fn get_memory_limits(&self) -> Result<JobMemoryLimits> {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::JobObjects::{
+        JOB_OBJECT_LIMIT_JOB_MEMORY, JOB_OBJECT_LIMIT_PROCESS_MEMORY, JOB_OBJECT_LIMIT_WORKINGSET,
+      };
+
+      let information = query_extended_limits(&self.job)
+        .map_err(|err| sys::priority_error(err, "query the job limits"))?;
+      let basic = &information.BasicLimitInformation;
+      let flags = basic.LimitFlags;
+      let working_set = flags.contains(JOB_OBJECT_LIMIT_WORKINGSET);
+      Ok(JobMemoryLimits {
+        process_memory: flags
+          .contains(JOB_OBJECT_LIMIT_PROCESS_MEMORY)
+          .then_some(information.ProcessMemoryLimit as f64),
+        job_memory: flags
+          .contains(JOB_OBJECT_LIMIT_JOB_MEMORY)
+          .then_some(information.JobMemoryLimit as f64),
+        min_working_set: working_set.then_some(basic.MinimumWorkingSetSize as f64),
+        max_working_set: working_set.then_some(basic.MaximumWorkingSetSize as f64),
+      })
+    }
+    #[cfg(not(windows))]
+    {
+      Err(sys::unsupported("Job objects"))
+    }
+  }
+
+  #[napi]
+  /// Replaces the I/O rate limits of the job on every volume, passing no limit disables the I/O rate control.
+  ///
+  /// On Windows, it uses the [`SetIoRateControlInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-setioratecontrolinformationjobobject) function, which requires Windows 10 and a job that is not nested in a job with I/O rate control.
+  pub fn set_io_rate_limits(&self, limits: JobIoRateLimits) -> Result<()> {
+    crate::stats::record("JobObject.setIoRateLimits", || {
+      let max_bandwidth = validate_bytes(limits.max_bandwidth, "maximum bandwidth")?;
+      #[cfg(windows)]
+      {
+        use windows::Win32::System::JobObjects::{
+          SetIoRateControlInformationJobObject, JOBOBJECT_IO_RATE_CONTROL_INFORMATION,
+          JOB_OBJECT_IO_RATE_CONTROL_ENABLE,
+        };
+
+        let enabled =
+          limits.max_iops.is_some() || max_bandwidth.is_some() || limits.reservation_iops.is_some();
+        // a null volume name applies the limits to every volume
+        let information = JOBOBJECT_IO_RATE_CONTROL_INFORMATION {
+          MaxIops: limits.max_iops.map_or(0, i64::from),
+          MaxBandwidth: max_bandwidth.map_or(0, |bandwidth| bandwidth as i64),
+          ReservationIops: limits.reservation_iops.map_or(0, i64::from),
+          ControlFlags: if enabled {
+            JOB_OBJECT_IO_RATE_CONTROL_ENABLE.0 as u32
+          } else {
+            0
+          },
+          ..Default::default()
+        };
+        // it returns a `DWORD` which is zero on failure, with the error in the last error
+        if unsafe { SetIoRateControlInformationJobObject(self.job.0, &information) } == 0 {
+          return Err(sys::priority_error(
+            std::io::Error::last_os_error(),
+            "set the job I/O rate limits",
+          ));
+        }
+        Ok(())
+      }
+      #[cfg(not(windows))]
+      {
+        let _ = (limits, max_bandwidth);
+        Err(sys::unsupported("Job objects"))
+      }
+    })
+  }
+
+  #[napi]
+  /// Returns the I/O rate limits of the job on every volume.
+  ///
+  /// On Windows, it uses the [`QueryIoRateControlInformationJobObject`](https://learn.microsoft.com/en-us/windows/win32/api/jobapi2/nf-jobapi2-queryioratecontrolinformationjobobject) function.
+  pub fn get_io_rate_limits(&self) -> Result<JobIoRateLimits> {
+    #[cfg(windows)]
+    {
+      use windows::core::PCWSTR;
+      use windows::Win32::System::JobObjects::{
+        FreeMemoryJobObject, QueryIoRateControlInformationJobObject,
+        JOBOBJECT_IO_RATE_CONTROL_INFORMATION, JOB_OBJECT_IO_RATE_CONTROL_ENABLE,
+      };
+
+      let mut blocks: *mut JOBOBJECT_IO_RATE_CONTROL_INFORMATION = std::ptr::null_mut();
+      let mut count = 0u32;
+      if unsafe {
+        QueryIoRateControlInformationJobObject(self.job.0, PCWSTR::null(), &mut blocks, &mut count)
+      } == 0
+      {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "query the job I/O rate limits",
+        ));
+      }
+      let limits = if blocks.is_null() {
+        None
+      } else {
+        let information = unsafe { std::slice::from_raw_parts(blocks, count as usize) }
+          .iter()
+          .find(|information| {
+            information.ControlFlags & JOB_OBJECT_IO_RATE_CONTROL_ENABLE.0 as u32 != 0
+          })
+          .map(|information| JobIoRateLimits {
+            max_iops: (information.MaxIops > 0).then_some(information.MaxIops as u32),
+            max_bandwidth: (information.MaxBandwidth > 0)
+              .then_some(information.MaxBandwidth as f64),
+            reservation_iops: (information.ReservationIops > 0)
+              .then_some(information.ReservationIops as u32),
+          });
+        unsafe { FreeMemoryJobObject(blocks as *const core::ffi::c_void) };
+        information
+      };
+      Ok(limits.unwrap_or(JobIoRateLimits {
+        max_iops: None,
+        max_bandwidth: None,
+        reservation_iops: None,
+      }))
+    }
+    #[cfg(not(windows))]
+    {
+      Err(sys::unsupported("Job objects"))
+    }
+  }
+}
@@ -0,0 +1,440 @@
+use napi::bindgen_prelude::{FromNapiValue, Function, PromiseRaw};
+use napi::{Env, Error, JsValue, Result, Status, Unknown};
+use napi_derive::napi;
+use std::sync::OnceLock;
+
+use crate::sys;
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Cross-platform priority levels, ordered from the lowest to the highest priority.
+pub enum PriorityLevel {
+  Idle = 0,
+  Low = 1,
+  BelowNormal = 2,
+  Normal = 3,
+  AboveNormal = 4,
+  High = 5,
+  Realtime = 6,
+}
+
+impl PriorityLevel {
+  #[cfg(unix)]
+  pub(crate) fn to_nice(self) -> i32 {
+    match self {
+      Self::Idle => 19,
+      Self::Low => 15,
+      Self::BelowNormal => 10,
+      Self::Normal => 0,
+      Self::AboveNormal => -5,
+      Self::High => -10,
+      Self::Realtime => -20,
+    }
+  }
+
+  #[cfg(unix)]
+  /// Maps a nice value onto the nearest level.
+  pub(crate) fn from_nice(value: i32) -> Self {
+    match value {
+      i32::MIN..=-15 => Self::Realtime,
+      -14..=-8 => Self::High,
+      -7..=-3 => Self::AboveNormal,
+      -2..=4 => Self::Normal,
+      5..=12 => Self::BelowNormal,
+      13..=17 => Self::Low,
+      _ => Self::Idle,
+    }
+  }
+
+  #[cfg(target_vendor = "apple")]
+  pub(crate) fn to_qos_class(self) -> libc::qos_class_t {
+    match self {
+      Self::Idle | Self::Low => libc::qos_class_t::QOS_CLASS_BACKGROUND,
+      Self::BelowNormal => libc::qos_class_t::QOS_CLASS_UTILITY,
+      Self::Normal => libc::qos_class_t::QOS_CLASS_DEFAULT,
+      Self::AboveNormal => libc::qos_class_t::QOS_CLASS_USER_INITIATED,
+      Self::High | Self::Realtime => libc::qos_class_t::QOS_CLASS_USER_INTERACTIVE,
+    }
+  }
+
+  #[cfg(windows)]
+  /// The process priority class and the calling thread priority used for this level.
+  pub(crate) fn to_windows(
+    self,
+  ) -> (
+    crate::process::ProcessPriorityClass,
+    crate::WindowsThreadPriority,
+  ) {
+    use crate::process::ProcessPriorityClass;
+    use crate::WindowsThreadPriority;
+
+    match self {
+      Self::Idle => (
+        ProcessPriorityClass::Idle,
+        WindowsThreadPriority::ThreadPriorityNormal,
+      ),
+      Self::Low => (
+        ProcessPriorityClass::BelowNormal,
+        WindowsThreadPriority::ThreadPriorityLowest,
+      ),
+      Self::BelowNormal => (
+        ProcessPriorityClass::BelowNormal,
+        WindowsThreadPriority::ThreadPriorityNormal,
+      ),
+      Self::Normal => (
+        ProcessPriorityClass::Normal,
+        WindowsThreadPriority::ThreadPriorityNormal,
+      ),
+      Self::AboveNormal => (
+        ProcessPriorityClass::AboveNormal,
+        WindowsThreadPriority::ThreadPriorityNormal,
+      ),
+      Self::High => (
+        ProcessPriorityClass::High,
+        WindowsThreadPriority::ThreadPriorityNormal,
+      ),
+      Self::Realtime => (
+        ProcessPriorityClass::Realtime,
+        WindowsThreadPriority::ThreadPriorityNormal,
+      ),
+    }
+  }
+
+  #[cfg(windows)]
+  pub(crate) fn from_windows(
+    priority_class: crate::process::ProcessPriorityClass,
+    thread_priority: i32,
+  ) -> Self {
+    use crate::process::ProcessPriorityClass;
+
+    match priority_class {
+      ProcessPriorityClass::Idle => Self::Idle,
+      ProcessPriorityClass::BelowNormal if thread_priority < 0 => Self::Low,
+      ProcessPriorityClass::BelowNormal => Self::BelowNormal,
+      ProcessPriorityClass::Normal => Self::Normal,
+      ProcessPriorityClass::AboveNormal => Self::AboveNormal,
+      ProcessPriorityClass::High => Self::High,
+      ProcessPriorityClass::Realtime => Self::Realtime,
+    }
+  }
+}
+
+impl std::str::FromStr for PriorityLevel {
+  type Err = Error;
+
+  /// Parses the camelCase name of a level, such as `idle` or `belowNormal`.
+  fn from_str(name: &str) -> Result<Self> {
+    match name {
+      "idle" => Ok(Self::Idle),
+      "low" => Ok(Self::Low),
+      "belowNormal" => Ok(Self::BelowNormal),
+      "normal" => Ok(Self::Normal),
+      "aboveNormal" => Ok(Self::AboveNormal),
+      "high" => Ok(Self::High),
+      "realtime" => Ok(Self::Realtime),
+      _ => Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "{name} is not a valid priority level, expected one of idle, low, belowNormal, normal, aboveNormal, high or realtime"
+        ),
+      )),
+    }
+  }
+}
+
+#[napi]
+/// This function set the priority of the current process to a cross-platform `PriorityLevel`.
+///
+/// On Unix, the level is mapped onto a nice value (Idle: 19, Low: 15, BelowNormal: 10, Normal: 0, AboveNormal: -5, High: -10, Realtime: -20) and applied with [`setpriority(2)`](https://linux.die.net/man/2/setpriority).
+/// Raising the priority requires root or CAP_SYS_NICE.
+///
+/// On macOS, the calling thread's QoS class is also updated with `pthread_set_qos_class_self_np`, because nice values are largely ignored under QoS.
+///
+/// On Windows, the level is mapped onto a process priority class and the calling thread priority.
+pub fn set_priority_level(level: PriorityLevel) -> Result<()> {
+  crate::stats::record("setPriorityLevel", || apply_priority_level(level))
+}
+
+pub(crate) fn apply_priority_level(level: PriorityLevel) -> Result<()> {
+  #[cfg(unix)]
+  {
+    crate::process::set_process_nice(0, level.to_nice())
+      .map_err(|err| sys::priority_error(err, "set the priority level"))?;
+    #[cfg(target_vendor = "apple")]
+    {
+      let ret = unsafe { libc::pthread_set_qos_class_self_np(level.to_qos_class(), 0) };
+      if ret != 0 {
+        return Err(sys::priority_error(
+          std::io::Error::from_raw_os_error(ret),
+          "set the QoS class of the current thread",
+        ));
+      }
+    }
+    Ok(())
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority};
+
+    let (priority_class, thread_priority) = level.to_windows();
+    crate::process::apply_priority_class(0, priority_class.into())
+      .map_err(|err| sys::priority_error(err, "set the priority level"))?;
+    unsafe { SetThreadPriority(GetCurrentThread(), thread_priority.into()) }
+      .map_err(|err| sys::priority_error(sys::win32_error(err), "set the priority level"))
+  }
+}
+
+#[napi]
+/// This function get the priority of the current process as a cross-platform `PriorityLevel`.
+///
+/// On Unix, the nice value is mapped onto the nearest level.
+///
+/// On Windows, the level is derived from the process priority class and the calling thread priority.
+pub fn get_priority_level() -> Result<PriorityLevel> {
+  #[cfg(unix)]
+  {
+    sys::getpriority(libc::PRIO_PROCESS, 0)
+      .map(PriorityLevel::from_nice)
+      .map_err(|err| sys::priority_error(err, "get the priority level"))
+  }
+  #[cfg(windows)]
+  {
+    let priority_class = crate::process::query_priority_class(0)
+      .map_err(|err| sys::priority_error(err, "get the priority level"))?;
+    let thread_priority = crate::get_current_process_priority()?;
+    Ok(PriorityLevel::from_windows(
+      priority_class.into(),
+      thread_priority,
+    ))
+  }
+}
+
+/// Maps `0..=100` linearly onto the nice values `19..=-20`.
+fn normalized_to_nice(priority: u32) -> i32 {
+  19 - ((priority.min(100) * 39 + 50) / 100) as i32
+}
+
+fn nice_to_normalized(value: i32) -> u32 {
+  (((19 - value.clamp(-20, 19)) * 100 + 19) / 39) as u32
+}
+
+#[napi]
+/// This function get the priority of the current process on a normalized scale, from `0` (the lowest priority) to `100` (the highest priority).
+///
+/// On Unix, the nice value `19..-20` is mapped linearly onto `0..100`.
+///
+/// On Windows, the process priority class is translated into a nice value the same way as `getPriority`, then mapped the same way.
+pub fn get_normalized_priority() -> Result<u32> {
+  crate::process::get_priority(0).map(nice_to_normalized)
+}
+
+#[napi]
+/// This function set the priority of the current process on a normalized scale, from `0` (the lowest priority) to `100` (the highest priority).
+///
+/// On Unix, `priority` is mapped linearly onto the nice values `19..-20`, there are only 40 nice values so nearby priorities may be the same.
+/// Raising the priority requires root or CAP_SYS_NICE.
+///
+/// On Windows, `priority` is mapped onto a nice value the same way, then onto the nearest process priority class the same way as `setPriority`.
+pub fn set_normalized_priority(priority: u32) -> Result<()> {
+  crate::stats::record("setNormalizedPriority", || {
+    if priority > 100 {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "{priority} is not a valid normalized priority, expected a value between 0 and 100"
+        ),
+      ));
+    }
+    crate::process::apply_priority(0, normalized_to_nice(priority))
+  })
+}
+
+/// The priority of the current process and thread, which can be restored later.
+#[derive(Clone)]
+pub(crate) struct PrioritySnapshot {
+  #[cfg(unix)]
+  nice: i32,
+  #[cfg(target_vendor = "apple")]
+  qos_class: libc::qos_class_t,
+  #[cfg(windows)]
+  priority_class: windows::Win32::System::Threading::PROCESS_CREATION_FLAGS,
+  #[cfg(windows)]
+  thread_priority: i32,
+}
+
+impl PrioritySnapshot {
+  pub(crate) fn capture() -> Result<Self> {
+    Self::read().map_err(|err| sys::priority_error(err, "get the current priority"))
+  }
+
+  fn read() -> std::io::Result<Self> {
+    #[cfg(unix)]
+    {
+      let nice = sys::getpriority(libc::PRIO_PROCESS, 0)?;
+      #[cfg(target_vendor = "apple")]
+      let qos_class = {
+        let mut qos_class = libc::qos_class_t::QOS_CLASS_UNSPECIFIED;
+        let mut relative_priority = 0;
+        let ret = unsafe {
+          libc::pthread_get_qos_class_np(
+            libc::pthread_self(),
+            &mut qos_class,
+            &mut relative_priority,
+          )
+        };
+        if ret != 0 {
+          return Err(std::io::Error::from_raw_os_error(ret));
+        }
+        qos_class
+      };
+      Ok(Self {
+        nice,
+        #[cfg(target_vendor = "apple")]
+        qos_class,
+      })
+    }
+    #[cfg(windows)]
+    {
+      Ok(Self {
+        priority_class: crate::process::query_priority_class(0)?,
+        thread_priority: sys::current_thread_priority()?,
+      })
+    }
+  }
+
+  /// Restoring a higher priority than the current one requires the same privileges as raising it.
+  pub(crate) fn restore(&self) -> Result<()> {
+    #[cfg(unix)]
+    {
+      crate::process::set_process_nice(0, self.nice)
+        .map_err(|err| sys::priority_error(err, "restore the priority"))?;
+      #[cfg(target_vendor = "apple")]
+      {
+        let qos_class = match self.qos_class {
+          libc::qos_class_t::QOS_CLASS_UNSPECIFIED => libc::qos_class_t::QOS_CLASS_DEFAULT,
+          qos_class => qos_class,
+        };
+        let ret = unsafe { libc::pthread_set_qos_class_self_np(qos_class, 0) };
+        if ret != 0 {
+          return Err(sys::priority_error(
+            std::io::Error::from_raw_os_error(ret),
+            "restore the QoS class of the current thread",
+          ));
+        }
+      }
+      Ok(())
+    }
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{
+        GetCurrentThread, SetThreadPriority, THREAD_PRIORITY,
+      };
+
+      crate::process::apply_priority_class(0, self.priority_class)
+        .map_err(|err| sys::priority_error(err, "restore the priority"))?;
+      unsafe { SetThreadPriority(GetCurrentThread(), THREAD_PRIORITY(self.thread_priority)) }
+        .map_err(|err| sys::priority_error(sys::win32_error(err), "restore the priority"))
+    }
+  }
+}
+
+/// The priority when the addon was loaded, `None` if it could not be read.
+static DEFAULT_PRIORITY: OnceLock<Option<DefaultPriority>> = OnceLock::new();
+
+struct DefaultPriority {
+  snapshot: PrioritySnapshot,
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  policy: libc::c_int,
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  param: libc::sched_param,
+}
+
+impl DefaultPriority {
+  /// It runs while the addon is loaded, before any JavaScript error can be created, so it only deals with OS errors.
+  fn capture() -> std::io::Result<Self> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let (policy, param) = {
+      let policy = unsafe { libc::sched_getscheduler(0) };
+      if policy == -1 {
+        return Err(std::io::Error::last_os_error());
+      }
+      let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+      if unsafe { libc::sched_getparam(0, &mut param) } == -1 {
+        return Err(std::io::Error::last_os_error());
+      }
+      (policy, param)
+    };
+    Ok(Self {
+      snapshot: PrioritySnapshot::read()?,
+      #[cfg(any(target_os = "linux", target_os = "android"))]
+      policy,
+      #[cfg(any(target_os = "linux", target_os = "android"))]
+      param,
+    })
+  }
+
+  fn restore(&self) -> Result<()> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      // the policy is restored first, because leaving a realtime policy resets the nice value
+      if unsafe { libc::sched_setscheduler(0, self.policy, &self.param) } == -1 {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "restore the scheduling policy",
+        ));
+      }
+    }
+    self.snapshot.restore()
+  }
+}
+
+#[napi_derive::module_init]
+fn capture_default_priority() {
+  DEFAULT_PRIORITY.get_or_init(|| DefaultPriority::capture().ok());
+}
+
+#[napi]
+/// This function restores the priority the current process had when the addon was loaded, reverting every change made since then.
+///
+/// It restores the nice value of the calling thread, and on Linux its scheduling policy as well. On macOS it restores the QoS class of the calling thread, on Windows the process priority class and the calling thread priority.
+///
+/// Restoring a higher priority than the current one requires the same privileges as raising it (root or CAP_SYS_NICE on Unix).
+pub fn restore_default_priority() -> Result<()> {
+  crate::stats::record("restoreDefaultPriority", || match DEFAULT_PRIORITY.get() {
+    Some(Some(default_priority)) => default_priority.restore(),
+    _ => Err(Error::new(
+      Status::GenericFailure,
+      "The priority was not captured when the addon was loaded".to_owned(),
+    )),
+  })
+}
+
+#[napi(ts_generic_types = "T", ts_return_type = "T")]
+/// This function set the priority of the current process to `level`, invokes `callback`, and restores the previous priority afterwards, even if `callback` throws.
+/// If `callback` returns a `Promise`, the priority is restored once the `Promise` settles, and the returned `Promise` rejects if restoring fails.
+///
+/// Restoring a higher priority than `level` requires the same privileges as raising the priority (root or CAP_SYS_NICE on Unix).
+pub fn with_priority<'env>(
+  env: &'env Env,
+  level: PriorityLevel,
+  #[napi(ts_arg_type = "() => T")] callback: Function<'env, (), Unknown<'env>>,
+) -> Result<Unknown<'env>> {
+  let snapshot = PrioritySnapshot::capture()?;
+  // only the priority change is counted, not the errors thrown by `callback`
+  crate::stats::record("withPriority", || apply_priority_level(level))?;
+  let value = match callback.call(()) {
+    Ok(value) => value,
+    Err(err) => {
+      // the error thrown by `callback` is more relevant than a failure to restore
+      let _ = snapshot.restore();
+      return Err(err);
+    }
+  };
+  if value.is_promise()? {
+    let mut promise: PromiseRaw<Unknown> =
+      unsafe { PromiseRaw::from_napi_value(env.raw(), value.raw()) }?;
+    return Ok(promise.finally(move |_| snapshot.restore())?.to_unknown());
+  }
+  snapshot.restore()?;
+  Ok(value)
+}
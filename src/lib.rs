@@ -1,9 +1,46 @@
 #![deny(clippy::all)]
 
+use napi::bindgen_prelude::Either3;
 use napi::{Error, Result, Status};
 use napi_derive::napi;
 
+pub mod activity;
+pub mod affinity;
+pub mod audio;
+pub mod background;
+pub mod cgroup;
+pub mod cpu_set;
+pub mod guard;
+pub mod io_priority;
+pub mod job;
+pub mod level;
+pub mod mapping;
+pub mod metrics;
+pub mod mmcss;
+pub mod numa;
+#[cfg(target_vendor = "apple")]
+mod objc;
+pub mod oom;
+pub mod power;
+pub mod pressure;
+pub mod privilege;
+pub mod process;
+pub mod qos;
+pub mod sched;
+pub mod snapshot;
+pub mod stats;
+mod sys;
+pub mod systemd;
+pub mod thermal;
+pub mod thread;
+pub mod timer;
+pub mod topology;
+pub mod usage;
+pub mod watch;
+pub mod workgroup;
+
 #[napi]
+#[derive(Clone, Copy)]
 pub enum WindowsThreadPriority {
   ThreadModeBackgroundBegin = 0x00010000,
   ThreadModeBackgroundEnd = 0x00020000,
@@ -38,67 +75,434 @@ impl TryFrom<i32> for WindowsThreadPriority {
   }
 }
 
+#[cfg(windows)]
+impl From<WindowsThreadPriority> for windows::Win32::System::Threading::THREAD_PRIORITY {
+  fn from(priority: WindowsThreadPriority) -> Self {
+    match priority {
+      WindowsThreadPriority::ThreadModeBackgroundBegin => {
+        windows::Win32::System::Threading::THREAD_MODE_BACKGROUND_BEGIN
+      }
+      WindowsThreadPriority::ThreadModeBackgroundEnd => {
+        windows::Win32::System::Threading::THREAD_MODE_BACKGROUND_END
+      }
+      WindowsThreadPriority::ThreadPriorityAboveNormal => {
+        windows::Win32::System::Threading::THREAD_PRIORITY_ABOVE_NORMAL
+      }
+      WindowsThreadPriority::ThreadPriorityBelowNormal => {
+        windows::Win32::System::Threading::THREAD_PRIORITY_BELOW_NORMAL
+      }
+      WindowsThreadPriority::ThreadPriorityHighest => {
+        windows::Win32::System::Threading::THREAD_PRIORITY_HIGHEST
+      }
+      WindowsThreadPriority::ThreadPriorityIdle => {
+        windows::Win32::System::Threading::THREAD_PRIORITY_IDLE
+      }
+      WindowsThreadPriority::ThreadPriorityLowest => {
+        windows::Win32::System::Threading::THREAD_PRIORITY_LOWEST
+      }
+      WindowsThreadPriority::ThreadPriorityNormal => {
+        windows::Win32::System::Threading::THREAD_PRIORITY_NORMAL
+      }
+      WindowsThreadPriority::ThreadPriorityTimeCritical => {
+        windows::Win32::System::Threading::THREAD_PRIORITY_TIME_CRITICAL
+      }
+    }
+  }
+}
+
+#[napi(object)]
+pub struct NiceOptions {
+  /// On Windows, treat `incr` as a nice value in `-20..19` and map it onto the nearest `WindowsThreadPriority` (see `niceToWindowsThreadPriority`), instead of throwing when it is not a valid `WindowsThreadPriority`.
+  /// It has no effect on Unix.
+  pub map_nice_value: Option<bool>,
+}
+
+#[napi(object)]
+pub struct NiceTarget {
+  /// The nice value increment, a higher nice value means a lower priority. The result is clamped to `-20..19`.
+  pub value: i32,
+  /// Whether the change applies to the calling thread or to the whole process, it defaults to `thread`.
+  /// It must be `process` (or omitted) when `pid` is set.
+  #[napi(ts_type = "'thread' | 'process'")]
+  pub target: Option<String>,
+  /// The process to change instead of the current one.
+  pub pid: Option<u32>,
+}
+
 #[napi]
 /// This function set the priority of the current process.
 /// On Unix, it uses the [`nice`](https://linux.die.net/man/2/nice) function.
 ///
 /// On Windows, it uses the [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) function.
-pub fn nice(incr: Option<i32>) -> Result<i32> {
-  let incr = incr.unwrap_or(0);
+/// Pass `{ mapNiceValue: true }` to use nice values on Windows as well.
+///
+/// This function is kept for compatibility, it is relative on Unix but absolute on Windows. Prefer `adjustPriority` and `setAbsolutePriority`, which behave the same on every platform.
+///
+/// Pass the camelCase name of a `PriorityLevel`, such as `'belowNormal'`, to set that level the same way as `setPriorityLevel`, and get the new priority back.
+///
+/// Pass a `NiceTarget` instead of a number to choose explicitly what is changed, `value` is then a nice value increment on every platform and the new nice value is returned. `options` is ignored in that case.
+/// - `thread`: the calling thread. On Linux the nice value is per thread, on Windows the thread priority is translated with the nice mapping. Other Unix platforms only have per-process nice values and throw.
+/// - `process`: every thread of the current process. On Linux each thread of `/proc/self/task` is updated, on Windows the process priority class is changed.
+/// - `pid`: the process identified by `pid`, the same way as `setPriority`.
+pub fn nice(
+  #[napi(
+    ts_arg_type = "number | 'idle' | 'low' | 'belowNormal' | 'normal' | 'aboveNormal' | 'high' | 'realtime' | NiceTarget"
+  )]
+  incr: Option<Either3<i32, String, NiceTarget>>,
+  options: Option<NiceOptions>,
+) -> Result<i32> {
+  crate::stats::record("nice", || apply_nice(incr, options))
+}
+
+pub(crate) fn apply_nice(
+  incr: Option<Either3<i32, String, NiceTarget>>,
+  options: Option<NiceOptions>,
+) -> Result<i32> {
+  let incr = match incr {
+    Some(Either3::C(target)) => return nice_target(target),
+    Some(Either3::B(name)) => {
+      level::apply_priority_level(name.parse()?)?;
+      return get_current_process_priority();
+    }
+    Some(Either3::A(incr)) => incr,
+    None => 0,
+  };
   #[cfg(unix)]
-  unsafe {
-    let ret = libc::nice(incr);
-    if ret == -1 {
-      return Err(std::io::Error::last_os_error().into());
+  {
+    let _ = options;
+    let change = || {
+      let ret = unsafe { libc::nice(incr) };
+      if ret == -1 {
+        return Err(std::io::Error::last_os_error());
+      }
+      Ok(ret)
+    };
+    if process::is_own_process(0) {
+      process::track_own_priority(change)
+    } else {
+      change()
     }
-    Ok(ret)
+    .map_err(Error::from)
   }
   #[cfg(windows)]
   {
-    use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority, THREAD_PRIORITY};
-
-    impl From<WindowsThreadPriority> for THREAD_PRIORITY {
-      fn from(priority: WindowsThreadPriority) -> Self {
-        match priority {
-          WindowsThreadPriority::ThreadModeBackgroundBegin => {
-            windows::Win32::System::Threading::THREAD_MODE_BACKGROUND_BEGIN
-          }
-          WindowsThreadPriority::ThreadModeBackgroundEnd => {
-            windows::Win32::System::Threading::THREAD_MODE_BACKGROUND_END
-          }
-          WindowsThreadPriority::ThreadPriorityAboveNormal => {
-            windows::Win32::System::Threading::THREAD_PRIORITY_ABOVE_NORMAL
-          }
-          WindowsThreadPriority::ThreadPriorityBelowNormal => {
-            windows::Win32::System::Threading::THREAD_PRIORITY_BELOW_NORMAL
-          }
-          WindowsThreadPriority::ThreadPriorityHighest => {
-            windows::Win32::System::Threading::THREAD_PRIORITY_HIGHEST
-          }
-          WindowsThreadPriority::ThreadPriorityIdle => {
-            windows::Win32::System::Threading::THREAD_PRIORITY_IDLE
-          }
-          WindowsThreadPriority::ThreadPriorityLowest => {
-            windows::Win32::System::Threading::THREAD_PRIORITY_LOWEST
-          }
-          WindowsThreadPriority::ThreadPriorityNormal => {
-            windows::Win32::System::Threading::THREAD_PRIORITY_NORMAL
-          }
-          WindowsThreadPriority::ThreadPriorityTimeCritical => {
-            windows::Win32::System::Threading::THREAD_PRIORITY_TIME_CRITICAL
-          }
-        }
-      }
-    }
+    use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority};
 
     let current_thread = unsafe { GetCurrentThread() };
-    let priority: WindowsThreadPriority = incr.try_into()?;
+    let priority: WindowsThreadPriority = if options
+      .and_then(|options| options.map_nice_value)
+      .unwrap_or(false)
+    {
+      mapping::nice_to_thread_priority(incr)
+    } else {
+      incr.try_into()?
+    };
     unsafe { SetThreadPriority(current_thread, priority.into()) }
       .map_err(|e| Error::new(Status::GenericFailure, e.message().to_string()))?;
     Ok(priority as i32)
   }
 }
 
+/// Applies `nice({ value, target, pid })`.
+fn nice_target(options: NiceTarget) -> Result<i32> {
+  let NiceTarget { value, target, pid } = options;
+  let process = match target.as_deref() {
+    None => pid.is_some(),
+    Some("thread") => false,
+    Some("process") => true,
+    Some(target) => {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("{target} is not a valid target, expected thread or process"),
+      ))
+    }
+  };
+  if let Some(pid) = pid {
+    if !process {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "target must be process when pid is set".to_owned(),
+      ));
+    }
+    let new_value = process::get_priority(pid)?
+      .saturating_add(value)
+      .clamp(-20, 19);
+    process::apply_priority(pid, new_value)?;
+    return process::get_priority(pid);
+  }
+  if !process {
+    #[cfg(any(target_os = "linux", target_os = "android", windows))]
+    {
+      return apply_priority_adjustment(value);
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+    {
+      return Err(sys::unsupported("Changing the priority of a single thread"));
+    }
+  }
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let new_value = sys::getpriority(libc::PRIO_PROCESS, 0)
+      .map_err(|err| sys::priority_error(err, "get the priority"))?
+      .saturating_add(value)
+      .clamp(-20, 19);
+    thread::apply_all_threads_priority(new_value)?;
+    Ok(new_value)
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let new_value = process::get_priority(0)?
+      .saturating_add(value)
+      .clamp(-20, 19);
+    process::apply_priority(0, new_value)?;
+    process::get_priority(0)
+  }
+}
+
+#[napi(object)]
+pub struct PriorityRange {
+  pub min: i32,
+  pub max: i32,
+}
+
+#[napi(object)]
+/// How `nice` behaves on the current platform.
+pub struct PlatformPriorityInfo {
+  /// Whether `nice` changes the calling thread or the whole process.
+  #[napi(ts_type = "'thread' | 'process'")]
+  pub kind: String,
+  /// The values accepted by `nice`, `min` is the highest priority on Unix and the lowest priority on Windows.
+  pub range: PriorityRange,
+  /// Whether `nice` adds its argument to the current priority instead of replacing it.
+  pub relative: bool,
+}
+
+#[napi]
+/// This function describes how `nice` behaves on the current platform, so callers can adapt to it programmatically.
+///
+/// On Linux, nice values belong to threads, so `nice` changes the calling thread. On other Unix platforms it changes the process. In both cases the increment is relative and the nice values range from `-20` to `19`.
+///
+/// On Windows, `nice` sets the absolute `WindowsThreadPriority` of the calling thread, from `-15` to `15`.
+pub fn get_platform_priority_info() -> PlatformPriorityInfo {
+  #[cfg(unix)]
+  {
+    PlatformPriorityInfo {
+      kind: if cfg!(any(target_os = "linux", target_os = "android")) {
+        "thread"
+      } else {
+        "process"
+      }
+      .to_owned(),
+      range: PriorityRange { min: -20, max: 19 },
+      relative: true,
+    }
+  }
+  #[cfg(windows)]
+  {
+    PlatformPriorityInfo {
+      kind: "thread".to_owned(),
+      range: PriorityRange { min: -15, max: 15 },
+      relative: false,
+    }
+  }
+}
+
+#[napi(object)]
+pub struct PriorityChange {
+  /// The priority before the change.
+  pub previous: i32,
+  /// The priority after the change.
+  pub current: i32,
+}
+
+#[napi]
+/// This function is the same as `nice`, but it returns the priority before the change as well, which makes save/restore patterns trivial.
+pub fn nice_with_previous(
+  incr: Option<i32>,
+  options: Option<NiceOptions>,
+) -> Result<PriorityChange> {
+  crate::stats::record("niceWithPrevious", || {
+    let previous = get_current_process_priority()?;
+    let current = apply_nice(incr.map(Either3::A), options)?;
+    Ok(PriorityChange { previous, current })
+  })
+}
+
+#[cfg(windows)]
+fn current_thread_nice() -> Result<i32> {
+  Ok(mapping::thread_priority_value_to_nice(
+    get_current_process_priority()?,
+  ))
+}
+
+#[cfg(windows)]
+fn set_current_thread_nice(value: i32) -> Result<()> {
+  use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority};
+
+  unsafe {
+    SetThreadPriority(
+      GetCurrentThread(),
+      mapping::nice_to_thread_priority(value).into(),
+    )
+  }
+  .map_err(|err| sys::priority_error(sys::win32_error(err), "set the priority"))
+}
+
+#[napi]
+/// This function set the priority of the current process to the absolute nice value `value`, clamped to `-20..19`.
+///
+/// On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function. On Linux the nice value belongs to the calling thread.
+///
+/// On Windows, `value` is mapped onto the nearest `WindowsThreadPriority` (see `niceToWindowsThreadPriority`) and applied to the calling thread.
+pub fn set_absolute_priority(value: i32) -> Result<()> {
+  crate::stats::record("setAbsolutePriority", || apply_absolute_priority(value))
+}
+
+pub(crate) fn apply_absolute_priority(value: i32) -> Result<()> {
+  let value = value.clamp(-20, 19);
+  #[cfg(unix)]
+  {
+    crate::process::set_process_nice(0, value)
+      .map_err(|err| sys::priority_error(err, "set the priority"))
+  }
+  #[cfg(windows)]
+  {
+    set_current_thread_nice(value)
+  }
+}
+
+#[napi]
+/// This function adds `delta` to the nice value of the current process, and returns the new nice value. A higher nice value means a lower priority.
+/// The result is clamped to `-20..19`.
+///
+/// On Unix, it reads the nice value with [`getpriority(2)`](https://linux.die.net/man/2/getpriority) and applies the new one with [`setpriority(2)`](https://linux.die.net/man/2/setpriority).
+///
+/// On Windows, the calling thread priority is translated into a nice value, adjusted, and mapped back onto the nearest `WindowsThreadPriority`.
+/// Because there are only 7 thread priorities, small deltas may not change anything.
+pub fn adjust_priority(delta: i32) -> Result<i32> {
+  crate::stats::record("adjustPriority", || apply_priority_adjustment(delta))
+}
+
+pub(crate) fn apply_priority_adjustment(delta: i32) -> Result<i32> {
+  #[cfg(unix)]
+  let current = sys::getpriority(libc::PRIO_PROCESS, 0)
+    .map_err(|err| sys::priority_error(err, "get the priority"))?;
+  #[cfg(windows)]
+  let current = current_thread_nice()?;
+  let value = current.saturating_add(delta).clamp(-20, 19);
+  apply_absolute_priority(value)?;
+  #[cfg(unix)]
+  {
+    Ok(value)
+  }
+  #[cfg(windows)]
+  {
+    current_thread_nice()
+  }
+}
+
+#[napi]
+/// This function set the lowest priority of the current process, and returns the new nice value.
+///
+/// On Unix, it sets the nice value `19`. On Windows, it sets the `Idle` priority class.
+pub fn set_lowest_possible() -> Result<i32> {
+  crate::stats::record("setLowestPossible", || {
+    process::apply_priority(0, 19)?;
+    process::get_priority(0)
+  })
+}
+
+#[cfg(unix)]
+/// The highest priority an unprivileged process may set, which is limited by `RLIMIT_NICE` on Linux and is the current priority elsewhere.
+fn unprivileged_highest_nice(current: i32) -> Result<i32> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let mut limit: libc::rlimit = unsafe { std::mem::zeroed() };
+    if unsafe { libc::getrlimit(libc::RLIMIT_NICE, &mut limit) } == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get RLIMIT_NICE",
+      ));
+    }
+    // the nice value can be lowered down to `20 - rlim_cur`
+    let floor = if limit.rlim_cur == libc::RLIM_INFINITY {
+      -20
+    } else {
+      20 - limit.rlim_cur.min(40) as i32
+    };
+    Ok(current.min(floor))
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    Ok(current)
+  }
+}
+
+#[napi]
+/// This function set the highest priority the current process is allowed to set, and returns the new nice value.
+///
+/// On Unix, it sets the nice value `-20` when the process has root or CAP_SYS_NICE. Otherwise it sets the lowest nice value allowed by `RLIMIT_NICE` on Linux, and keeps the current priority elsewhere.
+///
+/// On Windows, it sets the `High` priority class. `Realtime` is never used, because it can starve the system and silently falls back to `High` without the required privilege anyway.
+pub fn set_highest_allowed() -> Result<i32> {
+  crate::stats::record("setHighestAllowed", || {
+    #[cfg(unix)]
+    {
+      match crate::process::set_process_nice(0, -20) {
+        Ok(()) => return Ok(-20),
+        Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => {}
+        Err(err) => return Err(sys::priority_error(err, "set the priority")),
+      }
+      let current = sys::getpriority(libc::PRIO_PROCESS, 0)
+        .map_err(|err| sys::priority_error(err, "get the priority"))?;
+      let value = unprivileged_highest_nice(current)?;
+      if value != current {
+        crate::process::set_process_nice(0, value)
+          .map_err(|err| sys::priority_error(err, "set the priority"))?;
+      }
+      Ok(value)
+    }
+    #[cfg(windows)]
+    {
+      process::apply_priority(0, process::ProcessPriorityClass::High.to_nice())?;
+      process::get_priority(0)
+    }
+  })
+}
+
+#[napi]
+/// This function adds `delta` to the nice value of the current process like `adjustPriority`, and returns the nice values before and after the change.
+///
+/// On Unix, the result is clamped to `-20..19`.
+///
+/// On Windows, there is no relative adjustment. The calling thread priority is translated into a nice value, and the new priority is picked from the ordered `WindowsThreadPriority` lattice (see `getWindowsThreadPriorityMapping`).
+/// Unlike `adjustPriority`, a non-zero `delta` always moves at least one step, so repeated small adjustments are not lost.
+pub fn renice_by(delta: i32) -> Result<PriorityChange> {
+  crate::stats::record("reniceBy", || {
+    #[cfg(unix)]
+    {
+      let previous = sys::getpriority(libc::PRIO_PROCESS, 0)
+        .map_err(|err| sys::priority_error(err, "get the priority"))?;
+      let current = previous.saturating_add(delta).clamp(-20, 19);
+      apply_absolute_priority(current)?;
+      Ok(PriorityChange { previous, current })
+    }
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{GetCurrentThread, SetThreadPriority};
+
+      let previous = current_thread_nice()?;
+      let priority = mapping::renice_thread_priority(previous, delta);
+      unsafe { SetThreadPriority(GetCurrentThread(), priority.into()) }
+        .map_err(|err| sys::priority_error(sys::win32_error(err), "set the priority"))?;
+      Ok(PriorityChange {
+        previous,
+        current: current_thread_nice()?,
+      })
+    }
+  })
+}
+
 #[napi]
 /// This function get the priority of the current process.
 /// On Unix, it uses the [`getpriority(2)`](https://linux.die.net/man/2/getpriority).
@@ -122,65 +526,11 @@ pub fn nice(incr: Option<i32>) -> Result<i32> {
 /// | THREAD_PRIORITY_TIME_CRITICAL      | 15        | Base priority of 15 for IDLE_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, ABOVE_NORMAL_PRIORITY_CLASS, or HIGH_PRIORITY_CLASS processes, and a base priority of 31 for REALTIME_PRIORITY_CLASS processes.     |
 pub fn get_current_process_priority() -> Result<i32> {
   #[cfg(unix)]
-  unsafe {
-    extern "C" {
-      #[cfg(not(any(target_os = "dragonfly", target_os = "vxworks")))]
-      #[cfg_attr(
-        any(
-          target_os = "linux",
-          target_os = "emscripten",
-          target_os = "fuchsia",
-          target_os = "l4re",
-          target_os = "hurd",
-        ),
-        link_name = "__errno_location"
-      )]
-      #[cfg_attr(
-        any(
-          target_os = "netbsd",
-          target_os = "openbsd",
-          target_os = "android",
-          target_os = "redox",
-          target_env = "newlib"
-        ),
-        link_name = "__errno"
-      )]
-      #[cfg_attr(
-        any(target_os = "solaris", target_os = "illumos"),
-        link_name = "___errno"
-      )]
-      #[cfg_attr(target_os = "nto", link_name = "__get_errno_ptr")]
-      #[cfg_attr(
-        any(target_os = "freebsd", target_vendor = "apple"),
-        link_name = "__error"
-      )]
-      #[cfg_attr(target_os = "haiku", link_name = "_errnop")]
-      #[cfg_attr(target_os = "aix", link_name = "_Errno")]
-      fn errno_location() -> *mut core::ffi::c_int;
-    }
-    // clear the last error
-    *errno_location() = 0;
-    let ret = libc::getpriority(libc::PRIO_PROCESS, 0);
-    // recheck the os error
-    let os_error = std::io::Error::last_os_error();
-    if let Some(err) = os_error.raw_os_error() {
-      if err != 0 {
-        return Err(os_error.into());
-      }
-    };
-    Ok(ret)
+  {
+    Ok(sys::getpriority(libc::PRIO_PROCESS, 0)?)
   }
   #[cfg(windows)]
   {
-    use windows::Win32::System::Threading::{GetCurrentThread, GetThreadPriority};
-    use windows::Win32::System::WindowsProgramming::THREAD_PRIORITY_ERROR_RETURN;
-
-    let ret = unsafe { GetThreadPriority(GetCurrentThread()) };
-
-    if ret == THREAD_PRIORITY_ERROR_RETURN as i32 {
-      return Err(std::io::Error::last_os_error().into());
-    }
-
-    Ok(ret)
+    Ok(sys::current_thread_priority()?)
   }
 }
@@ -0,0 +1,119 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::WindowsThreadPriority;
+
+#[napi(object)]
+/// One row of the mapping between nice values and `WindowsThreadPriority`.
+pub struct WindowsThreadPriorityMapping {
+  pub windows_thread_priority: WindowsThreadPriority,
+  /// The nice value `windowsThreadPriority` is translated to.
+  pub nice: i32,
+  /// The lowest nice value translated to `windowsThreadPriority`.
+  pub min_nice: i32,
+  /// The highest nice value translated to `windowsThreadPriority`.
+  pub max_nice: i32,
+}
+
+/// `(priority, nice, min_nice, max_nice)`, ordered from the highest to the lowest priority.
+const MAPPING: [(WindowsThreadPriority, i32, i32, i32); 7] = [
+  (
+    WindowsThreadPriority::ThreadPriorityTimeCritical,
+    -20,
+    -20,
+    -15,
+  ),
+  (WindowsThreadPriority::ThreadPriorityHighest, -10, -14, -8),
+  (WindowsThreadPriority::ThreadPriorityAboveNormal, -5, -7, -3),
+  (WindowsThreadPriority::ThreadPriorityNormal, 0, -2, 2),
+  (WindowsThreadPriority::ThreadPriorityBelowNormal, 5, 3, 7),
+  (WindowsThreadPriority::ThreadPriorityLowest, 10, 8, 14),
+  (WindowsThreadPriority::ThreadPriorityIdle, 19, 15, 19),
+];
+
+pub(crate) fn nice_to_thread_priority(value: i32) -> WindowsThreadPriority {
+  let value = value.clamp(-20, 19);
+  MAPPING
+    .iter()
+    .find(|(_, _, min, max)| (*min..=*max).contains(&value))
+    .map(|(priority, ..)| *priority)
+    .unwrap_or(WindowsThreadPriority::ThreadPriorityNormal)
+}
+
+pub(crate) fn thread_priority_to_nice(priority: WindowsThreadPriority) -> Option<i32> {
+  MAPPING
+    .iter()
+    .find(|(p, ..)| *p as i32 == priority as i32)
+    .map(|(_, nice, ..)| *nice)
+}
+
+#[cfg(windows)]
+/// Translates a raw `GetThreadPriority` result into a nice value.
+/// Threads of `REALTIME_PRIORITY_CLASS` processes can have priorities between the named ones, they are mapped onto the nearest row.
+pub(crate) fn thread_priority_value_to_nice(value: i32) -> i32 {
+  match value {
+    15.. => -20,
+    2..=14 => -10,
+    1 => -5,
+    0 => 0,
+    -1 => 5,
+    -14..=-2 => 10,
+    _ => 19,
+  }
+}
+
+#[cfg(windows)]
+/// Moves `delta` nice values away from `value` along the table, by at least one row when `delta` is not `0`.
+pub(crate) fn renice_thread_priority(value: i32, delta: i32) -> WindowsThreadPriority {
+  let row = |value: i32| {
+    let value = value.clamp(-20, 19);
+    MAPPING
+      .iter()
+      .position(|(_, _, min, max)| (*min..=*max).contains(&value))
+      .unwrap_or(3)
+  };
+  let current = row(value);
+  let mut target = row(value.saturating_add(delta));
+  if target == current && delta > 0 {
+    target = (current + 1).min(MAPPING.len() - 1);
+  } else if target == current && delta < 0 {
+    target = current.saturating_sub(1);
+  }
+  MAPPING[target].0
+}
+
+#[napi]
+/// This function translate a nice value into the nearest `WindowsThreadPriority`.
+/// Values outside of `-20..19` are clamped first.
+pub fn nice_to_windows_thread_priority(value: i32) -> WindowsThreadPriority {
+  nice_to_thread_priority(value)
+}
+
+#[napi]
+/// This function translate a `WindowsThreadPriority` into a nice value.
+/// `ThreadModeBackgroundBegin` and `ThreadModeBackgroundEnd` are modes rather than priorities, they have no nice equivalent and throw.
+pub fn windows_thread_priority_to_nice(priority: i32) -> Result<i32> {
+  let priority: WindowsThreadPriority = priority.try_into()?;
+  thread_priority_to_nice(priority).ok_or_else(|| {
+    Error::new(
+      Status::InvalidArg,
+      format!("{} has no nice equivalent", priority as i32),
+    )
+  })
+}
+
+#[napi]
+/// This function returns the table used by `niceToWindowsThreadPriority` and `windowsThreadPriorityToNice`, ordered from the highest to the lowest priority.
+pub fn get_windows_thread_priority_mapping() -> Vec<WindowsThreadPriorityMapping> {
+  MAPPING
+    .iter()
+    .map(
+      |&(windows_thread_priority, nice, min_nice, max_nice)| WindowsThreadPriorityMapping {
+        windows_thread_priority,
+        nice,
+        min_nice,
+        max_nice,
+      },
+    )
+    .collect()
+}
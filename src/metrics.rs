@@ -0,0 +1,155 @@
+use std::fmt::{Debug, Write};
+
+use napi::Result;
+use napi_derive::napi;
+
+/// Appends a metric with its `HELP` and `TYPE` lines, it's skipped when `value` is `None`.
+fn gauge(text: &mut String, name: &str, help: &str, value: Option<f64>) {
+  if let Some(value) = value {
+    let _ = write!(
+      text,
+      "# HELP nice_{name} {help}\n# TYPE nice_{name} gauge\nnice_{name} {value}\n"
+    );
+  }
+}
+
+/// Appends an enum as a gauge set to `1` with the name of the variant in the `label` label, like the `_info` metrics of Prometheus exporters.
+fn variant(text: &mut String, name: &str, help: &str, label: &str, value: Option<impl Debug>) {
+  if let Some(value) = value {
+    let _ = write!(
+      text,
+      "# HELP nice_{name} {help}\n# TYPE nice_{name} gauge\nnice_{name}{{{label}=\"{value:?}\"}} 1\n"
+    );
+  }
+}
+
+#[napi]
+/// This function renders the scheduling state of the current process and the calling thread, and the number of successful and failed calls to the functions changing priorities, in the [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/), so it can be served on a `/metrics` endpoint as is.
+///
+/// The scheduling state comes from `getSchedulingSnapshot`, and the metrics of the fields which are `null` on the current platform are omitted.
+/// The call counters are the same as `getStats`, they're cumulative since the addon was loaded or `resetStats` was called, and cover the same functions.
+pub fn get_metrics_text() -> Result<String> {
+  let snapshot = crate::snapshot::get_scheduling_snapshot()?;
+  let mut text = String::new();
+  let _ = writeln!(
+    text,
+    "# HELP nice_info The platform, process and thread the metrics describe.\n# TYPE nice_info gauge\nnice_info{{platform=\"{}\",pid=\"{}\",tid=\"{}\"}} 1",
+    snapshot.platform,
+    snapshot.pid,
+    snapshot.tid.map(|tid| tid.to_string()).unwrap_or_default(),
+  );
+  gauge(
+    &mut text,
+    "value",
+    "The nice value of the calling thread.",
+    Some(f64::from(snapshot.nice)),
+  );
+  variant(
+    &mut text,
+    "priority_class",
+    "The priority class of the process.",
+    "class",
+    snapshot.priority_class,
+  );
+  variant(
+    &mut text,
+    "scheduler_policy",
+    "The scheduling policy of the calling thread.",
+    "policy",
+    snapshot.policy,
+  );
+  gauge(
+    &mut text,
+    "rt_priority",
+    "The real-time priority of the calling thread.",
+    snapshot.rt_priority.map(f64::from),
+  );
+  gauge(
+    &mut text,
+    "reset_on_fork",
+    "Whether SCHED_RESET_ON_FORK is set for the calling thread.",
+    snapshot
+      .reset_on_fork
+      .map(|reset_on_fork| f64::from(u8::from(reset_on_fork))),
+  );
+  gauge(
+    &mut text,
+    "latency_nice",
+    "The latency nice value of the calling thread.",
+    snapshot.latency_nice.map(f64::from),
+  );
+  gauge(
+    &mut text,
+    "affinity_cpus",
+    "The number of CPUs the calling thread may run on.",
+    snapshot.affinity.map(|affinity| affinity.len() as f64),
+  );
+  variant(
+    &mut text,
+    "io_class",
+    "The I/O scheduling class of the calling thread.",
+    "class",
+    snapshot.io_class,
+  );
+  gauge(
+    &mut text,
+    "io_level",
+    "The level within the I/O scheduling class of the calling thread.",
+    snapshot.io_level.map(f64::from),
+  );
+  variant(
+    &mut text,
+    "windows_io_priority",
+    "The I/O priority of the process.",
+    "priority",
+    snapshot.windows_io_priority,
+  );
+  gauge(
+    &mut text,
+    "memory_priority",
+    "The memory priority of the process.",
+    snapshot.memory_priority.map(f64::from),
+  );
+  gauge(
+    &mut text,
+    "oom_score_adj",
+    "The OOM score adjustment of the process.",
+    snapshot.oom_score_adj.map(f64::from),
+  );
+  variant(
+    &mut text,
+    "qos_class",
+    "The QoS class of the calling thread.",
+    "class",
+    snapshot.qos_class,
+  );
+  gauge(
+    &mut text,
+    "cgroup_cpu_weight",
+    "The CPU weight of the cgroup of the process.",
+    snapshot.cgroup_cpu_weight.map(f64::from),
+  );
+  gauge(
+    &mut text,
+    "rlimit_nice",
+    "The soft RLIMIT_NICE of the process.",
+    snapshot.rlimit_nice,
+  );
+  gauge(
+    &mut text,
+    "rlimit_rtprio",
+    "The soft RLIMIT_RTPRIO of the process.",
+    snapshot.rlimit_rtprio,
+  );
+  text.push_str("# HELP nice_calls_total The number of calls to the functions changing priorities.\n# TYPE nice_calls_total counter\n");
+  for stats in crate::stats::get_stats() {
+    for (result, count) in [("success", stats.successes), ("error", stats.failures)] {
+      let _ = writeln!(
+        text,
+        "nice_calls_total{{function=\"{}\",result=\"{result}\"}} {count}",
+        stats.function
+      );
+    }
+  }
+  Ok(text)
+}
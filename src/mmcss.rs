@@ -0,0 +1,127 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// The priority of a thread relative to the other threads of the same MMCSS task, the values are the `AVRT_PRIORITY_*` constants.
+pub enum MmcssPriority {
+  VeryLow = -2,
+  Low = -1,
+  Normal = 0,
+  High = 1,
+  Critical = 2,
+}
+
+#[napi]
+/// Registers the calling thread with the [Multimedia Class Scheduler Service](https://learn.microsoft.com/en-us/windows/win32/procthread/multimedia-class-scheduler-service), which boosts audio and video threads without starving the system the way `ThreadPriorityTimeCritical` does.
+/// The registration lasts until `revert()` is called or the object is garbage collected, and it must be created on the thread to register, such as a worker thread.
+///
+/// It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+///
+/// On Windows, it uses the [`AvSetMmThreadCharacteristicsW`](https://learn.microsoft.com/en-us/windows/win32/api/avrt/nf-avrt-avsetmmthreadcharacteristicsw), [`AvSetMmThreadPriority`](https://learn.microsoft.com/en-us/windows/win32/api/avrt/nf-avrt-avsetmmthreadpriority) and [`AvRevertMmThreadCharacteristics`](https://learn.microsoft.com/en-us/windows/win32/api/avrt/nf-avrt-avrevertmmthreadcharacteristics) functions.
+///
+/// On other platforms, the constructor throws.
+pub struct MmcssTask {
+  /// The MMCSS task handle, `None` once reverted.
+  handle: Option<usize>,
+  task_index: u32,
+}
+
+#[napi]
+impl MmcssTask {
+  #[napi(constructor)]
+  /// `taskName` is one of the tasks under `HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks`, such as `Audio`, `Capture`, `Games`, `Playback` or `Pro Audio`, it defaults to `Pro Audio`.
+  pub fn new(task_name: Option<String>) -> Result<Self> {
+    crate::stats::record("MmcssTask", || {
+      let task_name = task_name.as_deref().unwrap_or("Pro Audio");
+      #[cfg(windows)]
+      {
+        use windows::core::HSTRING;
+        use windows::Win32::System::Threading::AvSetMmThreadCharacteristicsW;
+
+        let mut task_index = 0;
+        let handle =
+          unsafe { AvSetMmThreadCharacteristicsW(&HSTRING::from(task_name), &mut task_index) }
+            .map_err(|err| {
+              sys::priority_error(
+                sys::win32_error(err),
+                &format!("register the thread with the MMCSS task {task_name}"),
+              )
+            })?;
+        Ok(Self {
+          handle: Some(handle.0 as usize),
+          task_index,
+        })
+      }
+      #[cfg(not(windows))]
+      {
+        let _ = task_name;
+        Err(sys::unsupported("MMCSS"))
+      }
+    })
+  }
+
+  #[napi]
+  /// Sets the priority of the thread relative to the other threads of the same task.
+  pub fn set_priority(&self, priority: MmcssPriority) -> Result<()> {
+    crate::stats::record("MmcssTask.setPriority", || {
+      let Some(handle) = self.handle else {
+        return Err(Error::new(
+          Status::GenericFailure,
+          "The MMCSS task has already been reverted",
+        ));
+      };
+      #[cfg(windows)]
+      {
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::Threading::{AvSetMmThreadPriority, AVRT_PRIORITY};
+
+        unsafe { AvSetMmThreadPriority(HANDLE(handle as _), AVRT_PRIORITY(priority as i32)) }
+          .map_err(|err| sys::priority_error(sys::win32_error(err), "set the MMCSS priority"))
+      }
+      #[cfg(not(windows))]
+      {
+        let _ = (handle, priority);
+        Ok(())
+      }
+    })
+  }
+
+  #[napi]
+  /// Unregisters the thread from the task, calling it more than once has no effect.
+  pub fn revert(&mut self) -> Result<()> {
+    if let Some(handle) = self.handle.take() {
+      #[cfg(windows)]
+      {
+        use windows::Win32::Foundation::HANDLE;
+        use windows::Win32::System::Threading::AvRevertMmThreadCharacteristics;
+
+        unsafe { AvRevertMmThreadCharacteristics(HANDLE(handle as _)) }
+          .map_err(|err| sys::priority_error(sys::win32_error(err), "revert the MMCSS task"))?;
+      }
+      #[cfg(not(windows))]
+      let _ = handle;
+    }
+    Ok(())
+  }
+
+  #[napi(getter)]
+  /// The index of the task, which identifies the registration to MMCSS.
+  pub fn task_index(&self) -> u32 {
+    self.task_index
+  }
+
+  #[napi(getter)]
+  /// Whether `revert()` has already been called.
+  pub fn reverted(&self) -> bool {
+    self.handle.is_none()
+  }
+}
+
+impl Drop for MmcssTask {
+  fn drop(&mut self) {
+    let _ = self.revert();
+  }
+}
@@ -0,0 +1,117 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi(object)]
+pub struct NumaOptions {
+  /// Whether memory may only be allocated from the node, by default the node is preferred and other nodes are used when it is full.
+  /// Only Linux supports it.
+  pub strict: Option<bool>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const MPOL_PREFERRED: libc::c_int = 1;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const MPOL_BIND: libc::c_int = 2;
+
+fn unknown_node(node: u32) -> Error {
+  Error::new(
+    Status::InvalidArg,
+    format!("{node} is not a valid NUMA node"),
+  )
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Lists the CPUs of the NUMA node `node`.
+fn numa_node_cpus(node: u32) -> Result<Vec<u32>> {
+  let path = format!("/sys/devices/system/node/node{node}/cpulist");
+  let list = match std::fs::read_to_string(&path) {
+    Ok(list) => list,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Err(unknown_node(node)),
+    Err(err) => return Err(sys::priority_error(err, &format!("read {path}"))),
+  };
+  crate::affinity::try_parse_cpu_list(&list).ok_or_else(|| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to read {path}: unexpected content"),
+    )
+  })
+}
+
+#[napi]
+/// This function keeps the calling thread on the NUMA node `node`, both its CPUs and its memory, so memory-bound workers stay NUMA-local.
+///
+/// On Linux, it restricts the thread to the CPUs of `/sys/devices/system/node/node{node}/cpulist` with [`sched_setaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_setaffinity.2.html), and sets its memory policy with [`set_mempolicy(2)`](https://man7.org/linux/man-pages/man2/set_mempolicy.2.html), `MPOL_PREFERRED` or `MPOL_BIND` when `strict` is set.
+///
+/// On Windows, it restricts the thread to the CPUs of the node with the [`GetNumaNodeProcessorMaskEx`](https://learn.microsoft.com/en-us/windows/win32/api/systemtopologyapi/nf-systemtopologyapi-getnumanodeprocessormaskex) and [`SetThreadGroupAffinity`](https://learn.microsoft.com/en-us/windows/win32/api/processtopologyapi/nf-processtopologyapi-setthreadgroupaffinity) functions.
+/// Windows allocates memory from the node of the CPU the thread runs on by default, use [`VirtualAllocExNuma`](https://learn.microsoft.com/en-us/windows/win32/api/memoryapi/nf-memoryapi-virtualallocexnuma) for explicit placement.
+///
+/// On other platforms, it throws.
+pub fn set_numa_node(node: u32, options: Option<NumaOptions>) -> Result<()> {
+  crate::stats::record("setNumaNode", || {
+    let strict = options.and_then(|options| options.strict).unwrap_or(false);
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      let cpus = numa_node_cpus(node)?;
+      if cpus.is_empty() {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("NUMA node {node} has no CPUs"),
+        ));
+      }
+      crate::affinity::sched_setaffinity(0, &cpus)
+        .map_err(|err| sys::priority_error(err, "set the CPU affinity"))?;
+      let bits = libc::c_ulong::BITS;
+      let mut nodemask = vec![0 as libc::c_ulong; (node / bits + 1) as usize];
+      nodemask[(node / bits) as usize] |= 1 << (node % bits);
+      let mode = if strict { MPOL_BIND } else { MPOL_PREFERRED };
+      // the kernel ignores the last bit of `maxnode`
+      let maxnode = (nodemask.len() as u32 * bits + 1) as libc::c_ulong;
+      if unsafe { libc::syscall(libc::SYS_set_mempolicy, mode, nodemask.as_ptr(), maxnode) } == -1 {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "set the memory policy",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::SystemInformation::GROUP_AFFINITY;
+      use windows::Win32::System::Threading::{
+        GetCurrentThread, GetNumaNodeProcessorMaskEx, SetThreadGroupAffinity,
+      };
+
+      let _ = strict;
+      let node = u16::try_from(node).map_err(|_| unknown_node(node))?;
+      let mut affinity = GROUP_AFFINITY::default();
+      if !unsafe { GetNumaNodeProcessorMaskEx(node, &mut affinity) }.as_bool() {
+        let err = std::io::Error::last_os_error();
+        if err.raw_os_error() == Some(windows::Win32::Foundation::ERROR_INVALID_PARAMETER.0 as i32)
+        {
+          return Err(unknown_node(node as u32));
+        }
+        return Err(sys::priority_error(err, "get the CPUs of the NUMA node"));
+      }
+      if affinity.Mask == 0 {
+        return Err(Error::new(
+          Status::InvalidArg,
+          format!("NUMA node {node} has no CPUs"),
+        ));
+      }
+      if !unsafe { SetThreadGroupAffinity(GetCurrentThread(), &affinity, None) }.as_bool() {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "set the group affinity",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+    {
+      let _ = (node, strict, unknown_node);
+      Err(sys::unsupported("Setting the NUMA node"))
+    }
+  })
+}
@@ -0,0 +1,42 @@
+//! A minimal bridge to the Objective-C runtime, for the few Foundation APIs without a C equivalent.
+
+use std::ffi::{c_char, c_void};
+
+pub(crate) type Id = *mut c_void;
+pub(crate) type Sel = *mut c_void;
+
+#[link(name = "objc")]
+extern "C" {
+  pub(crate) fn objc_getClass(name: *const c_char) -> Id;
+  pub(crate) fn sel_registerName(name: *const c_char) -> Sel;
+  pub(crate) fn objc_msgSend();
+  fn objc_autoreleasePoolPush() -> *mut c_void;
+  fn objc_autoreleasePoolPop(pool: *mut c_void);
+}
+
+// `NSProcessInfo` lives in Foundation
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {}
+
+/// Sends `selector` to `receiver`, `objc_msgSend` must be called through a pointer of the exact method signature, it's not variadic on arm64.
+macro_rules! msg_send {
+  ($receiver:expr, $selector:expr $(, $arg:expr => $ty:ty)* ; $ret:ty) => {{
+    let send: unsafe extern "C" fn($crate::objc::Id, $crate::objc::Sel $(, $ty)*) -> $ret =
+      std::mem::transmute($crate::objc::objc_msgSend as unsafe extern "C" fn());
+    send($receiver, $crate::objc::sel_registerName($selector.as_ptr()) $(, $arg)*)
+  }};
+}
+pub(crate) use msg_send;
+
+/// Runs `f` inside an autorelease pool, so autoreleased objects don't leak on threads without a run loop.
+pub(crate) fn with_autorelease_pool<T>(f: impl FnOnce() -> T) -> T {
+  let pool = unsafe { objc_autoreleasePoolPush() };
+  let result = f();
+  unsafe { objc_autoreleasePoolPop(pool) };
+  result
+}
+
+/// `[NSProcessInfo processInfo]`
+pub(crate) unsafe fn process_info() -> Id {
+  msg_send!(objc_getClass(c"NSProcessInfo".as_ptr()), c"processInfo"; Id)
+}
@@ -0,0 +1,102 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Returns the path of the `/proc` file `name` of the process identified by `pid`, the current process when it's `None`.
+pub(crate) fn proc_file(pid: Option<u32>, name: &str) -> String {
+  match pid {
+    Some(pid) => format!("/proc/{pid}/{name}"),
+    None => format!("/proc/self/{name}"),
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_proc_number(path: &str) -> Result<i32> {
+  let content = std::fs::read_to_string(path)
+    .map_err(|err| sys::priority_error(err, &format!("read {path}")))?;
+  content.trim().parse().map_err(|_| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to read {path}: unexpected content"),
+    )
+  })
+}
+
+#[napi]
+/// This function set the OOM score adjustment of the process identified by `pid`, it defaults to the current process.
+/// `value` ranges from `-1000`, which never kills the process, to `1000`, which kills it first when the system runs out of memory.
+///
+/// On Linux, it writes [`/proc/<pid>/oom_score_adj`](https://man7.org/linux/man-pages/man5/proc_pid_oom_score_adj.5.html).
+/// Lowering the value below its previous minimum requires root or `CAP_SYS_RESOURCE`.
+///
+/// On other platforms, it throws.
+pub fn set_oom_score_adj(value: i32, pid: Option<u32>) -> Result<()> {
+  crate::stats::record("setOomScoreAdj", || {
+    if !(-1000..=1000).contains(&value) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!(
+          "{value} is not a valid OOM score adjustment, expected a value between -1000 and 1000"
+        ),
+      ));
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      let path = proc_file(pid, "oom_score_adj");
+      std::fs::write(&path, value.to_string()).map_err(|err| {
+        if err.kind() == std::io::ErrorKind::PermissionDenied {
+          Error::new(
+            Status::GenericFailure,
+            format!("Permission denied to write {path}, lowering the OOM score adjustment requires root or CAP_SYS_RESOURCE: {err}"),
+          )
+        } else {
+          sys::priority_error(err, &format!("write {path}"))
+        }
+      })
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let _ = pid;
+      Err(sys::unsupported("Setting the OOM score adjustment"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the OOM score adjustment of the process identified by `pid`, it defaults to the current process.
+///
+/// On Linux, it reads [`/proc/<pid>/oom_score_adj`](https://man7.org/linux/man-pages/man5/proc_pid_oom_score_adj.5.html).
+///
+/// On other platforms, it throws.
+pub fn get_oom_score_adj(pid: Option<u32>) -> Result<i32> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    read_proc_number(&proc_file(pid, "oom_score_adj"))
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("Getting the OOM score adjustment"))
+  }
+}
+
+#[napi]
+/// This function get the OOM score of the process identified by `pid`, it defaults to the current process.
+/// The process with the highest score is killed first when the system runs out of memory, the score ranges from `0` to `2000` and includes the OOM score adjustment.
+///
+/// On Linux, it reads [`/proc/<pid>/oom_score`](https://man7.org/linux/man-pages/man5/proc_pid_oom_score.5.html).
+///
+/// On other platforms, it throws.
+pub fn get_oom_score(pid: Option<u32>) -> Result<u32> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    read_proc_number(&proc_file(pid, "oom_score")).map(|score| score.max(0) as u32)
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("Getting the OOM score"))
+  }
+}
@@ -0,0 +1,165 @@
+use napi::bindgen_prelude::Function;
+use napi::Result;
+use napi_derive::napi;
+
+use crate::sys;
+use crate::watch::{spawn_watcher, WatchOptions, Watcher};
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Where the machine gets its power from.
+pub enum PowerSource {
+  /// Mains power, also used for machines without batteries.
+  Ac = 0,
+  /// A battery, including the battery of a UPS.
+  Battery = 1,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_power_source() -> std::io::Result<PowerSource> {
+  let supplies = match std::fs::read_dir("/sys/class/power_supply") {
+    Ok(supplies) => supplies,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(PowerSource::Ac),
+    Err(err) => return Err(err),
+  };
+  let read = |supply: &std::path::Path, name: &str| {
+    std::fs::read_to_string(supply.join(name))
+      .map(|value| value.trim().to_owned())
+      .unwrap_or_default()
+  };
+  let mut has_battery = false;
+  for supply in supplies {
+    let supply = supply?.path();
+    match read(&supply, "type").as_str() {
+      "Battery" => {
+        // peripherals such as mice report their own batteries
+        has_battery |= read(&supply, "scope") != "Device" && read(&supply, "present") != "0";
+      }
+      // `Mains`, `USB`, `USB_C`, ...
+      _ => {
+        if read(&supply, "online") == "1" {
+          return Ok(PowerSource::Ac);
+        }
+      }
+    }
+  }
+  Ok(if has_battery {
+    PowerSource::Battery
+  } else {
+    PowerSource::Ac
+  })
+}
+
+#[cfg(target_vendor = "apple")]
+fn read_power_source() -> std::io::Result<PowerSource> {
+  use std::ffi::{c_char, c_void, CStr};
+
+  #[link(name = "IOKit", kind = "framework")]
+  extern "C" {
+    fn IOPSCopyPowerSourcesInfo() -> *const c_void;
+    fn IOPSGetProvidingPowerSourceType(snapshot: *const c_void) -> *const c_void;
+  }
+  #[link(name = "CoreFoundation", kind = "framework")]
+  extern "C" {
+    fn CFRelease(cf: *const c_void);
+    fn CFStringGetCString(
+      string: *const c_void,
+      buffer: *mut c_char,
+      size: isize,
+      encoding: u32,
+    ) -> u8;
+  }
+  const K_CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+
+  let snapshot = unsafe { IOPSCopyPowerSourcesInfo() };
+  if snapshot.is_null() {
+    return Err(std::io::Error::other("IOPSCopyPowerSourcesInfo failed"));
+  }
+  // `kIOPMACPowerKey`, `kIOPMBatteryPowerKey` or `kIOPMUPSPowerKey`
+  let mut buffer = [0 as c_char; 32];
+  let source_type = unsafe { IOPSGetProvidingPowerSourceType(snapshot) };
+  let ok = !source_type.is_null()
+    && unsafe {
+      CFStringGetCString(
+        source_type,
+        buffer.as_mut_ptr(),
+        buffer.len() as isize,
+        K_CF_STRING_ENCODING_UTF8,
+      )
+    } != 0;
+  unsafe { CFRelease(snapshot) };
+  if !ok {
+    return Err(std::io::Error::other(
+      "IOPSGetProvidingPowerSourceType failed",
+    ));
+  }
+  Ok(
+    match unsafe { CStr::from_ptr(buffer.as_ptr()) }.to_bytes() {
+      b"AC Power" => PowerSource::Ac,
+      _ => PowerSource::Battery,
+    },
+  )
+}
+
+#[cfg(windows)]
+fn read_power_source() -> std::io::Result<PowerSource> {
+  use windows::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+  let mut status = SYSTEM_POWER_STATUS::default();
+  unsafe { GetSystemPowerStatus(&mut status) }.map_err(sys::win32_error)?;
+  // `255` means unknown, which is treated as AC
+  Ok(if status.ACLineStatus == 0 {
+    PowerSource::Battery
+  } else {
+    PowerSource::Ac
+  })
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  target_vendor = "apple",
+  windows
+)))]
+fn read_power_source() -> std::io::Result<PowerSource> {
+  Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+}
+
+#[napi]
+/// This function returns where the machine gets its power from, so background services can lower their priority on battery.
+///
+/// On Linux, it reads `/sys/class/power_supply`, the source is `Battery` when the machine has a battery and no online mains or USB supply.
+///
+/// On macOS, it uses the [`IOPSGetProvidingPowerSourceType`](https://github.com/apple-oss-distributions/IOKitUser/blob/main/ps.subproj/IOPowerSources.h) function.
+///
+/// On Windows, it uses the [`GetSystemPowerStatus`](https://learn.microsoft.com/en-us/windows/win32/api/winbase/nf-winbase-getsystempowerstatus) function.
+///
+/// On other platforms, it throws.
+pub fn get_power_source() -> Result<PowerSource> {
+  read_power_source().map_err(|err| {
+    if err.kind() == std::io::ErrorKind::Unsupported {
+      sys::unsupported("Getting the power source")
+    } else {
+      sys::priority_error(err, "get the power source")
+    }
+  })
+}
+
+#[napi]
+/// This function calls `callback` with the new power source whenever `getPowerSource()` changes, checking it every `interval`.
+/// It polls on every platform instead of registering with [`RegisterPowerSettingNotification`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-registerpowersettingnotification) or `IOPSNotificationCreateRunLoopSource`, which need a window or a run loop, so a change is reported up to `interval` late.
+///
+/// On other platforms than Linux, macOS and Windows, it throws.
+pub fn on_power_source_change(
+  callback: Function<PowerSource, ()>,
+  options: Option<WatchOptions>,
+) -> Result<Watcher> {
+  let mut last = get_power_source()?;
+  spawn_watcher(callback, WatchOptions::interval(options)?, move || {
+    let source = read_power_source().ok()?;
+    (source != last).then(|| {
+      last = source;
+      source
+    })
+  })
+}
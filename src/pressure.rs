@@ -0,0 +1,316 @@
+use napi::bindgen_prelude::Function;
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+use crate::watch::Watcher;
+
+#[napi(object)]
+#[derive(Clone, Copy)]
+/// One line of a Linux [PSI](https://docs.kernel.org/accounting/psi.html) file.
+pub struct PressureStats {
+  /// The percentage of time stalled over the last 10 seconds.
+  pub avg10: f64,
+  /// The percentage of time stalled over the last 60 seconds.
+  pub avg60: f64,
+  /// The percentage of time stalled over the last 300 seconds.
+  pub avg300: f64,
+  /// The total stall time in microseconds.
+  pub total: f64,
+}
+
+#[napi(object)]
+pub struct CpuPressure {
+  /// The time at least one runnable task waited for a CPU.
+  pub some: PressureStats,
+  /// The time all non-idle tasks waited for a CPU at once, only reported since Linux 5.13.
+  pub full: Option<PressureStats>,
+}
+
+#[napi(object)]
+pub struct CpuPressureOptions {
+  /// The time window of the threshold in milliseconds, between `500` and `10000`, it defaults to `2000`.
+  /// Unprivileged processes can only use multiples of `2000`.
+  pub window: Option<u32>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const CPU_PRESSURE: &str = "/proc/pressure/cpu";
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Parses a line such as `some avg10=0.00 avg60=0.00 avg300=0.00 total=0`.
+fn parse_pressure_line(line: &str) -> Option<(&str, PressureStats)> {
+  let mut fields = line.split_whitespace();
+  let kind = fields.next()?;
+  let mut stats = PressureStats {
+    avg10: 0.0,
+    avg60: 0.0,
+    avg300: 0.0,
+    total: 0.0,
+  };
+  for field in fields {
+    let (key, value) = field.split_once('=')?;
+    let value = value.parse().ok()?;
+    match key {
+      "avg10" => stats.avg10 = value,
+      "avg60" => stats.avg60 = value,
+      "avg300" => stats.avg300 = value,
+      "total" => stats.total = value,
+      _ => {}
+    }
+  }
+  Some((kind, stats))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn parse_cpu_pressure(content: &str) -> Option<CpuPressure> {
+  let mut some = None;
+  let mut full = None;
+  for line in content.lines() {
+    match parse_pressure_line(line)? {
+      ("some", stats) => some = Some(stats),
+      ("full", stats) => full = Some(stats),
+      _ => {}
+    }
+  }
+  Some(CpuPressure { some: some?, full })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_cpu_pressure(file: &mut std::fs::File) -> Result<CpuPressure> {
+  use std::io::{Read, Seek};
+
+  let mut content = String::new();
+  file
+    .rewind()
+    .and_then(|_| file.read_to_string(&mut content))
+    .map_err(|err| sys::priority_error(err, &format!("read {CPU_PRESSURE}")))?;
+  parse_cpu_pressure(&content).ok_or_else(|| {
+    Error::new(
+      Status::GenericFailure,
+      format!("Failed to read {CPU_PRESSURE}: unexpected content"),
+    )
+  })
+}
+
+#[napi]
+/// This function returns how much the tasks of the system wait for a CPU, so adaptive schedulers can renice their workers when the system is overloaded.
+///
+/// On Linux, it reads [`/proc/pressure/cpu`](https://docs.kernel.org/accounting/psi.html), which requires a kernel built with `CONFIG_PSI`.
+///
+/// On other platforms, it throws.
+pub fn get_cpu_pressure() -> Result<CpuPressure> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let mut file = std::fs::File::open(CPU_PRESSURE)
+      .map_err(|err| sys::priority_error(err, &format!("open {CPU_PRESSURE}")))?;
+    read_cpu_pressure(&mut file)
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    Err(sys::unsupported("CPU pressure"))
+  }
+}
+
+#[napi]
+/// This function calls `callback` with the current CPU pressure whenever runnable tasks wait for a CPU more than `threshold` percent of `window`, at most once per window.
+///
+/// On Linux, it registers a [PSI trigger](https://docs.kernel.org/accounting/psi.html#monitoring-for-pressure-thresholds) on `/proc/pressure/cpu` and waits for it with `poll(2)`.
+/// Unprivileged triggers require Linux 6.5.
+///
+/// On other platforms, it throws.
+pub fn watch_cpu_pressure(
+  threshold: f64,
+  callback: Function<CpuPressure, ()>,
+  options: Option<CpuPressureOptions>,
+) -> Result<Watcher> {
+  let window = options.and_then(|options| options.window).unwrap_or(2000);
+  if !(threshold > 0.0 && threshold <= 100.0) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("{threshold} is not a valid threshold, expected a percentage between 0 and 100"),
+    ));
+  }
+  if !(500..=10_000).contains(&window) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "{window} is not a valid window, expected a duration between 500 and 10000 milliseconds"
+      ),
+    ));
+  }
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    use std::io::Write;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let window_us = u64::from(window) * 1000;
+    let stall_us = ((threshold / 100.0 * window_us as f64) as u64).max(1);
+    let mut file = std::fs::OpenOptions::new()
+      .read(true)
+      .write(true)
+      .custom_flags(libc::O_NONBLOCK)
+      .open(CPU_PRESSURE)
+      .map_err(|err| sys::priority_error(err, &format!("open {CPU_PRESSURE}")))?;
+    // the kernel expects the trigger to be NUL terminated
+    file
+      .write_all(format!("some {stall_us} {window_us}\0").as_bytes())
+      .map_err(|err| {
+        if err.raw_os_error() == Some(libc::EINVAL) && !window.is_multiple_of(2000) {
+          Error::new(
+            Status::InvalidArg,
+            format!("Failed to register the CPU pressure trigger, the window of unprivileged processes must be a multiple of 2000 milliseconds: {err}"),
+          )
+        } else {
+          sys::priority_error(err, "register the CPU pressure trigger")
+        }
+      })?;
+    crate::watch::spawn_watcher(callback, std::time::Duration::ZERO, move || {
+      let mut poll_fd = libc::pollfd {
+        fd: file.as_raw_fd(),
+        events: libc::POLLPRI,
+        revents: 0,
+      };
+      // wake up regularly to notice `stop()`
+      let ret = unsafe { libc::poll(&mut poll_fd, 1, 200) };
+      if ret <= 0 {
+        return None;
+      }
+      if poll_fd.revents & libc::POLLERR != 0 {
+        // the trigger is gone, for example because the cgroup was removed
+        std::thread::sleep(std::time::Duration::from_millis(window.into()));
+        return None;
+      }
+      read_cpu_pressure(&mut file).ok()
+    })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = callback;
+    Err(sys::unsupported("Watching the CPU pressure"))
+  }
+}
+
+#[napi(object)]
+pub struct SystemLoad {
+  /// The average number of runnable tasks over the last minute.
+  pub avg1: f64,
+  /// The average number of runnable tasks over the last 5 minutes.
+  pub avg5: f64,
+  /// The average number of runnable tasks over the last 15 minutes.
+  pub avg15: f64,
+  /// Whether the averages are emulated from the CPU utilization, which is the case on Windows.
+  pub emulated: bool,
+}
+
+#[cfg(windows)]
+const LOAD_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[cfg(windows)]
+/// The emulated 1, 5 and 15 minutes load averages, updated by a background thread started by the first `getSystemLoad` call.
+static EMULATED_LOAD: std::sync::OnceLock<std::sync::Mutex<[f64; 3]>> = std::sync::OnceLock::new();
+
+#[cfg(windows)]
+/// Returns the busy and the total CPU time of all the CPUs since boot, in 100 nanoseconds units.
+fn system_times() -> std::io::Result<(u64, u64)> {
+  use windows::Win32::Foundation::FILETIME;
+  use windows::Win32::System::Threading::GetSystemTimes;
+
+  let mut idle = FILETIME::default();
+  let mut kernel = FILETIME::default();
+  let mut user = FILETIME::default();
+  unsafe { GetSystemTimes(Some(&mut idle), Some(&mut kernel), Some(&mut user)) }
+    .map_err(sys::win32_error)?;
+  let value =
+    |time: FILETIME| (u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime);
+  // the kernel time includes the idle time
+  let total = value(kernel) + value(user);
+  Ok((total.saturating_sub(value(idle)), total))
+}
+
+#[cfg(windows)]
+fn emulated_load() -> std::io::Result<[f64; 3]> {
+  use windows::Win32::System::Threading::{GetActiveProcessorCount, ALL_PROCESSOR_GROUPS};
+
+  if let Some(load) = EMULATED_LOAD.get() {
+    return Ok(*load.lock().unwrap_or_else(|err| err.into_inner()));
+  }
+  let cpus = f64::from(unsafe { GetActiveProcessorCount(ALL_PROCESSOR_GROUPS) }.max(1));
+  let busy_cpus = move |(busy, total): (u64, u64)| {
+    if total == 0 {
+      0.0
+    } else {
+      busy as f64 / total as f64 * cpus
+    }
+  };
+  let mut previous = system_times()?;
+  let mut started = false;
+  // start from the utilization since boot, as if the averages had already converged
+  let load = EMULATED_LOAD.get_or_init(|| {
+    started = true;
+    std::sync::Mutex::new([busy_cpus(previous); 3])
+  });
+  if started {
+    std::thread::spawn(move || loop {
+      std::thread::sleep(LOAD_SAMPLE_INTERVAL);
+      let Ok(current) = system_times() else {
+        continue;
+      };
+      let sample = busy_cpus((
+        current.0.saturating_sub(previous.0),
+        current.1.saturating_sub(previous.1),
+      ));
+      previous = current;
+      // the exponentially-damped moving averages of the Unix load averages
+      let mut averages = load.lock().unwrap_or_else(|err| err.into_inner());
+      for (average, minutes) in averages.iter_mut().zip([1.0, 5.0, 15.0]) {
+        let decay = (-LOAD_SAMPLE_INTERVAL.as_secs_f64() / (minutes * 60.0)).exp();
+        *average = *average * decay + sample * (1.0 - decay);
+      }
+    });
+  }
+  Ok(*load.lock().unwrap_or_else(|err| err.into_inner()))
+}
+
+#[napi]
+/// This function returns the 1, 5 and 15 minutes load averages of the system, so adaptive schedulers have the same signal on every platform, unlike `getCpuPressure` which is only available on Linux.
+///
+/// On Unix, it uses the [`getloadavg(3)`](https://man7.org/linux/man-pages/man3/getloadavg.3.html) function, and the averages count the runnable tasks, plus the tasks in uninterruptible sleep on Linux.
+///
+/// On Windows, which has no load average, it samples the [`GetSystemTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getsystemtimes) function every 5 seconds in a background thread started by the first call, and averages the number of busy CPUs the same way. The averages start from the CPU utilization since boot, and can't exceed the number of CPUs.
+///
+/// On other platforms, it throws.
+pub fn get_system_load() -> Result<SystemLoad> {
+  #[cfg(all(unix, not(target_os = "android")))]
+  {
+    let mut averages = [0.0; 3];
+    if unsafe { libc::getloadavg(averages.as_mut_ptr(), 3) } != 3 {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "Failed to get the load averages".to_owned(),
+      ));
+    }
+    Ok(SystemLoad {
+      avg1: averages[0],
+      avg5: averages[1],
+      avg15: averages[2],
+      emulated: false,
+    })
+  }
+  #[cfg(windows)]
+  {
+    let averages =
+      emulated_load().map_err(|err| sys::priority_error(err, "get the load averages"))?;
+    Ok(SystemLoad {
+      avg1: averages[0],
+      avg5: averages[1],
+      avg15: averages[2],
+      emulated: true,
+    })
+  }
+  #[cfg(not(any(all(unix, not(target_os = "android")), windows)))]
+  {
+    Err(sys::unsupported("Load averages"))
+  }
+}
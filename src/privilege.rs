@@ -0,0 +1,518 @@
+use napi::Result;
+use napi_derive::napi;
+
+use crate::sys;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const CAP_SETPCAP: u32 = 8;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const CAP_SYS_NICE: u32 = 23;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SECBIT_NO_CAP_AMBIENT_RAISE: libc::c_ulong = 1 << 6;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SECBIT_NO_CAP_AMBIENT_RAISE_LOCKED: libc::c_ulong = 1 << 7;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Reads the effective capabilities of the current process from `/proc/self/status`.
+fn effective_capabilities() -> std::io::Result<u64> {
+  let status = std::fs::read_to_string("/proc/self/status")?;
+  status
+    .lines()
+    .find_map(|line| line.strip_prefix("CapEff:"))
+    .and_then(|value| u64::from_str_radix(value.trim(), 16).ok())
+    .ok_or_else(|| std::io::Error::other("CapEff is missing from /proc/self/status"))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Removes `CAP_SYS_NICE` from the effective, permitted and inheritable capabilities of the calling thread, dropping capabilities never requires privileges.
+/// With `CAP_SETPCAP`, it's also removed from the bounding set and raising ambient capabilities is locked, so executing a setuid or file capability binary can't bring it back.
+fn drop_cap_sys_nice() -> std::io::Result<()> {
+  #[repr(C)]
+  struct CapHeader {
+    version: u32,
+    pid: libc::c_int,
+  }
+  #[repr(C)]
+  #[derive(Default, Clone, Copy)]
+  struct CapData {
+    effective: u32,
+    permitted: u32,
+    inheritable: u32,
+  }
+  const LINUX_CAPABILITY_VERSION_3: u32 = 0x2008_0522;
+
+  let mut header = CapHeader {
+    version: LINUX_CAPABILITY_VERSION_3,
+    pid: 0,
+  };
+  let mut data = [CapData::default(); 2];
+  if unsafe { libc::syscall(libc::SYS_capget, &mut header, data.as_mut_ptr()) } == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  if data[0].effective & (1 << CAP_SETPCAP) != 0 {
+    if unsafe {
+      libc::prctl(
+        libc::PR_CAPBSET_DROP,
+        CAP_SYS_NICE as libc::c_ulong,
+        0,
+        0,
+        0,
+      )
+    } == -1
+    {
+      return Err(std::io::Error::last_os_error());
+    }
+    let securebits = unsafe { libc::prctl(libc::PR_GET_SECUREBITS, 0, 0, 0, 0) };
+    if securebits == -1
+      || unsafe {
+        libc::prctl(
+          libc::PR_SET_SECUREBITS,
+          securebits as libc::c_ulong
+            | SECBIT_NO_CAP_AMBIENT_RAISE
+            | SECBIT_NO_CAP_AMBIENT_RAISE_LOCKED,
+          0,
+          0,
+          0,
+        )
+      } == -1
+    {
+      return Err(std::io::Error::last_os_error());
+    }
+  }
+  let mask = !(1 << CAP_SYS_NICE);
+  data[0].effective &= mask;
+  data[0].permitted &= mask;
+  data[0].inheritable &= mask;
+  if unsafe { libc::syscall(libc::SYS_capset, &header, data.as_ptr()) } == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+fn open_process_token(
+  access: windows::Win32::Security::TOKEN_ACCESS_MASK,
+) -> std::io::Result<sys::OwnedHandle> {
+  use windows::Win32::Foundation::HANDLE;
+  use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+  let mut token = HANDLE::default();
+  unsafe { OpenProcessToken(GetCurrentProcess(), access, &mut token) }.map_err(sys::win32_error)?;
+  Ok(sys::OwnedHandle(token))
+}
+
+#[cfg(windows)]
+fn increase_base_priority_luid() -> std::io::Result<windows::Win32::Foundation::LUID> {
+  use windows::core::PCWSTR;
+  use windows::Win32::Foundation::LUID;
+  use windows::Win32::Security::{LookupPrivilegeValueW, SE_INC_BASE_PRIORITY_NAME};
+
+  let mut luid = LUID::default();
+  unsafe { LookupPrivilegeValueW(PCWSTR::null(), SE_INC_BASE_PRIORITY_NAME, &mut luid) }
+    .map_err(sys::win32_error)?;
+  Ok(luid)
+}
+
+#[cfg(windows)]
+fn is_elevated(token: &sys::OwnedHandle) -> std::io::Result<bool> {
+  use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_ELEVATION};
+
+  let mut elevation = TOKEN_ELEVATION::default();
+  let mut length = 0;
+  unsafe {
+    GetTokenInformation(
+      token.0,
+      TokenElevation,
+      Some(&mut elevation as *mut _ as *mut core::ffi::c_void),
+      std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+      &mut length,
+    )
+  }
+  .map_err(sys::win32_error)?;
+  Ok(elevation.TokenIsElevated != 0)
+}
+
+#[cfg(windows)]
+/// Returns the attributes of `SeIncreaseBasePriorityPrivilege` in the token, or `None` when the account doesn't hold it.
+fn increase_base_priority_attributes(
+  token: &sys::OwnedHandle,
+) -> std::io::Result<Option<windows::Win32::Security::TOKEN_PRIVILEGES_ATTRIBUTES>> {
+  use windows::Win32::Security::{
+    GetTokenInformation, TokenPrivileges, LUID_AND_ATTRIBUTES, TOKEN_PRIVILEGES,
+  };
+
+  let luid = increase_base_priority_luid()?;
+  let mut length = 0;
+  // the first call only reports the size of the variable length `TOKEN_PRIVILEGES`
+  let _ = unsafe { GetTokenInformation(token.0, TokenPrivileges, None, 0, &mut length) };
+  // `u64` keeps the buffer aligned for `TOKEN_PRIVILEGES`
+  let mut buffer = vec![0u64; (length as usize).div_ceil(8)];
+  unsafe {
+    GetTokenInformation(
+      token.0,
+      TokenPrivileges,
+      Some(buffer.as_mut_ptr().cast()),
+      length,
+      &mut length,
+    )
+  }
+  .map_err(sys::win32_error)?;
+  let privileges = buffer.as_ptr() as *const TOKEN_PRIVILEGES;
+  let privileges: &[LUID_AND_ATTRIBUTES] = unsafe {
+    std::slice::from_raw_parts(
+      std::ptr::addr_of!((*privileges).Privileges).cast(),
+      (*privileges).PrivilegeCount as usize,
+    )
+  };
+  Ok(
+    privileges
+      .iter()
+      .find(|privilege| {
+        privilege.Luid.LowPart == luid.LowPart && privilege.Luid.HighPart == luid.HighPart
+      })
+      .map(|privilege| privilege.Attributes),
+  )
+}
+
+#[napi]
+/// This function returns whether the current process is privileged enough to raise its priority above the normal one, so libraries can decide up front whether to even attempt a boost.
+///
+/// On Linux, it checks whether the effective capabilities in `/proc/self/status` include [`CAP_SYS_NICE`](https://man7.org/linux/man-pages/man7/capabilities.7.html). Note that `RLIMIT_NICE` may still allow unprivileged processes to raise their priority a little, see `setHighestAllowed`.
+///
+/// On other Unix platforms, it checks whether the process runs as root.
+///
+/// On Windows, it checks whether the process is elevated or its account holds the `SeIncreaseBasePriorityPrivilege` privilege, which is required by the `Realtime` priority class.
+pub fn can_raise_priority() -> Result<bool> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let capabilities =
+      effective_capabilities().map_err(|err| sys::priority_error(err, "read the capabilities"))?;
+    Ok(capabilities & (1 << CAP_SYS_NICE) != 0)
+  }
+  #[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+  {
+    Ok(unsafe { libc::geteuid() } == 0)
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::Security::TOKEN_QUERY;
+
+    let token = open_process_token(TOKEN_QUERY)
+      .map_err(|err| sys::priority_error(err, "open the process token"))?;
+    Ok(
+      is_elevated(&token).map_err(|err| sys::priority_error(err, "query the process token"))?
+        || increase_base_priority_attributes(&token)
+          .map_err(|err| sys::priority_error(err, "query the process token"))?
+          .is_some(),
+    )
+  }
+}
+
+#[napi(object)]
+/// The prediction of `canSetPriority`.
+pub struct PriorityCheck {
+  /// Whether the priority change would succeed.
+  pub allowed: bool,
+  /// Why the priority change would fail, `rlimit` when it exceeds `RLIMIT_NICE` on Linux and `missingPrivilege` when it requires root, `CAP_SYS_NICE` or `SeIncreaseBasePriorityPrivilege`.
+  #[napi(ts_type = "'rlimit' | 'missingPrivilege'")]
+  pub reason: Option<String>,
+  /// A human readable explanation of `reason`.
+  pub message: Option<String>,
+}
+
+impl PriorityCheck {
+  fn allowed() -> Self {
+    Self {
+      allowed: true,
+      reason: None,
+      message: None,
+    }
+  }
+
+  fn denied(reason: &str, message: String) -> Self {
+    Self {
+      allowed: false,
+      reason: Some(reason.to_owned()),
+      message: Some(message),
+    }
+  }
+}
+
+#[napi]
+/// This function predicts whether `setPriority(0, value)` would succeed without changing anything, and explains why when it would not.
+///
+/// On Unix, lowering the priority is always allowed, and raising it requires root or `CAP_SYS_NICE`. On Linux, unprivileged processes may still raise their nice value down to `20 - RLIMIT_NICE`.
+///
+/// On Windows, every priority class except `Realtime` is allowed, `Realtime` requires the `SeIncreaseBasePriorityPrivilege` privilege to be enabled, otherwise `SetPriorityClass` silently falls back to `High`.
+pub fn can_set_priority(value: i32) -> Result<PriorityCheck> {
+  #[cfg(unix)]
+  {
+    let value = value.clamp(-20, 19);
+    let current = sys::getpriority(libc::PRIO_PROCESS, 0)
+      .map_err(|err| sys::priority_error(err, "get the priority"))?;
+    if value >= current || can_raise_priority()? {
+      return Ok(PriorityCheck::allowed());
+    }
+    let highest = crate::unprivileged_highest_nice(current)?;
+    if value >= highest {
+      return Ok(PriorityCheck::allowed());
+    }
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+      Ok(PriorityCheck::denied(
+        "rlimit",
+        format!("the nice value can only be lowered down to {highest} by RLIMIT_NICE, lower values require CAP_SYS_NICE"),
+      ))
+    } else {
+      Ok(PriorityCheck::denied(
+        "missingPrivilege",
+        format!("raising the priority above the current nice value {current} requires root"),
+      ))
+    }
+  }
+  #[cfg(windows)]
+  {
+    use crate::process::ProcessPriorityClass;
+    use windows::Win32::Security::{SE_PRIVILEGE_ENABLED, TOKEN_QUERY};
+
+    if ProcessPriorityClass::from_nice(value) != ProcessPriorityClass::Realtime {
+      return Ok(PriorityCheck::allowed());
+    }
+    let token = open_process_token(TOKEN_QUERY)
+      .map_err(|err| sys::priority_error(err, "open the process token"))?;
+    let attributes = increase_base_priority_attributes(&token)
+      .map_err(|err| sys::priority_error(err, "query the process token"))?;
+    Ok(match attributes {
+      Some(attributes)
+        if attributes.0 & SE_PRIVILEGE_ENABLED.0 != 0 =>
+      {
+        PriorityCheck::allowed()
+      }
+      Some(_) => PriorityCheck::denied(
+        "missingPrivilege",
+        "the Realtime priority class requires SeIncreaseBasePriorityPrivilege, which is held but not enabled, see `enableIncreaseBasePriorityPrivilege`".to_owned(),
+      ),
+      None => PriorityCheck::denied(
+        "missingPrivilege",
+        "the Realtime priority class requires SeIncreaseBasePriorityPrivilege, which the account doesn't hold".to_owned(),
+      ),
+    })
+  }
+}
+
+#[napi(object)]
+/// The result of `getEffectivePriorityRange`.
+pub struct EffectivePriorityRange {
+  /// The lowest nice value, which is the highest priority, that `setPriority(0, value)` can actually apply.
+  pub min: i32,
+  /// The highest nice value, which is the lowest priority, that `setPriority(0, value)` can actually apply.
+  pub max: i32,
+  /// The priority classes that `setProcessPriorityClass` can actually apply, only reported on Windows.
+  pub priority_classes: Option<Vec<crate::process::ProcessPriorityClass>>,
+  /// The nice value of the autogroup of the current process, only reported on Linux when autogroup is enabled.
+  /// Nice values then only weigh threads against the other threads of the same session, while the session itself is weighed by this value.
+  pub autogroup_nice: Option<i32>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Reads the nice value of the autogroup from `/proc/self/autogroup`, such as `/autogroup-42 nice 0`.
+fn autogroup_nice() -> Option<i32> {
+  let enabled = std::fs::read_to_string("/proc/sys/kernel/sched_autogroup_enabled").ok()?;
+  if enabled.trim() != "1" {
+    return None;
+  }
+  let autogroup = std::fs::read_to_string("/proc/self/autogroup").ok()?;
+  autogroup.split_whitespace().nth(2)?.parse().ok()
+}
+
+#[napi]
+/// This function returns the range of nice values the current process can actually apply, considering the rlimits and privileges, so callers don't have to discover it by trial and error.
+///
+/// On Unix, the range ends at `19`. It starts at `-20` with root or `CAP_SYS_NICE`, at `20 - RLIMIT_NICE` on Linux, and at the current nice value elsewhere. On Linux, it also reports the nice value of the [autogroup](https://man7.org/linux/man-pages/man7/sched.7.html) when it's enabled.
+///
+/// On Windows, it also reports the settable priority classes, `Realtime` is only included when `SeIncreaseBasePriorityPrivilege` is enabled.
+pub fn get_effective_priority_range() -> Result<EffectivePriorityRange> {
+  #[cfg(unix)]
+  {
+    let min = if can_raise_priority()? {
+      -20
+    } else {
+      let current = sys::getpriority(libc::PRIO_PROCESS, 0)
+        .map_err(|err| sys::priority_error(err, "get the priority"))?;
+      crate::unprivileged_highest_nice(current)?
+    };
+    Ok(EffectivePriorityRange {
+      min,
+      max: 19,
+      priority_classes: None,
+      #[cfg(any(target_os = "linux", target_os = "android"))]
+      autogroup_nice: autogroup_nice(),
+      #[cfg(not(any(target_os = "linux", target_os = "android")))]
+      autogroup_nice: None,
+    })
+  }
+  #[cfg(windows)]
+  {
+    use crate::process::ProcessPriorityClass;
+
+    let mut priority_classes = vec![
+      ProcessPriorityClass::Idle,
+      ProcessPriorityClass::BelowNormal,
+      ProcessPriorityClass::Normal,
+      ProcessPriorityClass::AboveNormal,
+      ProcessPriorityClass::High,
+    ];
+    let highest = if can_set_priority(ProcessPriorityClass::Realtime.to_nice())?.allowed {
+      ProcessPriorityClass::Realtime
+    } else {
+      ProcessPriorityClass::High
+    };
+    if highest == ProcessPriorityClass::Realtime {
+      priority_classes.push(highest);
+    }
+    Ok(EffectivePriorityRange {
+      min: highest.to_nice(),
+      max: ProcessPriorityClass::Idle.to_nice(),
+      priority_classes: Some(priority_classes),
+      autogroup_nice: None,
+    })
+  }
+}
+
+#[napi]
+/// This function enables the `SeIncreaseBasePriorityPrivilege` privilege in the token of the current process, which is required before the `Realtime` priority class takes effect.
+/// It returns `false` when the account doesn't hold the privilege, in which case `Realtime` silently falls back to `High`.
+///
+/// On Windows, it uses the [`AdjustTokenPrivileges`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-adjusttokenprivileges) function.
+///
+/// On other platforms, it throws.
+pub fn enable_increase_base_priority_privilege() -> Result<bool> {
+  crate::stats::record("enableIncreaseBasePriorityPrivilege", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::Foundation::ERROR_NOT_ALL_ASSIGNED;
+      use windows::Win32::Security::{
+        AdjustTokenPrivileges, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES,
+        TOKEN_PRIVILEGES,
+      };
+
+      let token = open_process_token(TOKEN_ADJUST_PRIVILEGES)
+        .map_err(|err| sys::priority_error(err, "open the process token"))?;
+      let luid = increase_base_priority_luid()
+        .map_err(|err| sys::priority_error(err, "look up SeIncreaseBasePriorityPrivilege"))?;
+      let privileges = TOKEN_PRIVILEGES {
+        PrivilegeCount: 1,
+        Privileges: [LUID_AND_ATTRIBUTES {
+          Luid: luid,
+          Attributes: SE_PRIVILEGE_ENABLED,
+        }],
+      };
+      unsafe { AdjustTokenPrivileges(token.0, false, Some(&privileges), 0, None, None) }.map_err(
+        |err| {
+          sys::priority_error(
+            sys::win32_error(err),
+            "enable SeIncreaseBasePriorityPrivilege",
+          )
+        },
+      )?;
+      // `AdjustTokenPrivileges` succeeds even when the token doesn't hold the privilege
+      Ok(std::io::Error::last_os_error().raw_os_error() != Some(ERROR_NOT_ALL_ASSIGNED.0 as i32))
+    }
+    #[cfg(not(windows))]
+    {
+      Err(sys::unsupported("SeIncreaseBasePriorityPrivilege"))
+    }
+  })
+}
+
+#[napi]
+/// This function drops the ability of the calling thread to raise the priority above the current one, so sandboxed plugin hosts can stop the JavaScript code they load afterwards from promoting itself to realtime. It can't be undone.
+///
+/// On Linux, it lowers [`RLIMIT_NICE`](https://man7.org/linux/man-pages/man2/getrlimit.2.html) to the current nice value and `RLIMIT_RTPRIO` to the current realtime priority for the whole process, and removes `CAP_SYS_NICE` from the calling thread with `capset(2)`.
+/// With `CAP_SETPCAP`, it also removes `CAP_SYS_NICE` from the bounding set with [`PR_CAPBSET_DROP`](https://man7.org/linux/man-pages/man2/PR_CAPBSET_DROP.2const.html) and locks `SECBIT_NO_CAP_AMBIENT_RAISE`, so executing a setuid or file capability binary can't bring it back.
+/// Capabilities, the bounding set and securebits belong to threads: threads created afterwards by the calling thread inherit the restriction, but threads which already exist, such as the libuv thread pool or other worker threads, keep `CAP_SYS_NICE`, and native code running on them can still bypass the limits.
+///
+/// On Windows, it removes the `SeIncreaseBasePriorityPrivilege` privilege from the process token with the [`AdjustTokenPrivileges`](https://learn.microsoft.com/en-us/windows/win32/api/securitybaseapi/nf-securitybaseapi-adjusttokenprivileges) function, so the `Realtime` priority class falls back to `High`.
+///
+/// On other platforms, it throws.
+pub fn lock_priority_ceiling() -> Result<()> {
+  crate::stats::record("lockPriorityCeiling", || {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      let nice = sys::getpriority(libc::PRIO_PROCESS, 0)
+        .map_err(|err| sys::priority_error(err, "get the priority"))?;
+      let policy = unsafe { libc::sched_getscheduler(0) };
+      let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+      if policy == -1 || unsafe { libc::sched_getparam(0, &mut param) } == -1 {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "get the scheduling parameters",
+        ));
+      }
+      let rtprio = if policy == libc::SCHED_FIFO || policy == libc::SCHED_RR {
+        param.sched_priority
+      } else {
+        0
+      };
+      // never raise a limit which is already lower
+      let lower = |resource, value: libc::rlim_t, name: &str| {
+        let mut limit: libc::rlimit = unsafe { std::mem::zeroed() };
+        if unsafe { libc::getrlimit(resource, &mut limit) } == -1 {
+          return Err(sys::priority_error(
+            std::io::Error::last_os_error(),
+            &format!("get {name}"),
+          ));
+        }
+        let value = value.min(limit.rlim_cur);
+        limit.rlim_cur = value;
+        limit.rlim_max = value;
+        if unsafe { libc::setrlimit(resource, &limit) } == -1 {
+          return Err(sys::priority_error(
+            std::io::Error::last_os_error(),
+            &format!("set {name}"),
+          ));
+        }
+        Ok(())
+      };
+      // `RLIMIT_NICE` allows the nice value to be lowered down to `20 - rlim_cur`
+      lower(
+        libc::RLIMIT_NICE,
+        (20 - nice) as libc::rlim_t,
+        "RLIMIT_NICE",
+      )?;
+      lower(libc::RLIMIT_RTPRIO, rtprio as libc::rlim_t, "RLIMIT_RTPRIO")?;
+      drop_cap_sys_nice().map_err(|err| sys::priority_error(err, "drop CAP_SYS_NICE"))
+    }
+    #[cfg(windows)]
+    {
+      use windows::Win32::Security::{
+        AdjustTokenPrivileges, LUID_AND_ATTRIBUTES, SE_PRIVILEGE_REMOVED, TOKEN_ADJUST_PRIVILEGES,
+        TOKEN_PRIVILEGES,
+      };
+
+      let token = open_process_token(TOKEN_ADJUST_PRIVILEGES)
+        .map_err(|err| sys::priority_error(err, "open the process token"))?;
+      let luid = increase_base_priority_luid()
+        .map_err(|err| sys::priority_error(err, "look up SeIncreaseBasePriorityPrivilege"))?;
+      let privileges = TOKEN_PRIVILEGES {
+        PrivilegeCount: 1,
+        Privileges: [LUID_AND_ATTRIBUTES {
+          Luid: luid,
+          Attributes: SE_PRIVILEGE_REMOVED,
+        }],
+      };
+      // a token which doesn't hold the privilege is already locked
+      unsafe { AdjustTokenPrivileges(token.0, false, Some(&privileges), 0, None, None) }.map_err(
+        |err| {
+          sys::priority_error(
+            sys::win32_error(err),
+            "remove SeIncreaseBasePriorityPrivilege",
+          )
+        },
+      )
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+    {
+      Err(sys::unsupported("Locking the priority ceiling"))
+    }
+  })
+}
@@ -0,0 +1,829 @@
+use napi::bindgen_prelude::Function;
+use napi::{Either, Result};
+use napi_derive::napi;
+
+use crate::sys;
+use crate::watch::{interval_from_millis, spawn_watcher, WatchOptions, Watcher};
+
+#[cfg(windows)]
+use windows::Win32::System::Threading::{
+  ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS,
+  IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_CREATION_FLAGS, REALTIME_PRIORITY_CLASS,
+};
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Windows process priority classes, the values are the `*_PRIORITY_CLASS` constants.
+pub enum ProcessPriorityClass {
+  Idle = 0x00000040,
+  BelowNormal = 0x00004000,
+  Normal = 0x00000020,
+  AboveNormal = 0x00008000,
+  High = 0x00000080,
+  Realtime = 0x00000100,
+}
+
+impl ProcessPriorityClass {
+  /// Maps a nice value onto a priority class, using the same thresholds as libuv's `uv_os_setpriority`.
+  pub(crate) fn from_nice(value: i32) -> Self {
+    match value {
+      i32::MIN..=-15 => Self::Realtime,
+      -14..=-8 => Self::High,
+      -7..=-1 => Self::AboveNormal,
+      0..=9 => Self::Normal,
+      10..=18 => Self::BelowNormal,
+      _ => Self::Idle,
+    }
+  }
+
+  /// Maps a priority class back onto a nice value, using the same values as libuv's `uv_os_getpriority`.
+  pub(crate) fn to_nice(self) -> i32 {
+    match self {
+      Self::Realtime => -20,
+      Self::High => -14,
+      Self::AboveNormal => -7,
+      Self::Normal => 0,
+      Self::BelowNormal => 10,
+      Self::Idle => 19,
+    }
+  }
+}
+
+#[cfg(windows)]
+impl From<ProcessPriorityClass> for PROCESS_CREATION_FLAGS {
+  fn from(priority_class: ProcessPriorityClass) -> Self {
+    match priority_class {
+      ProcessPriorityClass::Idle => IDLE_PRIORITY_CLASS,
+      ProcessPriorityClass::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+      ProcessPriorityClass::Normal => NORMAL_PRIORITY_CLASS,
+      ProcessPriorityClass::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+      ProcessPriorityClass::High => HIGH_PRIORITY_CLASS,
+      ProcessPriorityClass::Realtime => REALTIME_PRIORITY_CLASS,
+    }
+  }
+}
+
+#[cfg(windows)]
+impl From<PROCESS_CREATION_FLAGS> for ProcessPriorityClass {
+  fn from(priority_class: PROCESS_CREATION_FLAGS) -> Self {
+    match priority_class {
+      IDLE_PRIORITY_CLASS => Self::Idle,
+      BELOW_NORMAL_PRIORITY_CLASS => Self::BelowNormal,
+      ABOVE_NORMAL_PRIORITY_CLASS => Self::AboveNormal,
+      HIGH_PRIORITY_CLASS => Self::High,
+      REALTIME_PRIORITY_CLASS => Self::Realtime,
+      _ => Self::Normal,
+    }
+  }
+}
+
+#[cfg(windows)]
+pub(crate) fn apply_priority_class(
+  pid: u32,
+  priority_class: PROCESS_CREATION_FLAGS,
+) -> std::io::Result<()> {
+  use windows::Win32::System::Threading::{
+    GetCurrentProcess, SetPriorityClass, PROCESS_SET_INFORMATION,
+  };
+
+  if pid == 0 || pid == std::process::id() {
+    return track_own_priority(|| {
+      unsafe { SetPriorityClass(GetCurrentProcess(), priority_class) }.map_err(sys::win32_error)
+    });
+  }
+  let process = sys::open_process(PROCESS_SET_INFORMATION, pid)?;
+  unsafe { SetPriorityClass(process.0, priority_class) }.map_err(sys::win32_error)
+}
+
+#[cfg(windows)]
+pub(crate) fn query_priority_class(pid: u32) -> std::io::Result<PROCESS_CREATION_FLAGS> {
+  use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetPriorityClass, PROCESS_QUERY_LIMITED_INFORMATION,
+  };
+
+  let priority_class = if pid == 0 {
+    unsafe { GetPriorityClass(GetCurrentProcess()) }
+  } else {
+    let process = sys::open_process(PROCESS_QUERY_LIMITED_INFORMATION, pid)?;
+    unsafe { GetPriorityClass(process.0) }
+  };
+  if priority_class == 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(PROCESS_CREATION_FLAGS(priority_class))
+}
+
+#[napi]
+/// This function set the priority of the process identified by `pid`.
+/// `pid` `0` means the current process.
+///
+/// On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_PROCESS`, `value` is the new nice value.
+///
+/// On Windows, it uses the [`SetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setpriorityclass) function.
+/// `value` is a nice value, it's mapped onto the nearest priority class the same way as Node.js `os.setPriority`.
+pub fn set_priority(pid: u32, value: i32) -> Result<()> {
+  crate::stats::record("setPriority", || apply_priority(pid, value))
+}
+
+pub(crate) fn apply_priority(pid: u32, value: i32) -> Result<()> {
+  #[cfg(unix)]
+  {
+    set_process_nice(pid as libc::id_t, value)
+      .map_err(|err| sys::priority_error(err, &format!("set the priority of process {pid}")))
+  }
+  #[cfg(windows)]
+  {
+    apply_priority_class(pid, ProcessPriorityClass::from_nice(value).into())
+      .map_err(|err| sys::priority_error(err, &format!("set the priority of process {pid}")))
+  }
+}
+
+#[napi]
+/// This function get the priority of the process identified by `pid`.
+/// `pid` `0` means the current process.
+///
+/// On Unix, it uses the [`getpriority(2)`](https://linux.die.net/man/2/getpriority) function with `PRIO_PROCESS`, and returns the nice value.
+///
+/// On Windows, it uses the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function.
+/// The priority class is mapped back to a nice value the same way as Node.js `os.getPriority`.
+pub fn get_priority(pid: u32) -> Result<i32> {
+  #[cfg(unix)]
+  {
+    sys::getpriority(libc::PRIO_PROCESS, pid as libc::id_t)
+      .map_err(|err| sys::priority_error(err, &format!("get the priority of process {pid}")))
+  }
+  #[cfg(windows)]
+  {
+    query_priority_class(pid)
+      .map(|priority_class| ProcessPriorityClass::from(priority_class).to_nice())
+      .map_err(|err| sys::priority_error(err, &format!("get the priority of process {pid}")))
+  }
+}
+
+#[napi]
+/// This function set the priority class of the process identified by `pid`, it defaults to the current process.
+///
+/// On Windows, it uses the [`SetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setpriorityclass) function.
+/// Unlike `nice`, which changes the priority of the calling thread, this affects every thread of the process.
+///
+/// On Unix, the priority class is mapped onto a nice value (Idle: 19, BelowNormal: 10, Normal: 0, AboveNormal: -7, High: -14, Realtime: -20) and applied with `setpriority(2)`.
+pub fn set_process_priority_class(
+  priority_class: ProcessPriorityClass,
+  pid: Option<u32>,
+) -> Result<()> {
+  crate::stats::record("setProcessPriorityClass", || {
+    let pid = pid.unwrap_or(0);
+    #[cfg(unix)]
+    {
+      set_process_nice(pid as libc::id_t, priority_class.to_nice()).map_err(|err| {
+        sys::priority_error(err, &format!("set the priority class of process {pid}"))
+      })
+    }
+    #[cfg(windows)]
+    {
+      apply_priority_class(pid, priority_class.into()).map_err(|err| {
+        sys::priority_error(err, &format!("set the priority class of process {pid}"))
+      })
+    }
+  })
+}
+
+#[napi]
+/// This function get the priority class of the process identified by `pid`, it defaults to the current process.
+///
+/// On Windows, it uses the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function.
+///
+/// On Unix, the nice value of the process is mapped onto the nearest priority class.
+pub fn get_process_priority_class(pid: Option<u32>) -> Result<ProcessPriorityClass> {
+  let pid = pid.unwrap_or(0);
+  #[cfg(unix)]
+  {
+    sys::getpriority(libc::PRIO_PROCESS, pid as libc::id_t)
+      .map(ProcessPriorityClass::from_nice)
+      .map_err(|err| sys::priority_error(err, &format!("get the priority class of process {pid}")))
+  }
+  #[cfg(windows)]
+  {
+    query_priority_class(pid)
+      .map(ProcessPriorityClass::from)
+      .map_err(|err| sys::priority_error(err, &format!("get the priority class of process {pid}")))
+  }
+}
+
+#[napi]
+/// This function set the priority of every process in the process group `pgid`.
+/// `pgid` `0` means the process group of the current process.
+///
+/// On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_PGRP`.
+///
+/// Windows doesn't have process groups, this function always throws on Windows.
+pub fn set_process_group_priority(pgid: u32, value: i32) -> Result<()> {
+  crate::stats::record("setProcessGroupPriority", || {
+    #[cfg(unix)]
+    {
+      let own_group = pgid == 0 || pgid as libc::pid_t == unsafe { libc::getpgrp() };
+      set_nice(libc::PRIO_PGRP, pgid as libc::id_t, value, own_group).map_err(|err| {
+        sys::priority_error(err, &format!("set the priority of process group {pgid}"))
+      })
+    }
+    #[cfg(not(unix))]
+    {
+      let _ = (pgid, value);
+      Err(sys::unsupported("Process group priority"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the priority of the process group `pgid`, which is the highest priority (lowest nice value) of any process in the group.
+/// `pgid` `0` means the process group of the current process.
+///
+/// On Unix, it uses the [`getpriority(2)`](https://linux.die.net/man/2/getpriority) function with `PRIO_PGRP`.
+///
+/// Windows doesn't have process groups, this function always throws on Windows.
+pub fn get_process_group_priority(pgid: u32) -> Result<i32> {
+  #[cfg(unix)]
+  {
+    sys::getpriority(libc::PRIO_PGRP, pgid as libc::id_t)
+      .map_err(|err| sys::priority_error(err, &format!("get the priority of process group {pgid}")))
+  }
+  #[cfg(not(unix))]
+  {
+    let _ = pgid;
+    Err(sys::unsupported("Process group priority"))
+  }
+}
+
+#[cfg(unix)]
+fn lookup_uid(name: &str) -> Result<libc::uid_t> {
+  use napi::{Error, Status};
+
+  let c_name = std::ffi::CString::new(name).map_err(|err| {
+    Error::new(
+      Status::InvalidArg,
+      format!("Invalid user name {name}: {err}"),
+    )
+  })?;
+  let mut buf = vec![0 as libc::c_char; 1024];
+  loop {
+    let mut passwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut result = std::ptr::null_mut();
+    let ret = unsafe {
+      libc::getpwnam_r(
+        c_name.as_ptr(),
+        &mut passwd,
+        buf.as_mut_ptr(),
+        buf.len(),
+        &mut result,
+      )
+    };
+    if ret == libc::ERANGE {
+      buf.resize(buf.len() * 2, 0);
+      continue;
+    }
+    if ret != 0 {
+      return Err(sys::priority_error(
+        std::io::Error::from_raw_os_error(ret),
+        &format!("look up user {name}"),
+      ));
+    }
+    if result.is_null() {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("User {name} does not exist"),
+      ));
+    }
+    return Ok(passwd.pw_uid);
+  }
+}
+
+#[napi]
+/// This function set the priority of every process owned by the user `user`.
+/// `user` can be either a numeric uid or a user name, `0` means the real user id of the current process.
+///
+/// On Unix, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_USER`, user names are resolved with [`getpwnam_r(3)`](https://linux.die.net/man/3/getpwnam_r).
+///
+/// Windows doesn't support per-user priority, this function always throws on Windows.
+pub fn set_user_priority(user: Either<u32, String>, value: i32) -> Result<()> {
+  crate::stats::record("setUserPriority", || {
+    #[cfg(unix)]
+    {
+      let uid = match &user {
+        Either::A(uid) => *uid as libc::uid_t,
+        Either::B(name) => lookup_uid(name)?,
+      };
+      // `PRIO_USER` selects the processes by their real user id
+      let own_user = uid == 0 || uid == unsafe { libc::getuid() };
+      set_nice(libc::PRIO_USER, uid as libc::id_t, value, own_user)
+        .map_err(|err| sys::priority_error(err, &format!("set the priority of user {uid}")))
+    }
+    #[cfg(not(unix))]
+    {
+      let _ = (user, value);
+      Err(sys::unsupported("Per-user priority"))
+    }
+  })
+}
+
+#[napi]
+/// This function make the current process enter or leave background processing mode.
+/// In background mode the system lowers the CPU, I/O and memory priority of the whole process, so it can perform background work without significantly affecting activity in the foreground.
+///
+/// On Windows, it uses the [`SetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setpriorityclass) function with `PROCESS_MODE_BACKGROUND_BEGIN` or `PROCESS_MODE_BACKGROUND_END`.
+/// It fails if the process is already in the requested mode.
+///
+/// This function always throws on other platforms.
+pub fn set_process_background_mode(enabled: bool) -> Result<()> {
+  crate::stats::record("setProcessBackgroundMode", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{
+        PROCESS_MODE_BACKGROUND_BEGIN, PROCESS_MODE_BACKGROUND_END,
+      };
+
+      let (mode, action) = if enabled {
+        (
+          PROCESS_MODE_BACKGROUND_BEGIN,
+          "enter process background mode",
+        )
+      } else {
+        (PROCESS_MODE_BACKGROUND_END, "leave process background mode")
+      };
+      apply_priority_class(0, mode).map_err(|err| sys::priority_error(err, action))
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = enabled;
+      Err(sys::unsupported("Process background mode"))
+    }
+  })
+}
+
+#[napi]
+/// This function opts the current process into or out of EcoQoS, the "Efficiency mode" of the Task Manager.
+/// With EcoQoS the system runs the process at a lower clock speed and prefers efficiency cores on hybrid CPUs, to save power.
+///
+/// On Windows, it uses the [`SetProcessInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setprocessinformation) function with `ProcessPowerThrottling` and `PROCESS_POWER_THROTTLING_EXECUTION_SPEED`.
+/// The Task Manager also lowers the priority class to `Idle` when it enables Efficiency mode, use `setProcessPriorityClass` for that.
+///
+/// On other platforms, it throws.
+pub fn set_efficiency_mode(enabled: bool) -> Result<()> {
+  crate::stats::record("setEfficiencyMode", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{
+        GetCurrentProcess, ProcessPowerThrottling, SetProcessInformation,
+        PROCESS_POWER_THROTTLING_CURRENT_VERSION, PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+        PROCESS_POWER_THROTTLING_STATE,
+      };
+
+      let state = PROCESS_POWER_THROTTLING_STATE {
+        Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+        ControlMask: PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+        StateMask: if enabled {
+          PROCESS_POWER_THROTTLING_EXECUTION_SPEED
+        } else {
+          0
+        },
+      };
+      unsafe {
+        SetProcessInformation(
+          GetCurrentProcess(),
+          ProcessPowerThrottling,
+          &state as *const _ as *const core::ffi::c_void,
+          std::mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+        )
+      }
+      .map_err(|err| sys::priority_error(sys::win32_error(err), "set the efficiency mode"))
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = enabled;
+      Err(sys::unsupported("Efficiency mode"))
+    }
+  })
+}
+
+#[napi]
+/// This function puts the process identified by `pid` into or out of the macOS background state, it defaults to the current process.
+/// In the background state the CPU, I/O and timers of every thread of the process are throttled.
+///
+/// On macOS, it uses the [`setpriority(2)`](https://developer.apple.com/library/archive/documentation/System/Conceptual/ManPages_iPhoneOS/man2/setpriority.2.html) function with `PRIO_DARWIN_PROCESS` and `PRIO_DARWIN_BG`.
+///
+/// On other platforms, it throws.
+pub fn set_darwin_background(enabled: bool, pid: Option<u32>) -> Result<()> {
+  crate::stats::record("setDarwinBackground", || {
+    let pid = pid.unwrap_or(0);
+    #[cfg(target_vendor = "apple")]
+    {
+      let value = if enabled { libc::PRIO_DARWIN_BG } else { 0 };
+      sys::setpriority(libc::PRIO_DARWIN_PROCESS, pid as libc::id_t, value).map_err(|err| {
+        sys::priority_error(err, &format!("set the background state of process {pid}"))
+      })
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    {
+      let _ = (enabled, pid);
+      Err(sys::unsupported("The Darwin background state"))
+    }
+  })
+}
+
+#[napi]
+/// This function enables or disables the dynamic priority boost of the threads of the process identified by `pid`, it defaults to the current process.
+/// Windows temporarily raises the priority of threads woken up by I/O or a GUI event, disabling it reduces the latency jitter of realtime-ish workloads.
+///
+/// On Windows, it uses the [`SetProcessPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setprocesspriorityboost) function.
+///
+/// On other platforms, it throws.
+pub fn set_process_priority_boost(enabled: bool, pid: Option<u32>) -> Result<()> {
+  crate::stats::record("setProcessPriorityBoost", || {
+    let pid = pid.unwrap_or(0);
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{
+        GetCurrentProcess, SetProcessPriorityBoost, PROCESS_SET_INFORMATION,
+      };
+
+      let set = || {
+        let owned = (pid != 0)
+          .then(|| sys::open_process(PROCESS_SET_INFORMATION, pid))
+          .transpose()?;
+        let process = owned
+          .as_ref()
+          .map_or_else(|| unsafe { GetCurrentProcess() }, |process| process.0);
+        unsafe { SetProcessPriorityBoost(process, !enabled) }.map_err(sys::win32_error)
+      };
+      set().map_err(|err| {
+        sys::priority_error(err, &format!("set the priority boost of process {pid}"))
+      })
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = (enabled, pid);
+      Err(sys::unsupported("Priority boost"))
+    }
+  })
+}
+
+#[napi]
+/// This function returns whether the dynamic priority boost is enabled for the process identified by `pid`, it defaults to the current process.
+///
+/// On Windows, it uses the [`GetProcessPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocesspriorityboost) function.
+///
+/// On other platforms, it throws.
+pub fn get_process_priority_boost(pid: Option<u32>) -> Result<bool> {
+  let pid = pid.unwrap_or(0);
+  #[cfg(windows)]
+  {
+    use windows::Win32::Foundation::BOOL;
+    use windows::Win32::System::Threading::{
+      GetCurrentProcess, GetProcessPriorityBoost, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    let get = || {
+      let owned = (pid != 0)
+        .then(|| sys::open_process(PROCESS_QUERY_LIMITED_INFORMATION, pid))
+        .transpose()?;
+      let process = owned
+        .as_ref()
+        .map_or_else(|| unsafe { GetCurrentProcess() }, |process| process.0);
+      let mut disabled = BOOL::default();
+      unsafe { GetProcessPriorityBoost(process, &mut disabled) }.map_err(sys::win32_error)?;
+      Ok(!disabled.as_bool())
+    };
+    get()
+      .map_err(|err| sys::priority_error(err, &format!("get the priority boost of process {pid}")))
+  }
+  #[cfg(not(windows))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("Priority boost"))
+  }
+}
+
+/// Lists the pids of all descendants of `pid`, parents before their children.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn list_descendants(pid: u32) -> std::io::Result<Vec<u32>> {
+  let mut processes = Vec::new();
+  for entry in std::fs::read_dir("/proc")? {
+    let Some(child) = entry?
+      .file_name()
+      .to_str()
+      .and_then(|name| name.parse::<u32>().ok())
+    else {
+      continue;
+    };
+    // the process may have exited after it was listed
+    let Ok(stat) = std::fs::read_to_string(format!("/proc/{child}/stat")) else {
+      continue;
+    };
+    // the command name is wrapped in parentheses and may contain spaces
+    if let Some(parent) = stat
+      .rsplit_once(')')
+      .and_then(|(_, rest)| rest.split_whitespace().nth(1))
+      .and_then(|ppid| ppid.parse::<u32>().ok())
+    {
+      processes.push((child, parent));
+    }
+  }
+  Ok(collect_descendants(pid, &processes))
+}
+
+/// Lists the pids of all descendants of `pid`, parents before their children.
+#[cfg(target_os = "macos")]
+pub(crate) fn list_descendants(pid: u32) -> std::io::Result<Vec<u32>> {
+  let mut descendants = Vec::new();
+  let mut queue = std::collections::VecDeque::from([pid]);
+  while let Some(parent) = queue.pop_front() {
+    let mut capacity =
+      unsafe { libc::proc_listchildpids(parent as libc::pid_t, std::ptr::null_mut(), 0) }.max(0)
+        as usize
+        + 16;
+    let children = loop {
+      let mut buf = vec![0 as libc::pid_t; capacity];
+      let count = unsafe {
+        libc::proc_listchildpids(
+          parent as libc::pid_t,
+          buf.as_mut_ptr().cast(),
+          (buf.len() * std::mem::size_of::<libc::pid_t>()) as libc::c_int,
+        )
+      };
+      if count < 0 {
+        return Err(std::io::Error::last_os_error());
+      }
+      if (count as usize) < capacity {
+        buf.truncate(count as usize);
+        break buf;
+      }
+      capacity *= 2;
+    };
+    for child in children {
+      descendants.push(child as u32);
+      queue.push_back(child as u32);
+    }
+  }
+  Ok(descendants)
+}
+
+/// Lists the pids of all descendants of `pid`, parents before their children.
+#[cfg(windows)]
+pub(crate) fn list_descendants(pid: u32) -> std::io::Result<Vec<u32>> {
+  use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+  };
+
+  let snapshot = sys::OwnedHandle(
+    unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) }.map_err(sys::win32_error)?,
+  );
+  let mut entry = PROCESSENTRY32W {
+    dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+    ..Default::default()
+  };
+  let mut processes = Vec::new();
+  let mut next = unsafe { Process32FirstW(snapshot.0, &mut entry) };
+  while next.is_ok() {
+    processes.push((entry.th32ProcessID, entry.th32ParentProcessID));
+    next = unsafe { Process32NextW(snapshot.0, &mut entry) };
+  }
+  Ok(collect_descendants(pid, &processes))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", windows))]
+fn collect_descendants(pid: u32, processes: &[(u32, u32)]) -> Vec<u32> {
+  let mut descendants = Vec::new();
+  let mut queue = std::collections::VecDeque::from([pid]);
+  while let Some(parent) = queue.pop_front() {
+    for &(child, _) in processes
+      .iter()
+      // Windows reuses pids, so a process may look like its own parent
+      .filter(|&&(child, ppid)| ppid == parent && child != parent && child != pid)
+    {
+      if !descendants.contains(&child) {
+        descendants.push(child);
+        queue.push_back(child);
+      }
+    }
+  }
+  descendants
+}
+
+#[napi]
+/// This function set the priority of the process identified by `pid` and all of its descendants, and returns the number of updated processes.
+/// `value` has the same meaning as in `setPriority`.
+///
+/// The descendants are found by walking the `/proc/*/stat` parent pids on Linux, [`proc_listchildpids`](https://opensource.apple.com/source/Libc/Libc-498/darwin/libproc.h) on macOS and [`CreateToolhelp32Snapshot`](https://learn.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-createtoolhelp32snapshot) on Windows.
+/// Descendants which exit while the tree is being walked are skipped.
+///
+/// This function always throws on other platforms.
+pub fn set_priority_tree(pid: u32, value: i32) -> Result<u32> {
+  crate::stats::record("setPriorityTree", || {
+    #[cfg(any(
+      target_os = "linux",
+      target_os = "android",
+      target_os = "macos",
+      windows
+    ))]
+    {
+      let pid = if pid == 0 { std::process::id() } else { pid };
+      apply_priority(pid, value)?;
+      let descendants = list_descendants(pid).map_err(|err| {
+        sys::priority_error(err, &format!("list the descendants of process {pid}"))
+      })?;
+      let mut updated = 1;
+      for child in descendants {
+        #[cfg(unix)]
+        // the current process is a descendant when `pid` is one of its ancestors
+        let result = match set_process_nice(child as libc::id_t, value) {
+          Err(err) if err.raw_os_error() == Some(libc::ESRCH) => continue,
+          result => result,
+        };
+        #[cfg(windows)]
+        let result =
+          match apply_priority_class(child, ProcessPriorityClass::from_nice(value).into()) {
+            Err(err)
+              if err.raw_os_error()
+                == Some(windows::Win32::Foundation::ERROR_INVALID_PARAMETER.0 as i32) =>
+            {
+              continue
+            }
+            result => result,
+          };
+        result.map_err(|err| {
+          sys::priority_error(err, &format!("set the priority of process {child}"))
+        })?;
+        updated += 1;
+      }
+      Ok(updated)
+    }
+    #[cfg(not(any(
+      target_os = "linux",
+      target_os = "android",
+      target_os = "macos",
+      windows
+    )))]
+    {
+      let _ = (pid, value);
+      Err(sys::unsupported("Setting the priority of a process tree"))
+    }
+  })
+}
+
+#[napi(object)]
+/// The options of `watchPriority`.
+pub struct PriorityWatchOptions {
+  /// The process to watch, it defaults to the current process.
+  pub pid: Option<u32>,
+  /// How often the priority is checked in milliseconds, it defaults to `1000` and must be at least `1`.
+  pub interval: Option<u32>,
+}
+
+#[napi(object)]
+/// A change of the priority of a process reported by `watchPriority` and `onExternalPriorityChange`.
+pub struct PriorityChangeEvent {
+  pub pid: u32,
+  /// The priority before the change, as returned by `getPriority`.
+  pub previous: i32,
+  /// The priority after the change, as returned by `getPriority`.
+  pub current: i32,
+  /// The priority class after the change, as returned by `getProcessPriorityClass`.
+  pub priority_class: ProcessPriorityClass,
+}
+
+#[napi]
+/// This function calls `callback` whenever the priority of the process identified by `pid` changes, for example when an administrator renices it, checking it every `interval` on a background thread.
+/// The priority of the current process is the nice value of its main thread on Unix.
+///
+/// On Unix, it polls [`getpriority(2)`](https://linux.die.net/man/2/getpriority) with `PRIO_PROCESS`.
+///
+/// On Windows, it polls the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function, so only changes of the priority class are reported.
+pub fn watch_priority(
+  callback: Function<PriorityChangeEvent, ()>,
+  options: Option<PriorityWatchOptions>,
+) -> Result<Watcher> {
+  // `0` would be the polling thread itself on Linux, where the nice value is per thread
+  let pid = options
+    .as_ref()
+    .and_then(|options| options.pid)
+    .unwrap_or_else(std::process::id);
+  let interval = interval_from_millis(options.and_then(|options| options.interval))?;
+  let mut last = get_priority(pid)?;
+  spawn_watcher(callback, interval, move || {
+    let current = get_priority(pid).ok()?;
+    if current == last {
+      return None;
+    }
+    let previous = std::mem::replace(&mut last, current);
+    Some(PriorityChangeEvent {
+      pid,
+      previous,
+      current,
+      priority_class: get_process_priority_class(Some(pid))
+        .unwrap_or_else(|_| ProcessPriorityClass::from_nice(current)),
+    })
+  })
+}
+
+/// The last priority of the current process set by this addon, see `track_own_priority`.
+struct OwnPriorityChange {
+  /// Incremented on every change, so watchers know whether a change happened since they last checked.
+  generation: u64,
+  priority: i32,
+}
+
+static OWN_PRIORITY_CHANGE: std::sync::Mutex<OwnPriorityChange> =
+  std::sync::Mutex::new(OwnPriorityChange {
+    generation: 0,
+    priority: 0,
+  });
+
+/// Calls `change`, which may change the priority of the current process, and records the priority it results in, so `onExternalPriorityChange` can tell it apart from external changes.
+/// The lock is held during the change, so watchers never see the new priority before it's recorded.
+pub(crate) fn track_own_priority<T>(
+  change: impl FnOnce() -> std::io::Result<T>,
+) -> std::io::Result<T> {
+  let mut own_change = OWN_PRIORITY_CHANGE
+    .lock()
+    .unwrap_or_else(|err| err.into_inner());
+  let result = change()?;
+  if let Ok(priority) = get_priority(std::process::id()) {
+    own_change.generation += 1;
+    own_change.priority = priority;
+  }
+  Ok(result)
+}
+
+#[cfg(unix)]
+/// Whether `setpriority(2)` with `PRIO_PROCESS` and `who` changes the priority of the current process as returned by `getPriority`, which is the nice value of the main thread on Linux.
+pub(crate) fn is_own_process(who: libc::id_t) -> bool {
+  let pid = std::process::id() as libc::id_t;
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  let who = if who == 0 {
+    // the calling thread
+    unsafe { libc::syscall(libc::SYS_gettid) as libc::id_t }
+  } else {
+    who
+  };
+  who == 0 || who == pid
+}
+
+#[cfg(unix)]
+/// `setpriority(2)` with `PRIO_PROCESS`, recording the changes of the priority of the current process with `track_own_priority`.
+pub(crate) fn set_process_nice(who: libc::id_t, value: i32) -> std::io::Result<()> {
+  set_nice(libc::PRIO_PROCESS, who, value, is_own_process(who))
+}
+
+#[cfg(unix)]
+/// `setpriority(2)`, recording the change with `track_own_priority` when the processes selected by `which` and `who` include the current process.
+fn set_nice(
+  which: sys::PriorityWhich,
+  who: libc::id_t,
+  value: i32,
+  includes_own_process: bool,
+) -> std::io::Result<()> {
+  if includes_own_process {
+    track_own_priority(|| sys::setpriority(which, who, value))
+  } else {
+    sys::setpriority(which, who, value)
+  }
+}
+
+#[napi]
+/// This function calls `callback` whenever the priority of the current process changes, except when the change was made by this addon, so applications can detect interference from ops tooling such as `renice` or the Task Manager, checking it every `interval` on a background thread.
+/// A change is attributed to this addon when the priority is the one this addon set last since the previous check, so external changes made between two checks to the same priority are missed.
+///
+/// On Unix, it polls [`getpriority(2)`](https://linux.die.net/man/2/getpriority) with `PRIO_PROCESS`, which is the nice value of the main thread on Linux.
+///
+/// On Windows, it polls the [`GetPriorityClass`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getpriorityclass) function, so only changes of the priority class are reported.
+pub fn on_external_priority_change(
+  callback: Function<PriorityChangeEvent, ()>,
+  options: Option<WatchOptions>,
+) -> Result<Watcher> {
+  let pid = std::process::id();
+  let (mut last, mut generation) = {
+    let own_change = OWN_PRIORITY_CHANGE
+      .lock()
+      .unwrap_or_else(|err| err.into_inner());
+    (get_priority(pid)?, own_change.generation)
+  };
+  spawn_watcher(callback, WatchOptions::interval(options)?, move || {
+    let own_change = OWN_PRIORITY_CHANGE
+      .lock()
+      .unwrap_or_else(|err| err.into_inner());
+    let current = get_priority(pid).ok()?;
+    let own = own_change.generation != generation && own_change.priority == current;
+    generation = own_change.generation;
+    drop(own_change);
+    if current == last {
+      return None;
+    }
+    let previous = std::mem::replace(&mut last, current);
+    (!own).then(|| PriorityChangeEvent {
+      pid,
+      previous,
+      current,
+      priority_class: get_process_priority_class(Some(pid))
+        .unwrap_or_else(|_| ProcessPriorityClass::from_nice(current)),
+    })
+  })
+}
@@ -0,0 +1,280 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// macOS Quality of Service classes, ordered from the lowest to the highest priority, see [`pthread_set_qos_class_self_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h).
+pub enum QosClass {
+  /// No QoS class was assigned, only returned by `getQosClass`.
+  Unspecified = 0x00,
+  /// Work the user isn't aware of, such as indexing or backups, it runs on the efficiency cores.
+  Background = 0x09,
+  /// Long-running work with a progress indicator, such as downloads.
+  Utility = 0x11,
+  Default = 0x15,
+  /// Work the user started and is waiting for, such as opening a document.
+  UserInitiated = 0x19,
+  /// Work interacting with the user, such as animations and event handling.
+  UserInteractive = 0x21,
+}
+
+#[cfg(target_vendor = "apple")]
+impl QosClass {
+  pub(crate) fn to_raw(self) -> libc::qos_class_t {
+    match self {
+      Self::Unspecified => libc::qos_class_t::QOS_CLASS_UNSPECIFIED,
+      Self::Background => libc::qos_class_t::QOS_CLASS_BACKGROUND,
+      Self::Utility => libc::qos_class_t::QOS_CLASS_UTILITY,
+      Self::Default => libc::qos_class_t::QOS_CLASS_DEFAULT,
+      Self::UserInitiated => libc::qos_class_t::QOS_CLASS_USER_INITIATED,
+      Self::UserInteractive => libc::qos_class_t::QOS_CLASS_USER_INTERACTIVE,
+    }
+  }
+
+  pub(crate) fn from_raw(qos_class: libc::qos_class_t) -> Self {
+    match qos_class {
+      libc::qos_class_t::QOS_CLASS_UNSPECIFIED => Self::Unspecified,
+      libc::qos_class_t::QOS_CLASS_BACKGROUND => Self::Background,
+      libc::qos_class_t::QOS_CLASS_UTILITY => Self::Utility,
+      libc::qos_class_t::QOS_CLASS_DEFAULT => Self::Default,
+      libc::qos_class_t::QOS_CLASS_USER_INITIATED => Self::UserInitiated,
+      libc::qos_class_t::QOS_CLASS_USER_INTERACTIVE => Self::UserInteractive,
+    }
+  }
+}
+
+/// `QOS_MIN_RELATIVE_PRIORITY` of `sys/qos.h`.
+const MIN_RELATIVE_PRIORITY: i32 = -15;
+
+fn check_relative_priority(relative_priority: i32) -> Result<()> {
+  if !(MIN_RELATIVE_PRIORITY..=0).contains(&relative_priority) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "{relative_priority} is not a valid relative priority, expected a value between -15 and 0"
+      ),
+    ));
+  }
+  Ok(())
+}
+
+#[napi]
+/// This function set the QoS class of the calling thread, the idiomatic way to prioritize work on macOS, where nice values are largely ignored under QoS.
+/// `relativePriority` lowers the priority within the class, from `0` (the default) to `-15`.
+///
+/// On macOS, it uses the [`pthread_set_qos_class_self_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) function.
+///
+/// On other platforms, it throws.
+pub fn set_qos_class(qos_class: QosClass, relative_priority: Option<i32>) -> Result<()> {
+  crate::stats::record("setQosClass", || {
+    let relative_priority = relative_priority.unwrap_or(0);
+    if qos_class == QosClass::Unspecified {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "The QoS class of a thread can't be set to Unspecified",
+      ));
+    }
+    check_relative_priority(relative_priority)?;
+    #[cfg(target_vendor = "apple")]
+    {
+      let ret =
+        unsafe { libc::pthread_set_qos_class_self_np(qos_class.to_raw(), relative_priority) };
+      if ret != 0 {
+        return Err(sys::priority_error(
+          std::io::Error::from_raw_os_error(ret),
+          "set the QoS class of the current thread",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    {
+      Err(sys::unsupported("Setting the QoS class"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the QoS class of the calling thread.
+///
+/// On macOS, it uses the [`pthread_get_qos_class_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) function.
+///
+/// On other platforms, it throws.
+pub fn get_qos_class() -> Result<QosClass> {
+  #[cfg(target_vendor = "apple")]
+  {
+    let mut qos_class = libc::qos_class_t::QOS_CLASS_UNSPECIFIED;
+    let mut relative_priority = 0;
+    let ret = unsafe {
+      libc::pthread_get_qos_class_np(libc::pthread_self(), &mut qos_class, &mut relative_priority)
+    };
+    if ret != 0 {
+      return Err(sys::priority_error(
+        std::io::Error::from_raw_os_error(ret),
+        "get the QoS class of the current thread",
+      ));
+    }
+    Ok(QosClass::from_raw(qos_class))
+  }
+  #[cfg(not(target_vendor = "apple"))]
+  {
+    Err(sys::unsupported("Getting the QoS class"))
+  }
+}
+
+#[cfg(target_vendor = "apple")]
+extern "C" {
+  fn pthread_override_qos_class_start_np(
+    thread: libc::pthread_t,
+    qos_class: libc::qos_class_t,
+    relative_priority: libc::c_int,
+  ) -> *mut libc::c_void;
+  fn pthread_override_qos_class_end_np(qos_override: *mut libc::c_void) -> libc::c_int;
+}
+
+#[cfg(target_vendor = "apple")]
+/// Calls `start` with the `pthread_t` of the thread of the current process whose `pthread_threadid_np` is `tid`.
+/// The thread ports are held until `start` returns, so the thread can't go away in the meantime.
+fn with_pthread<T>(tid: u64, start: impl FnOnce(libc::pthread_t) -> T) -> Result<Option<T>> {
+  let task = unsafe { sys::mach_task_self_ };
+  let mut threads: libc::thread_act_array_t = std::ptr::null_mut();
+  let mut count = 0;
+  let ret = unsafe { libc::task_threads(task, &mut threads, &mut count) };
+  if ret != libc::KERN_SUCCESS {
+    return Err(crate::thread::kern_error(ret, "list the threads"));
+  }
+  let ports = unsafe { std::slice::from_raw_parts(threads, count as usize) };
+  let mut result = None;
+  let mut start = Some(start);
+  for &port in ports {
+    let mut info: libc::thread_identifier_info_data_t = unsafe { std::mem::zeroed() };
+    let mut info_count = libc::THREAD_IDENTIFIER_INFO_COUNT;
+    let ret = unsafe {
+      libc::thread_info(
+        port,
+        libc::THREAD_IDENTIFIER_INFO as libc::thread_flavor_t,
+        &mut info as *mut _ as libc::thread_info_t,
+        &mut info_count,
+      )
+    };
+    if ret == libc::KERN_SUCCESS && info.thread_id == tid {
+      let thread = unsafe { libc::pthread_from_mach_thread_np(port) };
+      if thread != 0 {
+        if let Some(start) = start.take() {
+          result = Some(start(thread));
+        }
+      }
+    }
+  }
+  for &port in ports {
+    unsafe { sys::mach_port_deallocate(task, port) };
+  }
+  unsafe {
+    libc::vm_deallocate(
+      task,
+      threads as libc::vm_address_t,
+      std::mem::size_of_val(ports) as libc::vm_size_t,
+    )
+  };
+  Ok(result)
+}
+
+#[napi]
+/// Temporarily raises the QoS class of another thread of the current process, so a thread waiting on a worker doesn't suffer from priority inversion.
+/// The override lasts until `end()` is called or the object is garbage collected.
+///
+/// It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations:
+///
+/// ```ts
+/// {
+///   using _ = new QosOverride(workerThreadId, QosClass.UserInitiated)
+///   // wait for the worker
+/// }
+/// // the override ends here
+/// ```
+///
+/// On macOS, it uses the [`pthread_override_qos_class_start_np` and `pthread_override_qos_class_end_np`](https://github.com/apple-oss-distributions/libpthread/blob/main/include/pthread/qos.h) functions.
+///
+/// On other platforms, the constructor throws.
+pub struct QosOverride {
+  /// The `pthread_override_t`, `None` once ended.
+  handle: Option<usize>,
+}
+
+#[napi]
+impl QosOverride {
+  #[napi(constructor)]
+  /// `threadId` is the `getNativeThreadId()` of the thread to boost, it must be a running thread of the current process.
+  pub fn new(thread_id: i64, qos_class: QosClass, relative_priority: Option<i32>) -> Result<Self> {
+    crate::stats::record("QosOverride", || {
+      let relative_priority = relative_priority.unwrap_or(0);
+      if qos_class == QosClass::Unspecified {
+        return Err(Error::new(
+          Status::InvalidArg,
+          "The QoS class of an override can't be Unspecified",
+        ));
+      }
+      check_relative_priority(relative_priority)?;
+      #[cfg(target_vendor = "apple")]
+      {
+        let handle = with_pthread(thread_id as u64, |thread| unsafe {
+          pthread_override_qos_class_start_np(thread, qos_class.to_raw(), relative_priority)
+        })?
+        .ok_or_else(|| {
+          Error::new(
+            Status::InvalidArg,
+            format!("{thread_id} is not a thread of the current process"),
+          )
+        })?;
+        if handle.is_null() {
+          return Err(Error::new(
+            Status::GenericFailure,
+            format!("Failed to override the QoS class of thread {thread_id}"),
+          ));
+        }
+        Ok(Self {
+          handle: Some(handle as usize),
+        })
+      }
+      #[cfg(not(target_vendor = "apple"))]
+      {
+        let _ = thread_id;
+        Err(sys::unsupported("Overriding the QoS class"))
+      }
+    })
+  }
+
+  #[napi]
+  /// Ends the override, calling it more than once has no effect.
+  pub fn end(&mut self) -> Result<()> {
+    if let Some(handle) = self.handle.take() {
+      #[cfg(target_vendor = "apple")]
+      {
+        let ret = unsafe { pthread_override_qos_class_end_np(handle as *mut libc::c_void) };
+        if ret != 0 {
+          return Err(sys::priority_error(
+            std::io::Error::from_raw_os_error(ret),
+            "end the QoS override",
+          ));
+        }
+      }
+      #[cfg(not(target_vendor = "apple"))]
+      let _ = handle;
+    }
+    Ok(())
+  }
+
+  #[napi(getter)]
+  /// Whether `end()` has already been called.
+  pub fn ended(&self) -> bool {
+    self.handle.is_none()
+  }
+}
+
+impl Drop for QosOverride {
+  fn drop(&mut self) {
+    let _ = self.end();
+  }
+}
@@ -0,0 +1,605 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::{
+  sync::mpsc,
+  time::{Duration, Instant},
+};
+
+use crate::io_priority::IoPriorityClass;
+use crate::{sys, PriorityRange};
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// Linux scheduling policies, see [`sched(7)`](https://man7.org/linux/man-pages/man7/sched.7.html).
+pub enum SchedulerPolicy {
+  /// The default time-sharing policy, `SCHED_OTHER`.
+  Other = 0,
+  /// First in, first out real-time policy, `SCHED_FIFO`.
+  Fifo = 1,
+  /// Round-robin real-time policy, `SCHED_RR`.
+  RoundRobin = 2,
+  /// Time-sharing policy for CPU-bound batch work, `SCHED_BATCH`.
+  Batch = 3,
+  /// Policy for very low priority background work, `SCHED_IDLE`.
+  Idle = 5,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl TryFrom<libc::c_int> for SchedulerPolicy {
+  type Error = Error;
+
+  fn try_from(policy: libc::c_int) -> Result<Self> {
+    match policy {
+      libc::SCHED_OTHER => Ok(Self::Other),
+      libc::SCHED_FIFO => Ok(Self::Fifo),
+      libc::SCHED_RR => Ok(Self::RoundRobin),
+      libc::SCHED_BATCH => Ok(Self::Batch),
+      libc::SCHED_IDLE => Ok(Self::Idle),
+      _ => Err(Error::new(
+        Status::GenericFailure,
+        format!("Unknown scheduling policy {policy}"),
+      )),
+    }
+  }
+}
+
+#[napi(object)]
+pub struct SchedulerInfo {
+  pub policy: SchedulerPolicy,
+  /// The real-time priority, it's always `0` for `Other`, `Batch` and `Idle`.
+  pub priority: u32,
+  /// Whether `SCHED_RESET_ON_FORK` is set.
+  pub reset_on_fork: bool,
+}
+
+#[napi(object)]
+pub struct SchedulerOptions {
+  /// Set `SCHED_RESET_ON_FORK`, so that child processes fall back to `Other` and a non-negative nice value instead of inheriting a real-time policy.
+  pub reset_on_fork: Option<bool>,
+  /// The native id of the thread to change (see `getNativeThreadId`), it defaults to the calling thread.
+  pub tid: Option<u32>,
+  /// Start a watchdog thread which demotes the thread back to `Other` once it has been running for more than `watchdogMs` consecutive milliseconds without blocking, to protect the machine from lockups.
+  /// It only applies to `Fifo` and `RoundRobin` on Linux, and only to the threads of the current process.
+  /// The watchdog needs a higher real-time priority than `rtPriority`, without CAP_SYS_NICE it's capped by `RLIMIT_RTPRIO`, and `setScheduler` throws when it can't get one.
+  pub watchdog_ms: Option<u32>,
+}
+
+#[cfg(windows)]
+impl SchedulerPolicy {
+  /// Windows has no scheduling policies, they are approximated with thread priorities.
+  fn to_windows_thread_priority(self) -> crate::WindowsThreadPriority {
+    use crate::WindowsThreadPriority;
+
+    match self {
+      Self::Fifo | Self::RoundRobin => WindowsThreadPriority::ThreadPriorityTimeCritical,
+      Self::Other => WindowsThreadPriority::ThreadPriorityNormal,
+      Self::Batch => WindowsThreadPriority::ThreadPriorityBelowNormal,
+      Self::Idle => WindowsThreadPriority::ThreadPriorityIdle,
+    }
+  }
+}
+
+#[napi]
+/// This function set the scheduling policy and the real-time priority of the calling thread, or of the thread identified by the native thread id `tid`.
+///
+/// On Linux, it uses the [`sched_setscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_setscheduler.2.html) function.
+/// `rt_priority` must be `0` for `Other`, `Batch` and `Idle`, and between `1` and `99` for `Fifo` and `RoundRobin`.
+/// Real-time policies require root or CAP_SYS_NICE, or a non-zero `RLIMIT_RTPRIO`.
+/// Pass `{ resetOnFork: true }` to prevent child processes from inheriting a real-time policy.
+///
+/// On Windows, there are no scheduling policies, it uses the [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) function instead.
+/// `Fifo` and `RoundRobin` are mapped to `ThreadPriorityTimeCritical`, `Other` to `ThreadPriorityNormal`, `Batch` to `ThreadPriorityBelowNormal` and `Idle` to `ThreadPriorityIdle`. `rt_priority` and `resetOnFork` are ignored.
+///
+/// On other platforms, it throws.
+pub fn set_scheduler(
+  policy: SchedulerPolicy,
+  rt_priority: Option<u32>,
+  options: Option<SchedulerOptions>,
+) -> Result<()> {
+  crate::stats::record("setScheduler", || {
+    apply_scheduler(policy, rt_priority, options)
+  })
+}
+
+pub(crate) fn apply_scheduler(
+  policy: SchedulerPolicy,
+  rt_priority: Option<u32>,
+  options: Option<SchedulerOptions>,
+) -> Result<()> {
+  let tid = options.as_ref().and_then(|options| options.tid);
+  let action = || match tid {
+    Some(tid) => format!("set the scheduling policy of thread {tid}"),
+    None => "set the scheduling policy".to_owned(),
+  };
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let param = libc::sched_param {
+      sched_priority: rt_priority.unwrap_or(0) as libc::c_int,
+    };
+    let watchdog_ms = options.as_ref().and_then(|options| options.watchdog_ms);
+    let realtime = matches!(policy, SchedulerPolicy::Fifo | SchedulerPolicy::RoundRobin);
+    let mut policy = policy as libc::c_int;
+    if options
+      .and_then(|options| options.reset_on_fork)
+      .unwrap_or(false)
+    {
+      policy |= libc::SCHED_RESET_ON_FORK;
+    }
+    // the watchdog is started first, so that it doesn't inherit the real-time policy
+    let watchdog = match (realtime, watchdog_ms) {
+      (true, Some(watchdog_ms)) => {
+        let tid = match tid {
+          // the CPU time of a thread can only be read from its own process
+          Some(tid) if !std::path::Path::new(&format!("/proc/self/task/{tid}")).exists() => {
+            return Err(Error::new(
+              Status::InvalidArg,
+              format!(
+                "watchdogMs is only supported for the threads of the current process, {tid} is not one of them"
+              ),
+            ))
+          }
+          Some(tid) => tid as libc::pid_t,
+          None => crate::thread::current_thread_id()? as libc::pid_t,
+        };
+        Some(
+          spawn_realtime_watchdog(
+            tid,
+            param.sched_priority,
+            Duration::from_millis(watchdog_ms as u64),
+          )
+          .map_err(|err| sys::priority_error(err, "start the real-time watchdog"))?,
+        )
+      }
+      _ => None,
+    };
+    if unsafe { libc::sched_setscheduler(tid.unwrap_or(0) as libc::pid_t, policy, &param) } == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        &action(),
+      ));
+    }
+    if let Some(watchdog) = watchdog {
+      let _ = watchdog.send(());
+    }
+    Ok(())
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{
+      GetCurrentThread, SetThreadPriority, THREAD_SET_LIMITED_INFORMATION,
+    };
+
+    let _ = rt_priority;
+    let priority = policy.to_windows_thread_priority();
+    let ret = match tid {
+      Some(tid) => {
+        let thread = sys::open_thread(THREAD_SET_LIMITED_INFORMATION, tid)
+          .map_err(|err| sys::priority_error(err, &action()))?;
+        unsafe { SetThreadPriority(thread.0, priority.into()) }
+      }
+      None => unsafe { SetThreadPriority(GetCurrentThread(), priority.into()) },
+    };
+    ret.map_err(|err| sys::priority_error(sys::win32_error(err), &action()))
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+  {
+    let _ = (policy, rt_priority, action);
+    Err(sys::unsupported("Setting the scheduling policy"))
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// The CPU time consumed by the thread `tid`, or `None` once it has exited.
+fn thread_cpu_time(tid: libc::pid_t) -> Option<Duration> {
+  // `MAKE_THREAD_CPUCLOCK(tid, CPUCLOCK_SCHED)` from the kernel, which is also what `pthread_getcpuclockid` returns
+  let clock = (!tid << 3) | 6;
+  let mut time: libc::timespec = unsafe { std::mem::zeroed() };
+  if unsafe { libc::clock_gettime(clock, &mut time) } == -1 {
+    return None;
+  }
+  Some(Duration::new(time.tv_sec as u64, time.tv_nsec as u32))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Gives the calling thread the highest `SCHED_FIFO` priority it's allowed to use, which must be higher than `rt_priority`.
+fn outrank_realtime_priority(rt_priority: libc::c_int) -> std::io::Result<()> {
+  let max = unsafe { libc::sched_get_priority_max(libc::SCHED_FIFO) };
+  let set = |sched_priority| {
+    let param = libc::sched_param { sched_priority };
+    if unsafe { libc::sched_setscheduler(0, libc::SCHED_FIFO, &param) } == -1 {
+      return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+  };
+  let err = match set(max) {
+    Ok(()) => return Ok(()),
+    Err(err) if err.kind() == std::io::ErrorKind::PermissionDenied => err,
+    Err(err) => return Err(err),
+  };
+  // without CAP_SYS_NICE, the priority is capped by the soft `RLIMIT_RTPRIO`, such as with rtkit or an audio group
+  let mut limit: libc::rlimit = unsafe { std::mem::zeroed() };
+  if unsafe { libc::getrlimit(libc::RLIMIT_RTPRIO, &mut limit) } == -1 {
+    return Err(err);
+  }
+  let allowed = limit.rlim_cur.min(max as libc::rlim_t) as libc::c_int;
+  if allowed <= rt_priority {
+    return Err(std::io::Error::new(
+      std::io::ErrorKind::PermissionDenied,
+      format!(
+        "the watchdog needs a higher real-time priority than {rt_priority}, but RLIMIT_RTPRIO only allows {allowed}"
+      ),
+    ));
+  }
+  set(allowed)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Starts a watchdog which demotes `tid` to `SCHED_OTHER` once it has been running for `limit` without blocking.
+/// It starts watching when the returned sender is used, and stops when the sender is dropped unused, the thread exits or leaves its real-time policy.
+fn spawn_realtime_watchdog(
+  tid: libc::pid_t,
+  rt_priority: libc::c_int,
+  limit: Duration,
+) -> std::io::Result<mpsc::Sender<()>> {
+  let period = (limit / 4).clamp(Duration::from_millis(1), Duration::from_millis(10));
+  let (ready_sender, ready) = mpsc::channel();
+  let (start, start_receiver) = mpsc::channel();
+  std::thread::Builder::new()
+    .name("nice-rt-watchdog".to_owned())
+    .spawn(move || {
+      // the watchdog must outrank the thread it watches, otherwise it never runs on a busy CPU
+      let outranked = outrank_realtime_priority(rt_priority);
+      let failed = outranked.is_err();
+      let _ = ready_sender.send(outranked);
+      if failed || start_receiver.recv().is_err() {
+        return;
+      }
+      let Some(mut last_cpu) = thread_cpu_time(tid) else {
+        return;
+      };
+      let mut last_wall = Instant::now();
+      let mut busy = Duration::ZERO;
+      loop {
+        std::thread::sleep(period);
+        let Some(cpu) = thread_cpu_time(tid) else {
+          return;
+        };
+        let policy = unsafe { libc::sched_getscheduler(tid) } & !libc::SCHED_RESET_ON_FORK;
+        if policy != libc::SCHED_FIFO && policy != libc::SCHED_RR {
+          return;
+        }
+        let wall = last_wall.elapsed();
+        last_wall = Instant::now();
+        // the thread ran for (nearly) the whole period, so it didn't block
+        if (cpu - last_cpu) * 10 >= wall * 9 {
+          busy += wall;
+        } else {
+          busy = Duration::ZERO;
+        }
+        last_cpu = cpu;
+        if busy >= limit {
+          let param = libc::sched_param { sched_priority: 0 };
+          unsafe { libc::sched_setscheduler(tid, libc::SCHED_OTHER, &param) };
+          return;
+        }
+      }
+    })?;
+  ready
+    .recv()
+    .map_err(|_| std::io::Error::other("the watchdog thread exited"))??;
+  Ok(start)
+}
+
+#[napi]
+/// This function get the scheduling policy and the real-time priority of the calling thread, or of the thread identified by the native thread id `tid`.
+///
+/// On Linux, it uses the [`sched_getscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_getscheduler.2.html) and [`sched_getparam(2)`](https://man7.org/linux/man-pages/man2/sched_getparam.2.html) functions.
+///
+/// On other platforms, it throws.
+pub fn get_scheduler(tid: Option<u32>) -> Result<SchedulerInfo> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let pid = tid.unwrap_or(0) as libc::pid_t;
+    let action = |what: &str| match tid {
+      Some(tid) => format!("get the {what} of thread {tid}"),
+      None => format!("get the {what}"),
+    };
+    let policy = unsafe { libc::sched_getscheduler(pid) };
+    if policy == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        &action("scheduling policy"),
+      ));
+    }
+    let mut param: libc::sched_param = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sched_getparam(pid, &mut param) } == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        &action("scheduling parameters"),
+      ));
+    }
+    Ok(SchedulerInfo {
+      policy: SchedulerPolicy::try_from(policy & !libc::SCHED_RESET_ON_FORK)?,
+      priority: param.sched_priority as u32,
+      reset_on_fork: policy & libc::SCHED_RESET_ON_FORK != 0,
+    })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = tid;
+    Err(sys::unsupported("Getting the scheduling policy"))
+  }
+}
+
+#[napi]
+/// This function returns the valid real-time priorities of `policy`, so they can be validated before calling `setScheduler`.
+///
+/// On Linux, it uses the [`sched_get_priority_min(2)`](https://man7.org/linux/man-pages/man2/sched_get_priority_min.2.html) and [`sched_get_priority_max(2)`](https://man7.org/linux/man-pages/man2/sched_get_priority_max.2.html) functions.
+///
+/// On other platforms, it throws.
+pub fn get_scheduler_priority_range(policy: SchedulerPolicy) -> Result<PriorityRange> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let min = unsafe { libc::sched_get_priority_min(policy as libc::c_int) };
+    let max = unsafe { libc::sched_get_priority_max(policy as libc::c_int) };
+    if min == -1 || max == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get the priority range of the scheduling policy",
+      ));
+    }
+    Ok(PriorityRange { min, max })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = policy;
+    Err(sys::unsupported(
+      "Getting the priority range of a scheduling policy",
+    ))
+  }
+}
+
+#[napi]
+/// This function returns the `RoundRobin` time slice of the process identified by `pid` in nanoseconds, `pid` defaults to the current process.
+///
+/// On Linux, it uses the [`sched_rr_get_interval(2)`](https://man7.org/linux/man-pages/man2/sched_rr_get_interval.2.html) function.
+///
+/// On other platforms, it throws.
+pub fn get_round_robin_interval(pid: Option<u32>) -> Result<i64> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let pid = pid.unwrap_or(0);
+    let mut interval: libc::timespec = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sched_rr_get_interval(pid as libc::pid_t, &mut interval) } == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        &format!("get the round-robin interval of process {pid}"),
+      ));
+    }
+    Ok(interval.tv_sec as i64 * 1_000_000_000 + interval.tv_nsec as i64)
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("Getting the round-robin interval"))
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[repr(C)]
+#[derive(Default)]
+/// `struct sched_attr` including the `sched_latency_nice` field of the latency-nice kernel patches.
+struct SchedAttr {
+  size: u32,
+  sched_policy: u32,
+  sched_flags: u64,
+  sched_nice: i32,
+  sched_priority: u32,
+  sched_runtime: u64,
+  sched_deadline: u64,
+  sched_period: u64,
+  sched_util_min: u32,
+  sched_util_max: u32,
+  sched_latency_nice: i32,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// The size of `SchedAttr` without the trailing padding.
+const SCHED_ATTR_SIZE_LATENCY_NICE: u32 = 60;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SCHED_FLAG_KEEP_ALL: u64 = 0x08 | 0x10;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+const SCHED_FLAG_LATENCY_NICE: u64 = 0x80;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Whether the error means that the kernel doesn't know about latency nice.
+fn latency_nice_unsupported(err: &std::io::Error) -> bool {
+  matches!(
+    err.raw_os_error(),
+    Some(libc::ENOSYS) | Some(libc::EINVAL) | Some(libc::E2BIG)
+  )
+}
+
+#[napi]
+/// This function set the latency nice value of the calling thread, from `-20` (the most latency sensitive) to `19`, without changing its policy or nice value.
+/// It returns `false` when the kernel doesn't support latency nice.
+///
+/// On Linux, it uses the [`sched_setattr(2)`](https://man7.org/linux/man-pages/man2/sched_setattr.2.html) syscall with the `sched_latency_nice` field, which is only available on kernels with the latency-nice patches.
+///
+/// On other platforms, it throws.
+pub fn set_latency_nice(value: i32) -> Result<bool> {
+  crate::stats::record("setLatencyNice", || {
+    if !(-20..=19).contains(&value) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("{value} is not a valid latency nice value, expected a value between -20 and 19"),
+      ));
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      let attr = SchedAttr {
+        size: SCHED_ATTR_SIZE_LATENCY_NICE,
+        sched_flags: SCHED_FLAG_KEEP_ALL | SCHED_FLAG_LATENCY_NICE,
+        sched_latency_nice: value,
+        ..Default::default()
+      };
+      if unsafe { libc::syscall(libc::SYS_sched_setattr, 0, &attr, 0) } == -1 {
+        let err = std::io::Error::last_os_error();
+        if latency_nice_unsupported(&err) {
+          return Ok(false);
+        }
+        return Err(sys::priority_error(err, "set the latency nice value"));
+      }
+      Ok(true)
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      Err(sys::unsupported("Setting the latency nice value"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the latency nice value of the calling thread, or `null` when the kernel doesn't support latency nice.
+///
+/// On Linux, it uses the [`sched_getattr(2)`](https://man7.org/linux/man-pages/man2/sched_getattr.2.html) syscall.
+///
+/// On other platforms, it throws.
+pub fn get_latency_nice() -> Result<Option<i32>> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let mut attr = SchedAttr::default();
+    let size = SCHED_ATTR_SIZE_LATENCY_NICE;
+    if unsafe { libc::syscall(libc::SYS_sched_getattr, 0, &mut attr, size, 0) } == -1 {
+      let err = std::io::Error::last_os_error();
+      if latency_nice_unsupported(&err) {
+        return Ok(None);
+      }
+      return Err(sys::priority_error(err, "get the latency nice value"));
+    }
+    // the kernel reports the size of the `struct sched_attr` it knows about
+    if attr.size < size {
+      return Ok(None);
+    }
+    Ok(Some(attr.sched_latency_nice))
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    Err(sys::unsupported("Getting the latency nice value"))
+  }
+}
+
+#[napi(object, use_nullable = true)]
+/// The scheduling state of the calling thread, fields which are not supported on the current platform are `null`.
+pub struct SchedulingState {
+  /// The nice value, see `getPriority`.
+  pub nice: i32,
+  /// The scheduling policy, only available on Linux.
+  pub policy: Option<SchedulerPolicy>,
+  /// The real-time priority, only available on Linux.
+  pub rt_priority: Option<u32>,
+  /// The CPUs the thread may run on, only available on Linux.
+  pub affinity: Option<Vec<u32>>,
+  /// The I/O scheduling class, only available on Linux.
+  pub io_class: Option<IoPriorityClass>,
+  /// The level within `ioClass`, only available on Linux.
+  pub io_level: Option<u32>,
+}
+
+#[napi]
+/// This function returns the whole scheduling state of the calling thread in a single call.
+///
+/// On Linux, it combines [`getpriority(2)`](https://linux.die.net/man/2/getpriority), [`sched_getscheduler(2)`](https://man7.org/linux/man-pages/man2/sched_getscheduler.2.html), [`sched_getaffinity(2)`](https://man7.org/linux/man-pages/man2/sched_getaffinity.2.html) and [`ioprio_get(2)`](https://man7.org/linux/man-pages/man2/ioprio_get.2.html).
+///
+/// On other platforms, only `nice` is available, the other fields are `null`.
+pub fn get_scheduling_state() -> Result<SchedulingState> {
+  let nice = crate::process::get_priority(0)?;
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let scheduler = get_scheduler(None)?;
+    let affinity = crate::affinity::sched_getaffinity(0)
+      .map_err(|err| sys::priority_error(err, "get the CPU affinity"))?;
+    let (io_class, io_level) =
+      crate::io_priority::ioprio_get(crate::io_priority::IOPRIO_WHO_PROCESS, 0)
+        .map_err(|err| sys::priority_error(err, "get the I/O priority"))?;
+    Ok(SchedulingState {
+      nice,
+      policy: Some(scheduler.policy),
+      rt_priority: Some(scheduler.priority),
+      affinity: Some(affinity),
+      io_class: Some(io_class),
+      io_level: Some(io_level),
+    })
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    Ok(SchedulingState {
+      nice,
+      policy: None,
+      rt_priority: None,
+      affinity: None,
+      io_class: None,
+      io_level: None,
+    })
+  }
+}
+
+#[napi(object)]
+pub struct SchedStats {
+  /// The time spent running on a CPU in nanoseconds.
+  pub run_time: f64,
+  /// The time spent runnable, waiting on a runqueue for a CPU, in nanoseconds. It grows when the thread is starved by higher priority work.
+  pub wait_time: f64,
+  /// The number of time slices run on a CPU.
+  pub timeslices: f64,
+}
+
+#[napi]
+/// This function returns the scheduler statistics of the calling thread, or of the thread identified by the native thread id `tid`, to quantify how much a low priority thread is starved.
+/// Comparing `waitTime` with `runTime` over an interval tells how long the thread waited for a CPU for each unit of CPU time it got.
+///
+/// On Linux, it reads [`/proc/<tid>/schedstat`](https://docs.kernel.org/scheduler/sched-stats.html#proc-pid-schedstat), which requires a kernel built with `CONFIG_SCHED_INFO`.
+///
+/// On other platforms, it throws.
+pub fn get_sched_stats(tid: Option<u32>) -> Result<SchedStats> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let path = match tid {
+      Some(tid) => format!("/proc/{tid}/schedstat"),
+      None => "/proc/thread-self/schedstat".to_owned(),
+    };
+    let action = match tid {
+      Some(tid) => format!("get the scheduler statistics of thread {tid}"),
+      None => "get the scheduler statistics".to_owned(),
+    };
+    let content = std::fs::read_to_string(&path).map_err(|err| {
+      if err.kind() == std::io::ErrorKind::NotFound && tid.is_none() {
+        sys::unsupported("Scheduler statistics without CONFIG_SCHED_INFO")
+      } else {
+        sys::priority_error(err, &action)
+      }
+    })?;
+    // `run_time wait_time timeslices`
+    let fields = content
+      .split_whitespace()
+      .map(|field| field.parse::<f64>())
+      .collect::<std::result::Result<Vec<_>, _>>();
+    match fields.as_deref() {
+      Ok([run_time, wait_time, timeslices, ..]) => Ok(SchedStats {
+        run_time: *run_time,
+        wait_time: *wait_time,
+        timeslices: *timeslices,
+      }),
+      _ => Err(Error::new(
+        Status::GenericFailure,
+        format!("Failed to {action}: unexpected content {content:?} in {path}"),
+      )),
+    }
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    let _ = tid;
+    Err(sys::unsupported("Scheduler statistics"))
+  }
+}
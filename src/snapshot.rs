@@ -0,0 +1,146 @@
+use napi::Result;
+use napi_derive::napi;
+
+use crate::io_priority::{IoPriorityClass, WindowsIoPriority};
+use crate::process::ProcessPriorityClass;
+use crate::qos::QosClass;
+use crate::sched::SchedulerPolicy;
+
+#[napi(object, use_nullable = true)]
+/// The scheduling state of the current process and the calling thread, it only contains plain values so it can be passed to `JSON.stringify`.
+/// Fields which are not supported on the current platform, or which can't be read, are `null`.
+pub struct SchedulingSnapshot {
+  /// The platform, the same as `process.platform`.
+  pub platform: String,
+  pub pid: u32,
+  /// The native id of the calling thread, see `getNativeThreadId`.
+  pub tid: Option<i64>,
+  /// The nice value of the calling thread, see `getPriority`.
+  pub nice: i32,
+  /// The priority class of the process, see `getProcessPriorityClass`.
+  pub priority_class: Option<ProcessPriorityClass>,
+  /// The scheduling policy of the calling thread, only available on Linux.
+  pub policy: Option<SchedulerPolicy>,
+  /// The real-time priority of the calling thread, only available on Linux.
+  pub rt_priority: Option<u32>,
+  /// Whether `SCHED_RESET_ON_FORK` is set, only available on Linux.
+  pub reset_on_fork: Option<bool>,
+  /// The latency nice value of the calling thread, only available on Linux kernels with the latency-nice patches.
+  pub latency_nice: Option<i32>,
+  /// The CPUs the calling thread may run on, see `getCpuAffinity`.
+  pub affinity: Option<Vec<u32>>,
+  /// The I/O scheduling class of the calling thread, only available on Linux.
+  pub io_class: Option<IoPriorityClass>,
+  /// The level within `ioClass`, only available on Linux.
+  pub io_level: Option<u32>,
+  /// The I/O priority of the process, only available on Windows.
+  pub windows_io_priority: Option<WindowsIoPriority>,
+  /// The memory priority of the process from `1` (very low) to `5` (normal), only available on Windows.
+  pub memory_priority: Option<u32>,
+  /// The OOM score adjustment of the process, only available on Linux.
+  pub oom_score_adj: Option<i32>,
+  /// The QoS class of the calling thread, only available on macOS.
+  pub qos_class: Option<QosClass>,
+  /// The CPU weight of the cgroup of the process, see `getCgroupCpuWeight`.
+  pub cgroup_cpu_weight: Option<u32>,
+  /// The soft `RLIMIT_NICE`, `null` when unlimited, only available on Linux.
+  pub rlimit_nice: Option<f64>,
+  /// The soft `RLIMIT_RTPRIO`, `null` when unlimited, only available on Linux.
+  pub rlimit_rtprio: Option<f64>,
+}
+
+#[cfg(windows)]
+fn memory_priority() -> std::io::Result<u32> {
+  use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetProcessInformation, ProcessMemoryPriority, MEMORY_PRIORITY_INFORMATION,
+  };
+
+  let mut information = MEMORY_PRIORITY_INFORMATION::default();
+  unsafe {
+    GetProcessInformation(
+      GetCurrentProcess(),
+      ProcessMemoryPriority,
+      &mut information as *mut _ as *mut core::ffi::c_void,
+      std::mem::size_of::<MEMORY_PRIORITY_INFORMATION>() as u32,
+    )
+  }
+  .map_err(crate::sys::win32_error)?;
+  Ok(information.MemoryPriority.0)
+}
+
+#[napi]
+/// This function returns the scheduling state of the current process and the calling thread in a single call, which is suitable for bug reports and telemetry.
+/// Only reading the nice value may throw, the other fields are `null` when they can't be read.
+///
+/// On Linux, it combines `getScheduler`, `getLatencyNice`, `getCpuAffinity`, `getIoPriority`, `getOomScoreAdj`, `getCgroupCpuWeight` and [`getrlimit(2)`](https://man7.org/linux/man-pages/man2/getrlimit.2.html).
+///
+/// On macOS, it adds `getQosClass`.
+///
+/// On Windows, it adds `getProcessIoPriority` and the [`GetProcessInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocessinformation) function with `ProcessMemoryPriority`.
+pub fn get_scheduling_snapshot() -> Result<SchedulingSnapshot> {
+  let snapshot = SchedulingSnapshot {
+    platform: match std::env::consts::OS {
+      "windows" => "win32",
+      "macos" => "darwin",
+      os => os,
+    }
+    .to_owned(),
+    pid: std::process::id(),
+    tid: crate::thread::get_native_thread_id().ok(),
+    nice: crate::process::get_priority(0)?,
+    priority_class: crate::process::get_process_priority_class(None).ok(),
+    policy: None,
+    rt_priority: None,
+    reset_on_fork: None,
+    latency_nice: None,
+    affinity: crate::affinity::get_cpu_affinity(None).ok(),
+    io_class: None,
+    io_level: None,
+    windows_io_priority: None,
+    memory_priority: None,
+    oom_score_adj: None,
+    qos_class: None,
+    cgroup_cpu_weight: None,
+    rlimit_nice: None,
+    rlimit_rtprio: None,
+  };
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  let snapshot = {
+    // the soft limit, `None` when it's unlimited
+    let soft_rlimit = |resource| {
+      let mut limit: libc::rlimit = unsafe { std::mem::zeroed() };
+      if unsafe { libc::getrlimit(resource, &mut limit) } == -1 {
+        return None;
+      }
+      (limit.rlim_cur != libc::RLIM_INFINITY).then_some(limit.rlim_cur as f64)
+    };
+    let scheduler = crate::sched::get_scheduler(None).ok();
+    let io_priority =
+      crate::io_priority::ioprio_get(crate::io_priority::IOPRIO_WHO_PROCESS, 0).ok();
+    SchedulingSnapshot {
+      policy: scheduler.as_ref().map(|scheduler| scheduler.policy),
+      rt_priority: scheduler.as_ref().map(|scheduler| scheduler.priority),
+      reset_on_fork: scheduler.as_ref().map(|scheduler| scheduler.reset_on_fork),
+      latency_nice: crate::sched::get_latency_nice().ok().flatten(),
+      io_class: io_priority.map(|(class, _)| class),
+      io_level: io_priority.map(|(_, level)| level),
+      oom_score_adj: crate::oom::get_oom_score_adj(None).ok(),
+      cgroup_cpu_weight: crate::cgroup::get_cgroup_cpu_weight(None).ok(),
+      rlimit_nice: soft_rlimit(libc::RLIMIT_NICE),
+      rlimit_rtprio: soft_rlimit(libc::RLIMIT_RTPRIO),
+      ..snapshot
+    }
+  };
+  #[cfg(target_vendor = "apple")]
+  let snapshot = SchedulingSnapshot {
+    qos_class: crate::qos::get_qos_class().ok(),
+    ..snapshot
+  };
+  #[cfg(windows)]
+  let snapshot = SchedulingSnapshot {
+    windows_io_priority: crate::io_priority::get_process_io_priority().ok(),
+    memory_priority: memory_priority().ok(),
+    ..snapshot
+  };
+  Ok(snapshot)
+}
@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use napi::Result;
+use napi_derive::napi;
+
+#[napi(object)]
+#[derive(Clone)]
+pub struct LastError {
+  pub message: String,
+  /// The status of the error, such as `GenericFailure` or `InvalidArg`, the same as the `code` of the thrown error.
+  pub code: String,
+  /// When the error happened, in milliseconds since the Unix epoch.
+  pub timestamp: f64,
+}
+
+#[napi(object)]
+pub struct FunctionStats {
+  /// The JavaScript name of the function, such as `setPriority`.
+  pub function: String,
+  pub successes: f64,
+  pub failures: f64,
+  /// The most recent failure, `undefined` when the function never failed.
+  pub last_error: Option<LastError>,
+}
+
+#[derive(Default)]
+struct Counters {
+  successes: u64,
+  failures: u64,
+  last_error: Option<LastError>,
+}
+
+/// The counters of the functions changing priorities, keyed by their JavaScript name.
+static STATS: Mutex<BTreeMap<&'static str, Counters>> = Mutex::new(BTreeMap::new());
+
+/// Calls `f` and counts whether it succeeded or failed under `function`.
+pub(crate) fn record<T>(function: &'static str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+  let result = f();
+  let mut stats = STATS.lock().unwrap_or_else(|err| err.into_inner());
+  let counters = stats.entry(function).or_default();
+  match &result {
+    Ok(_) => counters.successes += 1,
+    Err(err) => {
+      counters.failures += 1;
+      counters.last_error = Some(LastError {
+        message: err.reason.clone(),
+        code: err.status.as_ref().to_owned(),
+        timestamp: std::time::SystemTime::now()
+          .duration_since(std::time::UNIX_EPOCH)
+          .map_or(0.0, |elapsed| elapsed.as_millis() as f64),
+      });
+    }
+  }
+  result
+}
+
+#[napi]
+/// This function returns how many times each function changing priorities succeeded and failed since the addon was loaded or `resetStats` was called, and its last error, so libraries can report how often priority changes are rejected in production.
+/// Only the functions which were called are returned, sorted by name, and every function, constructor and method setting priorities, affinities, cgroups or job limits is counted under the name it was called with, such as `nice`, `PriorityGuard` or `JobObject.setLimits`, even when it's implemented on top of another one, e.g. `nice('idle')` only counts as `nice`.
+/// The methods undoing a previous change, such as `restore()` or `revert()`, and `beginActivity` are not counted.
+/// The counters are shared by every thread of the process, including worker threads.
+pub fn get_stats() -> Vec<FunctionStats> {
+  let stats = STATS.lock().unwrap_or_else(|err| err.into_inner());
+  stats
+    .iter()
+    .map(|(function, counters)| FunctionStats {
+      function: (*function).to_owned(),
+      successes: counters.successes as f64,
+      failures: counters.failures as f64,
+      last_error: counters.last_error.clone(),
+    })
+    .collect()
+}
+
+#[napi]
+/// This function resets the counters returned by `getStats` and exported by `getMetricsText`.
+pub fn reset_stats() {
+  STATS.lock().unwrap_or_else(|err| err.into_inner()).clear();
+}
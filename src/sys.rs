@@ -0,0 +1,232 @@
+use napi::{Error, Status};
+
+#[cfg(unix)]
+extern "C" {
+  #[cfg(not(any(target_os = "dragonfly", target_os = "vxworks")))]
+  #[cfg_attr(
+    any(
+      target_os = "linux",
+      target_os = "emscripten",
+      target_os = "fuchsia",
+      target_os = "l4re",
+      target_os = "hurd",
+    ),
+    link_name = "__errno_location"
+  )]
+  #[cfg_attr(
+    any(
+      target_os = "netbsd",
+      target_os = "openbsd",
+      target_os = "android",
+      target_os = "redox",
+      target_env = "newlib"
+    ),
+    link_name = "__errno"
+  )]
+  #[cfg_attr(
+    any(target_os = "solaris", target_os = "illumos"),
+    link_name = "___errno"
+  )]
+  #[cfg_attr(target_os = "nto", link_name = "__get_errno_ptr")]
+  #[cfg_attr(
+    any(target_os = "freebsd", target_vendor = "apple"),
+    link_name = "__error"
+  )]
+  #[cfg_attr(target_os = "haiku", link_name = "_errnop")]
+  #[cfg_attr(target_os = "aix", link_name = "_Errno")]
+  fn errno_location() -> *mut core::ffi::c_int;
+}
+
+#[cfg(target_vendor = "apple")]
+extern "C" {
+  pub(crate) fn mach_port_deallocate(
+    task: libc::mach_port_t,
+    name: libc::mach_port_t,
+  ) -> libc::kern_return_t;
+  /// What the `mach_task_self()` macro reads.
+  pub(crate) static mach_task_self_: libc::mach_port_t;
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+pub(crate) type PriorityWhich = libc::__priority_which_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+pub(crate) type PriorityWhich = libc::c_int;
+
+#[cfg(unix)]
+/// `getpriority(2)` can legitimately return `-1`, so `errno` is cleared before the call and rechecked after it.
+pub(crate) fn getpriority(which: PriorityWhich, who: libc::id_t) -> std::io::Result<i32> {
+  unsafe {
+    // clear the last error
+    *errno_location() = 0;
+    let ret = libc::getpriority(which, who as _);
+    // recheck the os error
+    let os_error = std::io::Error::last_os_error();
+    if let Some(err) = os_error.raw_os_error() {
+      if err != 0 {
+        return Err(os_error);
+      }
+    };
+    Ok(ret)
+  }
+}
+
+#[cfg(unix)]
+pub(crate) fn setpriority(
+  which: PriorityWhich,
+  who: libc::id_t,
+  value: i32,
+) -> std::io::Result<()> {
+  if unsafe { libc::setpriority(which, who as _, value) } == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+/// `windows::core::Error` stores Win32 errors as `HRESULT`s, unwrap them so that `std::io::ErrorKind` is meaningful.
+pub(crate) fn win32_error(err: windows::core::Error) -> std::io::Error {
+  let code = err.code().0 as u32;
+  if code & 0xFFFF_0000 == 0x8007_0000 {
+    std::io::Error::from_raw_os_error((code & 0xFFFF) as i32)
+  } else {
+    std::io::Error::from_raw_os_error(code as i32)
+  }
+}
+
+#[cfg(windows)]
+/// Closes the wrapped handle when dropped.
+pub(crate) struct OwnedHandle(pub(crate) windows::Win32::Foundation::HANDLE);
+
+#[cfg(windows)]
+impl Drop for OwnedHandle {
+  fn drop(&mut self) {
+    unsafe {
+      let _ = windows::Win32::Foundation::CloseHandle(self.0);
+    }
+  }
+}
+
+#[cfg(windows)]
+pub(crate) fn open_process(
+  access: windows::Win32::System::Threading::PROCESS_ACCESS_RIGHTS,
+  pid: u32,
+) -> std::io::Result<OwnedHandle> {
+  unsafe { windows::Win32::System::Threading::OpenProcess(access, false, pid) }
+    .map(OwnedHandle)
+    .map_err(win32_error)
+}
+
+#[cfg(windows)]
+pub(crate) fn current_thread_priority() -> std::io::Result<i32> {
+  use windows::Win32::System::Threading::{GetCurrentThread, GetThreadPriority};
+  use windows::Win32::System::WindowsProgramming::THREAD_PRIORITY_ERROR_RETURN;
+
+  let ret = unsafe { GetThreadPriority(GetCurrentThread()) };
+  if ret == THREAD_PRIORITY_ERROR_RETURN as i32 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(ret)
+}
+
+/// Turns an OS error into a JavaScript error, with a hint when the failure is caused by missing privileges.
+pub(crate) fn priority_error(err: std::io::Error, action: &str) -> Error {
+  if err.kind() == std::io::ErrorKind::PermissionDenied {
+    #[cfg(unix)]
+    let hint = "raising the priority or changing the priority of processes owned by other users requires root or CAP_SYS_NICE";
+    #[cfg(windows)]
+    let hint = "the target is owned by another user or requires administrator privileges";
+    return Error::new(
+      Status::GenericFailure,
+      format!("Permission denied to {action}, {hint}: {err}"),
+    );
+  }
+  Error::new(Status::GenericFailure, format!("Failed to {action}: {err}"))
+}
+
+pub(crate) fn unsupported(feature: &str) -> Error {
+  Error::new(
+    Status::GenericFailure,
+    format!("{feature} is not supported on this platform"),
+  )
+}
+
+#[cfg(windows)]
+pub(crate) fn open_thread(
+  access: windows::Win32::System::Threading::THREAD_ACCESS_RIGHTS,
+  tid: u32,
+) -> std::io::Result<OwnedHandle> {
+  unsafe { windows::Win32::System::Threading::OpenThread(access, false, tid) }
+    .map(OwnedHandle)
+    .map_err(win32_error)
+}
+
+#[cfg(target_vendor = "apple")]
+/// Reads a numeric `sysctl`, which is either a 32-bit integer or an array of 64-bit integers.
+pub(crate) fn sysctl_numbers(name: &std::ffi::CStr) -> std::io::Result<Vec<u64>> {
+  let mut length = 0;
+  if unsafe {
+    libc::sysctlbyname(
+      name.as_ptr(),
+      std::ptr::null_mut(),
+      &mut length,
+      std::ptr::null_mut(),
+      0,
+    )
+  } == -1
+  {
+    return Err(std::io::Error::last_os_error());
+  }
+  let mut buffer = vec![0u8; length];
+  if unsafe {
+    libc::sysctlbyname(
+      name.as_ptr(),
+      buffer.as_mut_ptr().cast(),
+      &mut length,
+      std::ptr::null_mut(),
+      0,
+    )
+  } == -1
+  {
+    return Err(std::io::Error::last_os_error());
+  }
+  buffer.truncate(length);
+  if length == 4 {
+    return Ok(vec![
+      u32::from_ne_bytes(buffer[..4].try_into().unwrap()) as u64
+    ]);
+  }
+  Ok(
+    buffer
+      .chunks_exact(8)
+      .map(|chunk| u64::from_ne_bytes(chunk.try_into().unwrap()))
+      .collect(),
+  )
+}
+
+#[cfg(windows)]
+#[link(name = "ntdll")]
+extern "system" {
+  /// Missing from the `windows` crate, see [`NtSetInformationProcess`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntsetinformationprocess).
+  pub(crate) fn NtSetInformationProcess(
+    process: windows::Win32::Foundation::HANDLE,
+    class: windows::Wdk::System::Threading::PROCESSINFOCLASS,
+    information: *const core::ffi::c_void,
+    length: u32,
+  ) -> windows::Win32::Foundation::NTSTATUS;
+  /// Missing from the `windows` crate, the undocumented counterpart of `NtQueryTimerResolution`.
+  pub(crate) fn NtSetTimerResolution(
+    desired: u32,
+    set: windows::Win32::Foundation::BOOLEAN,
+    current: *mut u32,
+  ) -> windows::Win32::Foundation::NTSTATUS;
+}
+
+#[cfg(windows)]
+/// Turns an `NTSTATUS` into the matching Win32 error, so that `std::io::ErrorKind` is meaningful.
+pub(crate) fn nt_result(status: windows::Win32::Foundation::NTSTATUS) -> std::io::Result<()> {
+  if status.is_ok() {
+    return Ok(());
+  }
+  let code = unsafe { windows::Win32::Foundation::RtlNtStatusToDosError(status) };
+  Err(std::io::Error::from_raw_os_error(code as i32))
+}
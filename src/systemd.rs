@@ -0,0 +1,193 @@
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Error, Result, Status, Task};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi(object)]
+#[derive(Default)]
+pub struct SystemdUnitOptions {
+  /// The unit to change, such as `worker.service`, it defaults to the unit of the current process.
+  pub unit: Option<String>,
+  /// Whether the unit is managed by the user manager instead of the system manager, it defaults to whether the current process runs under the user manager.
+  pub user: Option<bool>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Finds the unit of the current process and whether it's managed by the user manager, from its cgroup path such as `/user.slice/user-1000.slice/user@1000.service/app.slice/app.service`.
+fn calling_unit() -> std::io::Result<Option<(String, bool)>> {
+  let cgroups = std::fs::read_to_string("/proc/self/cgroup")?;
+  // the unified hierarchy on cgroup v2 hosts, the named systemd hierarchy on cgroup v1 hosts
+  let path = cgroups
+    .lines()
+    .find_map(|line| line.strip_prefix("0::"))
+    .filter(|path| *path != "/")
+    .or_else(|| {
+      cgroups
+        .lines()
+        .find_map(|line| line.split_once(":name=systemd:").map(|(_, path)| path))
+    });
+  Ok(path.and_then(|path| {
+    let user = path.split('/').any(|name| name.starts_with("user@"));
+    path
+      .rsplit('/')
+      .find(|name| name.ends_with(".service") || name.ends_with(".scope"))
+      .map(|unit| (unit.to_owned(), user))
+  }))
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Sets a `u64` property of a unit at runtime with the `SetUnitProperties` D-Bus method of the systemd manager, through `busctl`.
+/// It blocks until the manager replies, and polkit is told not to prompt for a password, so it fails instead of waiting for an agent.
+fn set_unit_property(property: &str, value: u32, options: SystemdUnitOptions) -> Result<()> {
+  let calling_unit = calling_unit()
+    .map_err(|err| sys::priority_error(err, "resolve the systemd unit of the current process"))?;
+  let user = options
+    .user
+    .or(calling_unit.as_ref().map(|(_, user)| *user))
+    .unwrap_or(false);
+  let unit = match (options.unit, calling_unit) {
+    (Some(unit), _) if unit.is_empty() => {
+      return Err(Error::new(
+        Status::InvalidArg,
+        "unit must not be empty".to_owned(),
+      ))
+    }
+    (Some(unit), _) => unit,
+    (None, Some((unit, _))) => unit,
+    (None, None) => {
+      return Err(Error::new(
+        Status::GenericFailure,
+        "The current process doesn't run in a systemd service or scope".to_owned(),
+      ))
+    }
+  };
+  let manager = if user { "user" } else { "system" };
+  let action = format!("set {property} of {unit} in the {manager} manager");
+  let mut command = std::process::Command::new("busctl");
+  if user {
+    command.arg("--user");
+  }
+  command.arg("--allow-interactive-authorization=false");
+  let output = command
+    .args([
+      "--",
+      "call",
+      "org.freedesktop.systemd1",
+      "/org/freedesktop/systemd1",
+      "org.freedesktop.systemd1.Manager",
+      "SetUnitProperties",
+      // unit name, runtime only, and an array with a single property
+      "sba(sv)",
+      &unit,
+      "true",
+      "1",
+      property,
+      "t",
+      &value.to_string(),
+    ])
+    .output()
+    .map_err(|err| {
+      if err.kind() == std::io::ErrorKind::NotFound {
+        Error::new(
+          Status::GenericFailure,
+          format!("Failed to {action}, busctl is not available, systemd is not installed: {err}"),
+        )
+      } else {
+        sys::priority_error(err, &action)
+      }
+    })?;
+  if output.status.success() {
+    return Ok(());
+  }
+  Err(Error::new(
+    Status::GenericFailure,
+    format!(
+      "Failed to {action}: {}",
+      String::from_utf8_lossy(&output.stderr).trim()
+    ),
+  ))
+}
+
+fn validate_weight(weight: u32, property: &str) -> Result<()> {
+  if !(1..=10_000).contains(&weight) {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("{weight} is not a valid {property}, expected a value between 1 and 10000"),
+    ));
+  }
+  Ok(())
+}
+
+pub struct SystemdUnitPropertyTask {
+  function: &'static str,
+  property: &'static str,
+  weight: u32,
+  options: Option<SystemdUnitOptions>,
+}
+
+impl Task for SystemdUnitPropertyTask {
+  type Output = ();
+  type JsValue = ();
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    crate::stats::record(self.function, || {
+      validate_weight(self.weight, self.property)?;
+      #[cfg(any(target_os = "linux", target_os = "android"))]
+      {
+        set_unit_property(
+          self.property,
+          self.weight,
+          self.options.take().unwrap_or_default(),
+        )
+      }
+      #[cfg(not(any(target_os = "linux", target_os = "android")))]
+      {
+        let _ = &self.options;
+        Err(sys::unsupported("systemd"))
+      }
+    })
+  }
+
+  fn resolve(&mut self, _env: Env, _output: Self::Output) -> Result<Self::JsValue> {
+    Ok(())
+  }
+}
+
+#[napi(ts_return_type = "Promise<void>")]
+/// This function sets the `CPUWeight` of a systemd unit at runtime, it defaults to the unit of the current process, so services can adjust their own share of CPU time without writing to the cgroup filesystem.
+/// `weight` ranges from `1` to `10000`, and `100` is the default.
+///
+/// On Linux, it calls the [`SetUnitProperties`](https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.systemd1.html) D-Bus method of the systemd manager with `busctl` on a libuv thread pool thread, which is allowed for the units of the user manager, and requires root or a polkit authorization for the units of the system manager. Interactive polkit authorization is disabled, so the returned `Promise` rejects instead of waiting for a password prompt.
+///
+/// On other platforms, the returned `Promise` rejects.
+pub fn set_systemd_unit_cpu_weight(
+  weight: u32,
+  options: Option<SystemdUnitOptions>,
+) -> AsyncTask<SystemdUnitPropertyTask> {
+  AsyncTask::new(SystemdUnitPropertyTask {
+    function: "setSystemdUnitCpuWeight",
+    property: "CPUWeight",
+    weight,
+    options,
+  })
+}
+
+#[napi(ts_return_type = "Promise<void>")]
+/// This function sets the `IOWeight` of a systemd unit at runtime, it defaults to the unit of the current process.
+/// `weight` ranges from `1` to `10000`, and `100` is the default, it only applies to block devices using an I/O scheduler that supports weights, such as BFQ.
+///
+/// On Linux, it calls the [`SetUnitProperties`](https://www.freedesktop.org/software/systemd/man/latest/org.freedesktop.systemd1.html) D-Bus method of the systemd manager with `busctl` on a libuv thread pool thread, with the same permissions as `setSystemdUnitCpuWeight`.
+///
+/// On other platforms, the returned `Promise` rejects.
+pub fn set_systemd_unit_io_weight(
+  weight: u32,
+  options: Option<SystemdUnitOptions>,
+) -> AsyncTask<SystemdUnitPropertyTask> {
+  AsyncTask::new(SystemdUnitPropertyTask {
+    function: "setSystemdUnitIoWeight",
+    property: "IOWeight",
+    weight,
+    options,
+  })
+}
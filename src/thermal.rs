@@ -0,0 +1,154 @@
+use napi::bindgen_prelude::Function;
+use napi::Result;
+use napi_derive::napi;
+
+use crate::sys;
+use crate::watch::{spawn_watcher, WatchOptions, Watcher};
+
+#[napi]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+/// How hot the machine is, from the coolest to the hottest, mirroring [`NSProcessInfo.ThermalState`](https://developer.apple.com/documentation/foundation/processinfo/thermalstate).
+pub enum ThermalState {
+  Nominal = 0,
+  /// Slightly elevated, the system starts to take measures such as spinning up the fans.
+  Fair = 1,
+  /// High, the performance is throttled and the work should be reduced.
+  Serious = 2,
+  /// Significantly impacting the performance, the work should be stopped.
+  Critical = 3,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_millidegrees(path: &std::path::Path) -> Option<i64> {
+  std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_thermal_state() -> std::io::Result<ThermalState> {
+  let zones = match std::fs::read_dir("/sys/class/thermal") {
+    Ok(zones) => zones,
+    // machines without thermal zones, such as most virtual machines, never get hot
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(ThermalState::Nominal),
+    Err(err) => return Err(err),
+  };
+  let mut state = ThermalState::Nominal;
+  for zone in zones {
+    let zone = zone?.path();
+    if !zone
+      .file_name()
+      .and_then(|name| name.to_str())
+      .is_some_and(|name| name.starts_with("thermal_zone"))
+    {
+      continue;
+    }
+    let Some(temperature) = read_millidegrees(&zone.join("temp")) else {
+      continue;
+    };
+    for trip in 0.. {
+      let Ok(kind) = std::fs::read_to_string(zone.join(format!("trip_point_{trip}_type"))) else {
+        break;
+      };
+      let reached = match kind.trim() {
+        "critical" => ThermalState::Critical,
+        "hot" => ThermalState::Serious,
+        "passive" => ThermalState::Fair,
+        // `active` trip points only start fans
+        _ => continue,
+      };
+      if read_millidegrees(&zone.join(format!("trip_point_{trip}_temp")))
+        .is_some_and(|trip_temperature| trip_temperature > 0 && temperature >= trip_temperature)
+      {
+        state = state.max(reached);
+      }
+    }
+  }
+  Ok(state)
+}
+
+#[cfg(target_vendor = "apple")]
+fn read_thermal_state() -> std::io::Result<ThermalState> {
+  use crate::objc::{self, msg_send};
+
+  let state: isize = objc::with_autorelease_pool(|| unsafe {
+    msg_send!(objc::process_info(), c"thermalState"; isize)
+  });
+  Ok(match state {
+    0 => ThermalState::Nominal,
+    1 => ThermalState::Fair,
+    2 => ThermalState::Serious,
+    _ => ThermalState::Critical,
+  })
+}
+
+#[cfg(windows)]
+fn read_thermal_state() -> std::io::Result<ThermalState> {
+  use windows::Win32::System::Power::{
+    CallNtPowerInformation, SystemPowerInformation, PO_TZ_PASSIVE, SYSTEM_POWER_INFORMATION,
+  };
+
+  let mut information: SYSTEM_POWER_INFORMATION = unsafe { std::mem::zeroed() };
+  sys::nt_result(unsafe {
+    CallNtPowerInformation(
+      SystemPowerInformation,
+      None,
+      0,
+      Some(&mut information as *mut _ as *mut core::ffi::c_void),
+      std::mem::size_of::<SYSTEM_POWER_INFORMATION>() as u32,
+    )
+  })?;
+  // passive cooling means the CPUs are throttled to cool down
+  Ok(if information.CoolingMode == PO_TZ_PASSIVE {
+    ThermalState::Serious
+  } else {
+    ThermalState::Nominal
+  })
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  target_vendor = "apple",
+  windows
+)))]
+fn read_thermal_state() -> std::io::Result<ThermalState> {
+  Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+}
+
+#[napi]
+/// This function returns how hot the machine is, so apps can lower their priority or shed load before the system throttles them.
+///
+/// On Linux, it compares the temperature of each `/sys/class/thermal/thermal_zone*` with its `passive` (`Fair`), `hot` (`Serious`) and `critical` trip points, it's always `Nominal` without thermal zones.
+///
+/// On macOS, it uses the [`thermalState`](https://developer.apple.com/documentation/foundation/processinfo/thermalstate) property of `NSProcessInfo`.
+///
+/// On Windows, it uses the [`CallNtPowerInformation`](https://learn.microsoft.com/en-us/windows/win32/api/powerbase/nf-powerbase-callntpowerinformation) function with `SystemPowerInformation`, the state is `Serious` when the system cools down by throttling the CPUs and `Nominal` otherwise.
+///
+/// On other platforms, it throws.
+pub fn get_thermal_state() -> Result<ThermalState> {
+  read_thermal_state().map_err(|err| {
+    if err.kind() == std::io::ErrorKind::Unsupported {
+      sys::unsupported("Getting the thermal state")
+    } else {
+      sys::priority_error(err, "get the thermal state")
+    }
+  })
+}
+
+#[napi]
+/// This function calls `callback` with the new thermal state whenever `getThermalState()` changes, checking it every `interval`.
+/// Windows has no notification for passive cooling, so it's checked the same way on every platform.
+///
+/// On other platforms than Linux, macOS and Windows, it throws.
+pub fn on_thermal_state_change(
+  callback: Function<ThermalState, ()>,
+  options: Option<WatchOptions>,
+) -> Result<Watcher> {
+  let mut last = get_thermal_state()?;
+  spawn_watcher(callback, WatchOptions::interval(options)?, move || {
+    let state = read_thermal_state().ok()?;
+    (state != last).then(|| {
+      last = state;
+      state
+    })
+  })
+}
@@ -0,0 +1,879 @@
+use napi::Result;
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi]
+/// This function set the priority of the thread identified by the native thread id `tid`.
+///
+/// On Linux, threads have their own nice value, it uses the [`setpriority(2)`](https://linux.die.net/man/2/setpriority) function with `PRIO_PROCESS` and the thread id, `value` is the new nice value.
+///
+/// On Windows, it uses the [`OpenThread`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-openthread) and [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) functions, `value` must be a `WindowsThreadPriority`.
+///
+/// This function always throws on other platforms.
+pub fn set_thread_priority(tid: u32, value: i32) -> Result<()> {
+  crate::stats::record("setThreadPriority", || {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      crate::process::set_process_nice(tid as libc::id_t, value)
+        .map_err(|err| sys::priority_error(err, &format!("set the priority of thread {tid}")))
+    }
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{SetThreadPriority, THREAD_SET_LIMITED_INFORMATION};
+
+      let priority: crate::WindowsThreadPriority = value.try_into()?;
+      let action = || format!("set the priority of thread {tid}");
+      let thread = sys::open_thread(THREAD_SET_LIMITED_INFORMATION, tid)
+        .map_err(|err| sys::priority_error(err, &action()))?;
+      unsafe { SetThreadPriority(thread.0, priority.into()) }
+        .map_err(|err| sys::priority_error(sys::win32_error(err), &action()))
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android", windows)))]
+    {
+      let _ = (tid, value);
+      Err(sys::unsupported("Setting the priority of another thread"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the native id of the calling thread, which can be passed to other threads for targeted renicing.
+///
+/// On Linux, it uses the `gettid` syscall.
+///
+/// On macOS, it uses the `pthread_threadid_np` function.
+///
+/// On FreeBSD, it uses the [`pthread_getthreadid_np(3)`](https://man.freebsd.org/cgi/man.cgi?query=pthread_getthreadid_np) function.
+///
+/// On Windows, it uses the [`GetCurrentThreadId`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getcurrentthreadid) function.
+pub fn get_native_thread_id() -> Result<i64> {
+  Ok(current_thread_id()?)
+}
+
+/// The `gettid` wrapper was only added in glibc 2.30, so the raw syscall is used instead.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn current_thread_id() -> std::io::Result<i64> {
+  Ok(unsafe { libc::syscall(libc::SYS_gettid) } as i64)
+}
+
+#[cfg(target_vendor = "apple")]
+pub(crate) fn current_thread_id() -> std::io::Result<i64> {
+  let mut tid = 0u64;
+  let ret = unsafe { libc::pthread_threadid_np(0, &mut tid) };
+  if ret != 0 {
+    return Err(std::io::Error::from_raw_os_error(ret));
+  }
+  Ok(tid as i64)
+}
+
+#[cfg(target_os = "freebsd")]
+pub(crate) fn current_thread_id() -> std::io::Result<i64> {
+  Ok(unsafe { libc::pthread_getthreadid_np() } as i64)
+}
+
+#[cfg(windows)]
+pub(crate) fn current_thread_id() -> std::io::Result<i64> {
+  Ok(unsafe { windows::Win32::System::Threading::GetCurrentThreadId() } as i64)
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "android",
+  target_vendor = "apple",
+  target_os = "freebsd",
+  windows
+)))]
+pub(crate) fn current_thread_id() -> std::io::Result<i64> {
+  Err(std::io::Error::new(
+    std::io::ErrorKind::Unsupported,
+    "Native thread id is not supported on this platform",
+  ))
+}
+
+/// Lists the native ids of all threads of the process `pid`, `0` means the current process.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn list_thread_ids(pid: u32) -> std::io::Result<Vec<u32>> {
+  let task_dir = if pid == 0 {
+    "/proc/self/task".to_owned()
+  } else {
+    format!("/proc/{pid}/task")
+  };
+  let mut tids = Vec::new();
+  for entry in std::fs::read_dir(task_dir)? {
+    if let Some(tid) = entry?
+      .file_name()
+      .to_str()
+      .and_then(|name| name.parse().ok())
+    {
+      tids.push(tid);
+    }
+  }
+  Ok(tids)
+}
+
+/// Lists the native ids of all threads of the process `pid`, `0` means the current process.
+#[cfg(windows)]
+pub(crate) fn list_thread_ids(pid: u32) -> std::io::Result<Vec<u32>> {
+  use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+  };
+  use windows::Win32::System::Threading::GetCurrentProcessId;
+
+  let pid = if pid == 0 {
+    unsafe { GetCurrentProcessId() }
+  } else {
+    pid
+  };
+  let snapshot = sys::OwnedHandle(
+    unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0) }.map_err(sys::win32_error)?,
+  );
+  let mut entry = THREADENTRY32 {
+    dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+    ..Default::default()
+  };
+  let mut tids = Vec::new();
+  let mut next = unsafe { Thread32First(snapshot.0, &mut entry) };
+  while next.is_ok() {
+    if entry.th32OwnerProcessID == pid {
+      tids.push(entry.th32ThreadID);
+    }
+    next = unsafe { Thread32Next(snapshot.0, &mut entry) };
+  }
+  Ok(tids)
+}
+
+#[napi]
+/// This function set the priority of every thread of the current process, including the libuv and V8 worker threads, and returns the number of updated threads.
+///
+/// On Linux, every thread has its own nice value, it walks `/proc/self/task` and applies [`setpriority(2)`](https://linux.die.net/man/2/setpriority) to each thread id.
+///
+/// On Windows, it enumerates the threads with [`CreateToolhelp32Snapshot`](https://learn.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-createtoolhelp32snapshot) and applies [`SetThreadPriority`](https://docs.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriority) to each of them, `value` must be a `WindowsThreadPriority`.
+///
+/// On other Unix platforms, the nice value belongs to the process, so it uses `setpriority(2)` with `PRIO_PROCESS` once and returns `1`.
+pub fn set_all_threads_priority(value: i32) -> Result<u32> {
+  crate::stats::record("setAllThreadsPriority", || {
+    apply_all_threads_priority(value)
+  })
+}
+
+pub(crate) fn apply_all_threads_priority(value: i32) -> Result<u32> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let tids = list_thread_ids(0)
+      .map_err(|err| sys::priority_error(err, "list the threads of the current process"))?;
+    let mut updated = 0;
+    for tid in tids {
+      match crate::process::set_process_nice(tid as libc::id_t, value) {
+        Ok(()) => updated += 1,
+        // the thread exited after it was listed
+        Err(err) if err.raw_os_error() == Some(libc::ESRCH) => {}
+        Err(err) => {
+          return Err(sys::priority_error(
+            err,
+            &format!("set the priority of thread {tid}"),
+          ))
+        }
+      }
+    }
+    Ok(updated)
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::Foundation::ERROR_INVALID_PARAMETER;
+    use windows::Win32::System::Threading::{SetThreadPriority, THREAD_SET_LIMITED_INFORMATION};
+
+    let priority: crate::WindowsThreadPriority = value.try_into()?;
+    let tids = list_thread_ids(0)
+      .map_err(|err| sys::priority_error(err, "list the threads of the current process"))?;
+    let mut updated = 0;
+    for tid in tids {
+      let action = || format!("set the priority of thread {tid}");
+      let thread = match sys::open_thread(THREAD_SET_LIMITED_INFORMATION, tid) {
+        Ok(thread) => thread,
+        // the thread exited after it was listed
+        Err(err) if err.raw_os_error() == Some(ERROR_INVALID_PARAMETER.0 as i32) => continue,
+        Err(err) => return Err(sys::priority_error(err, &action())),
+      };
+      unsafe { SetThreadPriority(thread.0, priority.into()) }
+        .map_err(|err| sys::priority_error(sys::win32_error(err), &action()))?;
+      updated += 1;
+    }
+    Ok(updated)
+  }
+  #[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+  {
+    crate::process::set_process_nice(0, value)
+      .map_err(|err| sys::priority_error(err, "set the priority of the current process"))?;
+    Ok(1)
+  }
+}
+
+#[napi]
+/// This function yields the processor to another thread which is ready to run, so spin-wait loops can cooperate with the scheduler.
+///
+/// On Unix, it uses the [`sched_yield(2)`](https://man7.org/linux/man-pages/man2/sched_yield.2.html) function.
+///
+/// On Windows, it uses the [`SwitchToThread`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-switchtothread) function.
+///
+/// It returns `false` when there was no other thread to switch to. On Unix, it always returns `true`.
+pub fn yield_now() -> Result<bool> {
+  #[cfg(unix)]
+  {
+    if unsafe { libc::sched_yield() } == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "yield the processor",
+      ));
+    }
+    Ok(true)
+  }
+  #[cfg(windows)]
+  {
+    Ok(unsafe { windows::Win32::System::Threading::SwitchToThread() }.as_bool())
+  }
+}
+
+#[cfg(target_vendor = "apple")]
+/// Mach calls return a `kern_return_t` instead of setting `errno`.
+pub(crate) fn kern_error(ret: libc::kern_return_t, action: &str) -> napi::Error {
+  napi::Error::new(
+    napi::Status::GenericFailure,
+    format!("Failed to {action}: kern_return_t {ret}"),
+  )
+}
+
+#[napi]
+/// This function set the affinity tag of the calling thread, threads sharing the same non-zero tag are hinted to run on CPUs sharing an L2 cache, and `0` removes the hint.
+/// It returns `false` when the machine doesn't support affinity tags, which is the case on Apple silicon.
+///
+/// On macOS, it uses the [`thread_policy_set`](https://developer.apple.com/library/archive/releasenotes/Performance/RN-AffinityAPI/) function with `THREAD_AFFINITY_POLICY`.
+///
+/// On other platforms, it throws.
+pub fn set_thread_affinity_tag(tag: u32) -> Result<bool> {
+  crate::stats::record("setThreadAffinityTag", || {
+    #[cfg(target_vendor = "apple")]
+    {
+      let mut policy = libc::thread_affinity_policy_data_t {
+        affinity_tag: tag as libc::integer_t,
+      };
+      let ret = unsafe {
+        libc::thread_policy_set(
+          libc::pthread_mach_thread_np(libc::pthread_self()),
+          libc::THREAD_AFFINITY_POLICY as libc::thread_policy_flavor_t,
+          &mut policy as *mut _ as libc::thread_policy_t,
+          libc::THREAD_AFFINITY_POLICY_COUNT,
+        )
+      };
+      match ret {
+        libc::KERN_SUCCESS => Ok(true),
+        libc::KERN_NOT_SUPPORTED => Ok(false),
+        ret => Err(kern_error(ret, "set the thread affinity tag")),
+      }
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    {
+      let _ = tag;
+      Err(sys::unsupported("Setting the thread affinity tag"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the affinity tag of the calling thread, `0` means there is no tag.
+///
+/// On macOS, it uses the [`thread_policy_get`](https://developer.apple.com/library/archive/releasenotes/Performance/RN-AffinityAPI/) function with `THREAD_AFFINITY_POLICY`.
+///
+/// On other platforms, it throws.
+pub fn get_thread_affinity_tag() -> Result<u32> {
+  #[cfg(target_vendor = "apple")]
+  {
+    let mut policy = libc::thread_affinity_policy_data_t {
+      affinity_tag: libc::THREAD_AFFINITY_TAG_NULL,
+    };
+    let mut count = libc::THREAD_AFFINITY_POLICY_COUNT;
+    let mut get_default: libc::boolean_t = 0;
+    let ret = unsafe {
+      libc::thread_policy_get(
+        libc::pthread_mach_thread_np(libc::pthread_self()),
+        libc::THREAD_AFFINITY_POLICY as libc::thread_policy_flavor_t,
+        &mut policy as *mut _ as libc::thread_policy_t,
+        &mut count,
+        &mut get_default,
+      )
+    };
+    match ret {
+      libc::KERN_SUCCESS => Ok(policy.affinity_tag as u32),
+      libc::KERN_NOT_SUPPORTED => Ok(0),
+      ret => Err(kern_error(ret, "get the thread affinity tag")),
+    }
+  }
+  #[cfg(not(target_vendor = "apple"))]
+  {
+    Err(sys::unsupported("Getting the thread affinity tag"))
+  }
+}
+
+#[napi]
+/// This function opts the calling thread into or out of EcoQoS, so a worker thread can save power while the main thread stays at normal QoS.
+/// With EcoQoS the system runs the thread at a lower clock speed and prefers efficiency cores on hybrid CPUs.
+///
+/// On Windows, it uses the [`SetThreadInformation`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadinformation) function with `ThreadPowerThrottling` and `THREAD_POWER_THROTTLING_EXECUTION_SPEED`.
+///
+/// On other platforms, it throws.
+pub fn set_thread_efficiency_mode(enabled: bool) -> Result<()> {
+  crate::stats::record("setThreadEfficiencyMode", || {
+    #[cfg(windows)]
+    {
+      use windows::Win32::System::Threading::{
+        GetCurrentThread, SetThreadInformation, ThreadPowerThrottling,
+        THREAD_POWER_THROTTLING_CURRENT_VERSION, THREAD_POWER_THROTTLING_EXECUTION_SPEED,
+        THREAD_POWER_THROTTLING_STATE,
+      };
+
+      let state = THREAD_POWER_THROTTLING_STATE {
+        Version: THREAD_POWER_THROTTLING_CURRENT_VERSION,
+        ControlMask: THREAD_POWER_THROTTLING_EXECUTION_SPEED,
+        StateMask: if enabled {
+          THREAD_POWER_THROTTLING_EXECUTION_SPEED
+        } else {
+          0
+        },
+      };
+      unsafe {
+        SetThreadInformation(
+          GetCurrentThread(),
+          ThreadPowerThrottling,
+          &state as *const _ as *const core::ffi::c_void,
+          std::mem::size_of::<THREAD_POWER_THROTTLING_STATE>() as u32,
+        )
+      }
+      .map_err(|err| sys::priority_error(sys::win32_error(err), "set the thread efficiency mode"))
+    }
+    #[cfg(not(windows))]
+    {
+      let _ = enabled;
+      Err(sys::unsupported("Thread efficiency mode"))
+    }
+  })
+}
+
+#[napi]
+/// This function enables or disables the dynamic priority boost of the thread identified by `tid`, it defaults to the calling thread.
+///
+/// On Windows, it uses the [`SetThreadPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-setthreadpriorityboost) function.
+///
+/// On other platforms, it throws.
+pub fn set_thread_priority_boost(enabled: bool, tid: Option<u32>) -> Result<()> {
+  crate::stats::record("setThreadPriorityBoost", || {
+    apply_thread_priority_boost(enabled, tid)
+  })
+}
+
+pub(crate) fn apply_thread_priority_boost(enabled: bool, tid: Option<u32>) -> Result<()> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{
+      GetCurrentThread, SetThreadPriorityBoost, THREAD_SET_INFORMATION,
+    };
+
+    let set = || {
+      let owned = tid
+        .map(|tid| sys::open_thread(THREAD_SET_INFORMATION, tid))
+        .transpose()?;
+      let thread = owned
+        .as_ref()
+        .map_or_else(|| unsafe { GetCurrentThread() }, |thread| thread.0);
+      unsafe { SetThreadPriorityBoost(thread, !enabled) }.map_err(sys::win32_error)
+    };
+    set().map_err(|err| sys::priority_error(err, "set the thread priority boost"))
+  }
+  #[cfg(not(windows))]
+  {
+    let _ = (enabled, tid);
+    Err(sys::unsupported("Thread priority boost"))
+  }
+}
+
+#[napi]
+/// This function returns whether the dynamic priority boost is enabled for the thread identified by `tid`, it defaults to the calling thread.
+///
+/// On Windows, it uses the [`GetThreadPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadpriorityboost) function.
+///
+/// On other platforms, it throws.
+pub fn get_thread_priority_boost(tid: Option<u32>) -> Result<bool> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::Foundation::BOOL;
+    use windows::Win32::System::Threading::{
+      GetCurrentThread, GetThreadPriorityBoost, THREAD_QUERY_LIMITED_INFORMATION,
+    };
+
+    let get = || {
+      let owned = tid
+        .map(|tid| sys::open_thread(THREAD_QUERY_LIMITED_INFORMATION, tid))
+        .transpose()?;
+      let thread = owned
+        .as_ref()
+        .map_or_else(|| unsafe { GetCurrentThread() }, |thread| thread.0);
+      let mut disabled = BOOL::default();
+      unsafe { GetThreadPriorityBoost(thread, &mut disabled) }.map_err(sys::win32_error)?;
+      Ok(!disabled.as_bool())
+    };
+    get().map_err(|err| sys::priority_error(err, "get the thread priority boost"))
+  }
+  #[cfg(not(windows))]
+  {
+    let _ = tid;
+    Err(sys::unsupported("Thread priority boost"))
+  }
+}
+
+#[napi(object)]
+pub struct ThreadSchedulingInfo {
+  /// Whether the dynamic priority boost is enabled, see `setThreadPriorityBoost`.
+  pub boost_enabled: bool,
+  /// Whether the thread is in background processing mode, see `isInBackgroundMode`.
+  pub background_mode: bool,
+  /// The base priority from `0` to `31`, which combines the priority class of the process and the relative priority of the thread.
+  pub base_priority: i32,
+  /// The current priority from `0` to `31`, which is higher than `basePriority` while the thread is boosted.
+  pub current_priority: i32,
+}
+
+#[cfg(windows)]
+/// `THREAD_BASIC_INFORMATION` from `ntddk.h`, which is not in the `windows` crate.
+#[repr(C)]
+pub(crate) struct ThreadBasicInformation {
+  exit_status: windows::Win32::Foundation::NTSTATUS,
+  teb_base_address: *mut core::ffi::c_void,
+  client_id: windows::Win32::System::WindowsProgramming::CLIENT_ID,
+  pub(crate) affinity_mask: usize,
+  priority: i32,
+  base_priority: i32,
+}
+
+#[cfg(windows)]
+/// Reads `ThreadBasicInformation` with `NtQueryInformationThread`, the handle needs `THREAD_QUERY_LIMITED_INFORMATION`.
+pub(crate) fn thread_basic_information(
+  thread: windows::Win32::Foundation::HANDLE,
+) -> std::io::Result<ThreadBasicInformation> {
+  use windows::Wdk::System::Threading::{NtQueryInformationThread, ThreadBasicInformation};
+
+  let mut basic: ThreadBasicInformation = unsafe { std::mem::zeroed() };
+  let mut length = 0;
+  sys::nt_result(unsafe {
+    NtQueryInformationThread(
+      thread,
+      ThreadBasicInformation,
+      &mut basic as *mut _ as *mut _,
+      std::mem::size_of::<ThreadBasicInformation>() as u32,
+      &mut length,
+    )
+  })?;
+  Ok(basic)
+}
+
+#[napi]
+/// This function returns whether the dynamic priority boost is enabled, whether background processing mode is on, and the base and current priorities of the thread identified by `tid`, it defaults to the calling thread.
+/// The relative priority returned by `GetThreadPriority` alone hides the priority class of the process, the dynamic boosts and the background mode.
+///
+/// On Windows, it uses the [`NtQueryInformationThread`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationthread) function with `ThreadBasicInformation`, `ThreadIoPriority` and `ThreadPagePriority`, the [`NtQueryInformationProcess`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntqueryinformationprocess) function with `ProcessBasicInformation` and the [`GetThreadPriorityBoost`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadpriorityboost) function.
+///
+/// On other platforms, it throws.
+pub fn get_thread_scheduling_info(tid: Option<u32>) -> Result<ThreadSchedulingInfo> {
+  #[cfg(windows)]
+  {
+    use windows::Wdk::System::Threading::{NtQueryInformationProcess, ProcessBasicInformation};
+    use windows::Win32::Foundation::{BOOL, HANDLE};
+    use windows::Win32::System::Threading::{
+      GetCurrentProcess, GetCurrentProcessId, GetCurrentThread, GetThreadPriorityBoost,
+      PROCESS_BASIC_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION,
+      THREAD_QUERY_LIMITED_INFORMATION,
+    };
+
+    let get = || {
+      let owned = tid
+        .map(|tid| sys::open_thread(THREAD_QUERY_LIMITED_INFORMATION, tid))
+        .transpose()?;
+      let thread = owned
+        .as_ref()
+        .map_or_else(|| unsafe { GetCurrentThread() }, |thread| thread.0);
+      let basic = thread_basic_information(thread)?;
+      let mut disabled = BOOL::default();
+      unsafe { GetThreadPriorityBoost(thread, &mut disabled) }.map_err(sys::win32_error)?;
+
+      let pid = basic.client_id.UniqueProcess.0 as u32;
+      let owned_process = (pid != unsafe { GetCurrentProcessId() })
+        .then(|| sys::open_process(PROCESS_QUERY_LIMITED_INFORMATION, pid))
+        .transpose()?;
+      let process: HANDLE = owned_process
+        .as_ref()
+        .map_or_else(|| unsafe { GetCurrentProcess() }, |process| process.0);
+      let mut process_basic: PROCESS_BASIC_INFORMATION = unsafe { std::mem::zeroed() };
+      let mut length = 0;
+      sys::nt_result(unsafe {
+        NtQueryInformationProcess(
+          process,
+          ProcessBasicInformation,
+          &mut process_basic as *mut _ as *mut _,
+          std::mem::size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+          &mut length,
+        )
+      })?;
+      // the relative priority is `16` or `-16` for `THREAD_PRIORITY_TIME_CRITICAL` and `THREAD_PRIORITY_IDLE`,
+      // which saturate at the top or the bottom of the dynamic or the realtime range
+      let realtime = process_basic.BasePriority >= 16;
+      let base_priority = match (basic.base_priority, realtime) {
+        (16, false) => 15,
+        (16, true) => 31,
+        (-16, false) => 1,
+        (-16, true) => 16,
+        (relative, _) => process_basic.BasePriority + relative,
+      };
+      Ok(ThreadSchedulingInfo {
+        boost_enabled: !disabled.as_bool(),
+        background_mode: crate::background::thread_background_mode(thread)?,
+        base_priority,
+        current_priority: basic.priority,
+      })
+    };
+    get().map_err(|err| sys::priority_error(err, "get the thread scheduling information"))
+  }
+  #[cfg(not(windows))]
+  {
+    let _ = tid;
+    Err(sys::unsupported("Thread scheduling information"))
+  }
+}
+
+#[napi(object)]
+/// The time constraints of a realtime thread in nanoseconds.
+pub struct RealtimeThreadPolicy {
+  /// The nominal time between the starts of two processing cycles, `0` means the work isn't periodic.
+  pub period_ns: u32,
+  /// The CPU time needed by each cycle.
+  pub computation_ns: u32,
+  /// The maximum time between the start and the end of each cycle, it must not be less than `computationNs`.
+  pub constraint_ns: u32,
+  /// Whether the computation may be interrupted, it defaults to `true`.
+  pub preemptible: Option<bool>,
+}
+
+#[cfg(target_vendor = "apple")]
+/// Converts nanoseconds to Mach absolute time units, which are not nanoseconds on Apple silicon.
+fn nanos_to_abs(nanos: u32) -> u32 {
+  #[repr(C)]
+  struct MachTimebaseInfo {
+    numer: u32,
+    denom: u32,
+  }
+  // `libc::mach_timebase_info` is deprecated
+  extern "C" {
+    fn mach_timebase_info(info: *mut MachTimebaseInfo) -> libc::c_int;
+  }
+
+  let mut info = MachTimebaseInfo { numer: 0, denom: 0 };
+  if unsafe { mach_timebase_info(&mut info) } != 0 || info.numer == 0 {
+    return nanos;
+  }
+  (u64::from(nanos) * u64::from(info.denom) / u64::from(info.numer)).min(u64::from(u32::MAX)) as u32
+}
+
+#[napi]
+/// This function gives the calling thread realtime scheduling with the given time constraints, which is the only supported way to get audio-grade scheduling on macOS.
+/// The scheduler guarantees `computationNs` of CPU time within `constraintNs` of the start of each `periodNs`, and demotes the thread if it uses more.
+///
+/// On macOS, it uses the [`thread_policy_set`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/thread_policy.h) function with `THREAD_TIME_CONSTRAINT_POLICY`.
+///
+/// On other platforms, it throws.
+pub fn set_realtime_thread_policy(policy: RealtimeThreadPolicy) -> Result<()> {
+  crate::stats::record("setRealtimeThreadPolicy", || {
+    apply_realtime_thread_policy(policy)
+  })
+}
+
+pub(crate) fn apply_realtime_thread_policy(policy: RealtimeThreadPolicy) -> Result<()> {
+  if policy.computation_ns == 0 || policy.computation_ns > policy.constraint_ns {
+    return Err(napi::Error::new(
+      napi::Status::InvalidArg,
+      format!(
+        "computationNs {} is not valid, expected a positive duration not greater than constraintNs {}",
+        policy.computation_ns, policy.constraint_ns
+      ),
+    ));
+  }
+  #[cfg(target_vendor = "apple")]
+  {
+    let mut data = libc::thread_time_constraint_policy_data_t {
+      period: nanos_to_abs(policy.period_ns),
+      computation: nanos_to_abs(policy.computation_ns),
+      constraint: nanos_to_abs(policy.constraint_ns),
+      preemptible: policy.preemptible.unwrap_or(true) as libc::boolean_t,
+    };
+    let ret = unsafe {
+      libc::thread_policy_set(
+        libc::pthread_mach_thread_np(libc::pthread_self()),
+        libc::THREAD_TIME_CONSTRAINT_POLICY as libc::thread_policy_flavor_t,
+        &mut data as *mut _ as libc::thread_policy_t,
+        libc::THREAD_TIME_CONSTRAINT_POLICY_COUNT,
+      )
+    };
+    match ret {
+      libc::KERN_SUCCESS => Ok(()),
+      // the kernel rejects computations outside of its supported range
+      libc::KERN_INVALID_ARGUMENT => Err(napi::Error::new(
+        napi::Status::InvalidArg,
+        "The realtime thread policy was rejected, the computation is probably too short or too long",
+      )),
+      ret => Err(kern_error(ret, "set the realtime thread policy")),
+    }
+  }
+  #[cfg(not(target_vendor = "apple"))]
+  {
+    Err(sys::unsupported("The realtime thread policy"))
+  }
+}
+
+#[napi(object, use_nullable = true)]
+/// A thread of a process, fields which are not supported on the current platform, or which can't be read, are `null`.
+pub struct ThreadInfo {
+  /// The native id of the thread, see `getNativeThreadId`.
+  pub tid: i64,
+  pub name: Option<String>,
+  /// The nice value of the thread, only available on Linux.
+  pub nice: Option<i32>,
+  /// The scheduling policy of the thread, on Linux and macOS.
+  pub policy: Option<crate::sched::SchedulerPolicy>,
+  /// The real-time priority of the thread, only available on Linux.
+  pub rt_priority: Option<u32>,
+  /// The priority of the thread relative to the priority class of the process, see `WindowsThreadPriority`, only available on Windows.
+  pub priority: Option<i32>,
+  #[napi(
+    ts_type = "'running' | 'sleeping' | 'uninterruptible' | 'stopped' | 'zombie' | 'dead' | 'idle' | null"
+  )]
+  /// What the thread is doing, on Linux and macOS.
+  pub state: Option<String>,
+  /// The CPU time consumed by the thread in microseconds, in user and kernel mode.
+  pub cpu_time: Option<f64>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Reads a thread from `/proc/<pid>/task/<tid>/stat`, see [`proc_pid_stat(5)`](https://man7.org/linux/man-pages/man5/proc_pid_stat.5.html).
+fn read_thread_info(pid: u32, tid: u32) -> std::io::Result<ThreadInfo> {
+  let invalid = || std::io::Error::new(std::io::ErrorKind::InvalidData, "unexpected stat format");
+  let stat = std::fs::read_to_string(format!("/proc/{pid}/task/{tid}/stat"))?;
+  // the name may contain spaces and parentheses, so it's delimited by the first `(` and the last `)`
+  let (start, end) = stat.find('(').zip(stat.rfind(')')).ok_or_else(invalid)?;
+  let name = &stat[start + 1..end];
+  // the fields after the name, starting with the state, which is the third field
+  let fields = stat[end + 1..].split_whitespace().collect::<Vec<_>>();
+  let field = |number: usize| fields.get(number - 3).copied().ok_or_else(invalid);
+  let state = match field(3)? {
+    "R" => "running",
+    "S" => "sleeping",
+    "D" => "uninterruptible",
+    "T" | "t" => "stopped",
+    "Z" => "zombie",
+    "I" => "idle",
+    _ => "dead",
+  };
+  let ticks = field(14)?.parse::<f64>().map_err(|_| invalid())?
+    + field(15)?.parse::<f64>().map_err(|_| invalid())?;
+  let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) } as f64;
+  Ok(ThreadInfo {
+    tid: i64::from(tid),
+    name: Some(name.to_owned()),
+    nice: field(19)?.parse().ok(),
+    policy: field(41)?
+      .parse::<libc::c_int>()
+      .ok()
+      .and_then(|policy| crate::sched::SchedulerPolicy::try_from(policy).ok()),
+    rt_priority: field(40)?.parse().ok(),
+    priority: None,
+    state: Some(state.to_owned()),
+    cpu_time: (ticks_per_second > 0.0).then(|| ticks / ticks_per_second * 1e6),
+  })
+}
+
+#[cfg(target_vendor = "apple")]
+/// Reads a thread of the current process from its Mach port.
+fn read_thread_info(port: libc::mach_port_t) -> Option<ThreadInfo> {
+  let mut identifier: libc::thread_identifier_info_data_t = unsafe { std::mem::zeroed() };
+  let mut count = libc::THREAD_IDENTIFIER_INFO_COUNT;
+  let ret = unsafe {
+    libc::thread_info(
+      port,
+      libc::THREAD_IDENTIFIER_INFO as libc::thread_flavor_t,
+      &mut identifier as *mut _ as libc::thread_info_t,
+      &mut count,
+    )
+  };
+  if ret != libc::KERN_SUCCESS {
+    return None;
+  }
+  let mut basic: libc::thread_basic_info = unsafe { std::mem::zeroed() };
+  let mut count = libc::THREAD_BASIC_INFO_COUNT;
+  let basic = (unsafe {
+    libc::thread_info(
+      port,
+      libc::THREAD_BASIC_INFO as libc::thread_flavor_t,
+      &mut basic as *mut _ as libc::thread_info_t,
+      &mut count,
+    )
+  } == libc::KERN_SUCCESS)
+    .then_some(basic);
+  let name = {
+    let thread = unsafe { libc::pthread_from_mach_thread_np(port) };
+    let mut buffer = [0 as libc::c_char; 64];
+    (thread != 0
+      && unsafe { libc::pthread_getname_np(thread, buffer.as_mut_ptr(), buffer.len()) } == 0)
+      .then(|| {
+        unsafe { std::ffi::CStr::from_ptr(buffer.as_ptr()) }
+          .to_string_lossy()
+          .into_owned()
+      })
+      .filter(|name| !name.is_empty())
+  };
+  let micros = |time: libc::time_value_t| time.seconds as f64 * 1e6 + time.microseconds as f64;
+  Some(ThreadInfo {
+    tid: identifier.thread_id as i64,
+    name,
+    nice: None,
+    // `POLICY_TIMESHARE`, `POLICY_RR` and `POLICY_FIFO` from `mach/policy.h`
+    policy: basic.and_then(|basic| match basic.policy {
+      1 => Some(crate::sched::SchedulerPolicy::Other),
+      2 => Some(crate::sched::SchedulerPolicy::RoundRobin),
+      4 => Some(crate::sched::SchedulerPolicy::Fifo),
+      _ => None,
+    }),
+    rt_priority: None,
+    priority: None,
+    state: basic.map(|basic| {
+      match basic.run_state {
+        libc::TH_STATE_RUNNING => "running",
+        libc::TH_STATE_STOPPED => "stopped",
+        libc::TH_STATE_WAITING => "sleeping",
+        libc::TH_STATE_UNINTERRUPTIBLE => "uninterruptible",
+        _ => "dead",
+      }
+      .to_owned()
+    }),
+    cpu_time: basic.map(|basic| micros(basic.user_time) + micros(basic.system_time)),
+  })
+}
+
+#[cfg(windows)]
+/// Reads a thread with the handle opened from its id, only the id is known when it can't be opened.
+fn read_thread_info(tid: u32) -> ThreadInfo {
+  use windows::Win32::Foundation::{LocalFree, FILETIME, HLOCAL};
+  use windows::Win32::System::Threading::{
+    GetThreadDescription, GetThreadPriority, GetThreadTimes, THREAD_QUERY_LIMITED_INFORMATION,
+  };
+  use windows::Win32::System::WindowsProgramming::THREAD_PRIORITY_ERROR_RETURN;
+
+  let mut info = ThreadInfo {
+    tid: i64::from(tid),
+    name: None,
+    nice: None,
+    policy: None,
+    rt_priority: None,
+    priority: None,
+    state: None,
+    cpu_time: None,
+  };
+  let Ok(thread) = sys::open_thread(THREAD_QUERY_LIMITED_INFORMATION, tid) else {
+    return info;
+  };
+  let priority = unsafe { GetThreadPriority(thread.0) };
+  info.priority = (priority != THREAD_PRIORITY_ERROR_RETURN as i32).then_some(priority);
+  if let Ok(description) = unsafe { GetThreadDescription(thread.0) } {
+    info.name = unsafe { description.to_string() }
+      .ok()
+      .filter(|name| !name.is_empty());
+    unsafe { LocalFree(HLOCAL(description.0.cast())) };
+  }
+  let mut creation = FILETIME::default();
+  let mut exit = FILETIME::default();
+  let mut kernel = FILETIME::default();
+  let mut user = FILETIME::default();
+  if unsafe { GetThreadTimes(thread.0, &mut creation, &mut exit, &mut kernel, &mut user) }.is_ok() {
+    info.cpu_time =
+      Some(crate::usage::filetime_micros(kernel) + crate::usage::filetime_micros(user));
+  }
+  info
+}
+
+#[napi]
+/// This function lists the threads of the process identified by `pid` with their priorities, it defaults to the current process, so users can see exactly which threads are running hot.
+/// Threads which exit while they are listed are skipped.
+///
+/// On Linux, it reads [`/proc/<pid>/task/<tid>/stat`](https://man7.org/linux/man-pages/man5/proc_pid_stat.5.html).
+///
+/// On macOS, it uses the [`task_threads`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/task.defs) and [`thread_info`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/thread_info.h) functions, only the current process is supported.
+///
+/// On Windows, it enumerates the threads with [`CreateToolhelp32Snapshot`](https://learn.microsoft.com/en-us/windows/win32/api/tlhelp32/nf-tlhelp32-createtoolhelp32snapshot) and reads them with [`GetThreadPriority`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadpriority), [`GetThreadDescription`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreaddescription) and [`GetThreadTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadtimes).
+///
+/// On other platforms, it throws.
+pub fn list_threads(pid: Option<u32>) -> Result<Vec<ThreadInfo>> {
+  let action = match pid {
+    Some(pid) => format!("list the threads of process {pid}"),
+    None => "list the threads".to_owned(),
+  };
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let pid = pid.unwrap_or_else(std::process::id);
+    let tids = list_thread_ids(pid).map_err(|err| sys::priority_error(err, &action))?;
+    Ok(
+      tids
+        .into_iter()
+        .filter_map(|tid| read_thread_info(pid, tid).ok())
+        .collect(),
+    )
+  }
+  #[cfg(target_vendor = "apple")]
+  {
+    if pid.is_some_and(|pid| pid != std::process::id()) {
+      return Err(sys::unsupported("Listing the threads of other processes"));
+    }
+    let task = unsafe { sys::mach_task_self_ };
+    let mut threads: libc::thread_act_array_t = std::ptr::null_mut();
+    let mut count = 0;
+    let ret = unsafe { libc::task_threads(task, &mut threads, &mut count) };
+    if ret != libc::KERN_SUCCESS {
+      return Err(kern_error(ret, &action));
+    }
+    let ports = unsafe { std::slice::from_raw_parts(threads, count as usize) };
+    let infos = ports
+      .iter()
+      .filter_map(|&port| read_thread_info(port))
+      .collect();
+    for &port in ports {
+      unsafe { sys::mach_port_deallocate(task, port) };
+    }
+    unsafe {
+      libc::vm_deallocate(
+        task,
+        threads as libc::vm_address_t,
+        std::mem::size_of_val(ports) as libc::vm_size_t,
+      )
+    };
+    Ok(infos)
+  }
+  #[cfg(windows)]
+  {
+    let tids =
+      list_thread_ids(pid.unwrap_or(0)).map_err(|err| sys::priority_error(err, &action))?;
+    Ok(tids.into_iter().map(read_thread_info).collect())
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    windows
+  )))]
+  {
+    let _ = action;
+    Err(sys::unsupported("Listing threads"))
+  }
+}
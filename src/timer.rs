@@ -0,0 +1,238 @@
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi(object)]
+/// Windows timer resolutions in milliseconds, a smaller value is a finer resolution.
+pub struct TimerResolution {
+  /// The finest resolution the system supports, usually `0.5`.
+  pub finest: f64,
+  /// The coarsest resolution, which is the default, usually `15.625`.
+  pub coarsest: f64,
+  /// The resolution currently in effect.
+  pub current: f64,
+}
+
+#[cfg(windows)]
+/// `STATUS_TIMER_RESOLUTION_NOT_SET`, returned when the process didn't request a resolution.
+const STATUS_TIMER_RESOLUTION_NOT_SET: i32 = 0xC000_0245_u32 as i32;
+
+#[cfg(windows)]
+/// Converts a duration in 100-nanosecond units into milliseconds.
+fn to_milliseconds(hundred_nanoseconds: u32) -> f64 {
+  hundred_nanoseconds as f64 / 10_000.0
+}
+
+#[napi]
+/// This function requests a finer timer resolution for the current process, so sleeps and timers of latency-sensitive threads don't quantize to 15.6ms, and returns the resolution in effect in milliseconds.
+/// The system rounds `milliseconds` to a supported resolution, and a finer resolution increases the power usage.
+///
+/// On Windows, it uses the `NtSetTimerResolution` function, the finer-grained equivalent of [`timeBeginPeriod`](https://learn.microsoft.com/en-us/windows/win32/api/timeapi/nf-timeapi-timebeginperiod).
+/// Since Windows 10 version 2004, the resolution only affects the current process.
+///
+/// On other platforms, it throws.
+pub fn set_timer_resolution(milliseconds: f64) -> Result<f64> {
+  crate::stats::record("setTimerResolution", || {
+    if !(milliseconds > 0.0 && milliseconds <= 1000.0) {
+      return Err(Error::new(
+        Status::InvalidArg,
+        format!("{milliseconds} is not a valid timer resolution, expected a duration between 0 and 1000 milliseconds"),
+      ));
+    }
+    #[cfg(windows)]
+    {
+      let mut current = 0;
+      sys::nt_result(unsafe {
+        sys::NtSetTimerResolution(
+          (milliseconds * 10_000.0).round().max(1.0) as u32,
+          true.into(),
+          &mut current,
+        )
+      })
+      .map_err(|err| sys::priority_error(err, "set the timer resolution"))?;
+      Ok(to_milliseconds(current))
+    }
+    #[cfg(not(windows))]
+    {
+      Err(sys::unsupported("Setting the timer resolution"))
+    }
+  })
+}
+
+#[napi]
+/// This function withdraws the timer resolution requested with `setTimerResolution`, and returns the resolution in effect in milliseconds.
+/// Calling it without a request has no effect.
+///
+/// On Windows, it uses the `NtSetTimerResolution` function, the equivalent of [`timeEndPeriod`](https://learn.microsoft.com/en-us/windows/win32/api/timeapi/nf-timeapi-timeendperiod).
+///
+/// On other platforms, it throws.
+pub fn clear_timer_resolution() -> Result<f64> {
+  crate::stats::record("clearTimerResolution", || {
+    #[cfg(windows)]
+    {
+      let mut current = 0;
+      let status = unsafe { sys::NtSetTimerResolution(0, false.into(), &mut current) };
+      if status.0 != STATUS_TIMER_RESOLUTION_NOT_SET {
+        sys::nt_result(status)
+          .map_err(|err| sys::priority_error(err, "clear the timer resolution"))?;
+      }
+      Ok(to_milliseconds(current))
+    }
+    #[cfg(not(windows))]
+    {
+      Err(sys::unsupported("Clearing the timer resolution"))
+    }
+  })
+}
+
+#[napi]
+/// This function returns the supported and current timer resolutions in milliseconds.
+///
+/// On Windows, it uses the `NtQueryTimerResolution` function.
+///
+/// On other platforms, it throws.
+pub fn get_timer_resolution() -> Result<TimerResolution> {
+  #[cfg(windows)]
+  {
+    use windows::Wdk::System::SystemInformation::NtQueryTimerResolution;
+
+    let (mut coarsest, mut finest, mut current) = (0, 0, 0);
+    sys::nt_result(unsafe { NtQueryTimerResolution(&mut coarsest, &mut finest, &mut current) })
+      .map_err(|err| sys::priority_error(err, "get the timer resolution"))?;
+    Ok(TimerResolution {
+      finest: to_milliseconds(finest),
+      coarsest: to_milliseconds(coarsest),
+      current: to_milliseconds(current),
+    })
+  }
+  #[cfg(not(windows))]
+  {
+    Err(sys::unsupported("Getting the timer resolution"))
+  }
+}
+
+#[napi]
+/// This function set the timer slack of the calling thread in nanoseconds, how late the kernel may fire its timers to coalesce wake-ups.
+/// Latency-sensitive threads can reduce it, while background threads can increase it to save power. `0` restores the default slack of the thread, usually 50 microseconds.
+///
+/// On Linux, it uses the [`prctl(2)`](https://man7.org/linux/man-pages/man2/prctl.2.html) function with `PR_SET_TIMERSLACK`.
+///
+/// On other platforms, it throws.
+pub fn set_timer_slack(nanoseconds: u32) -> Result<()> {
+  crate::stats::record("setTimerSlack", || {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+      if unsafe { libc::prctl(libc::PR_SET_TIMERSLACK, nanoseconds as libc::c_ulong) } == -1 {
+        return Err(sys::priority_error(
+          std::io::Error::last_os_error(),
+          "set the timer slack",
+        ));
+      }
+      Ok(())
+    }
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    {
+      let _ = nanoseconds;
+      Err(sys::unsupported("Setting the timer slack"))
+    }
+  })
+}
+
+#[napi]
+/// This function get the timer slack of the calling thread in nanoseconds.
+///
+/// On Linux, it uses the [`prctl(2)`](https://man7.org/linux/man-pages/man2/prctl.2.html) function with `PR_GET_TIMERSLACK`.
+///
+/// On other platforms, it throws.
+pub fn get_timer_slack() -> Result<u32> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  {
+    let slack = unsafe { libc::prctl(libc::PR_GET_TIMERSLACK) };
+    if slack == -1 {
+      return Err(sys::priority_error(
+        std::io::Error::last_os_error(),
+        "get the timer slack",
+      ));
+    }
+    Ok(slack as u32)
+  }
+  #[cfg(not(any(target_os = "linux", target_os = "android")))]
+  {
+    Err(sys::unsupported("Getting the timer slack"))
+  }
+}
+
+#[napi(object)]
+pub struct JitterOptions {
+  /// How long to measure in milliseconds, it defaults to `1000`.
+  pub duration_ms: Option<u32>,
+  /// The period of the timing loop in microseconds, it defaults to `1000`.
+  pub interval_us: Option<u32>,
+}
+
+#[napi(object)]
+/// Wakeup latencies in microseconds, the time between when the timing loop asked to wake up and when it actually ran.
+pub struct SchedulingJitter {
+  /// The number of wakeups measured.
+  pub samples: u32,
+  pub min: f64,
+  pub mean: f64,
+  pub p50: f64,
+  pub p99: f64,
+  pub max: f64,
+}
+
+#[napi]
+/// This function runs a timing loop on the calling thread, at its current priority and scheduling policy, which sleeps until the start of each `intervalUs` period for `durationMs`, and reports how late it woke up, like [`cyclictest`](https://wiki.linuxfoundation.org/realtime/documentation/howto/tools/cyclictest/start).
+/// It verifies whether a realtime setup actually works on a given machine: under load, a real-time policy should keep `p99` and `max` low while `Other` degrades.
+///
+/// It blocks the calling thread for `durationMs`, so it's meant to run in a worker thread or a diagnostic script. The latencies include the timer slack on Linux, see `setTimerSlack`, and the timer resolution on Windows, see `setTimerResolution`.
+pub fn measure_scheduling_jitter(options: Option<JitterOptions>) -> Result<SchedulingJitter> {
+  let (duration_ms, interval_us) = options
+    .map(|options| (options.duration_ms, options.interval_us))
+    .unwrap_or_default();
+  let duration = std::time::Duration::from_millis(duration_ms.unwrap_or(1000).into());
+  let interval_us = interval_us.unwrap_or(1000);
+  if interval_us == 0 || duration.is_zero() {
+    return Err(Error::new(
+      Status::InvalidArg,
+      "durationMs and intervalUs must be positive".to_owned(),
+    ));
+  }
+  let interval = std::time::Duration::from_micros(interval_us.into());
+  if duration < interval {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!(
+        "durationMs {} is shorter than intervalUs {interval_us}",
+        duration.as_millis()
+      ),
+    ));
+  }
+  let start = std::time::Instant::now();
+  let mut next = start + interval;
+  let mut latencies = Vec::new();
+  while next <= start + duration {
+    if let Some(remaining) = next.checked_duration_since(std::time::Instant::now()) {
+      std::thread::sleep(remaining);
+    }
+    let now = std::time::Instant::now();
+    latencies.push(now.saturating_duration_since(next).as_secs_f64() * 1e6);
+    next += interval;
+    // periods missed while the thread wasn't running are skipped rather than measured as back to back wakeups
+    while next < now {
+      next += interval;
+    }
+  }
+  latencies.sort_by(f64::total_cmp);
+  let percentile = |p: f64| latencies[((latencies.len() - 1) as f64 * p).round() as usize];
+  Ok(SchedulingJitter {
+    samples: latencies.len() as u32,
+    min: latencies[0],
+    mean: latencies.iter().sum::<f64>() / latencies.len() as f64,
+    p50: percentile(0.5),
+    p99: percentile(0.99),
+    max: latencies[latencies.len() - 1],
+  })
+}
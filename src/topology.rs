@@ -0,0 +1,565 @@
+use napi::Result;
+use napi_derive::napi;
+
+use crate::affinity::AffinityOptions;
+use crate::sys;
+
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct LogicalCpu {
+  /// The CPU index used by `setCpuAffinity`, on Windows it is `group * bits + index within the group`, where `bits` is the number of bits of a processor group mask (64 on 64-bit Windows, 32 on 32-bit Windows).
+  pub cpu: u32,
+  /// The index of the physical core in `CpuTopology.physicalCores`, shared by SMT siblings.
+  pub core: u32,
+  /// The index of the socket (package).
+  pub socket: u32,
+  pub numa_node: u32,
+}
+
+#[napi(object)]
+pub struct CpuCache {
+  pub level: u32,
+  #[napi(ts_type = "'data' | 'instruction' | 'unified'")]
+  pub kind: String,
+  /// The size of the cache in bytes.
+  pub size: u32,
+  /// The CPUs sharing this cache.
+  pub cpus: Vec<u32>,
+}
+
+#[napi(object)]
+pub struct CpuTopology {
+  pub sockets: u32,
+  pub physical_cores: u32,
+  pub logical_cpus: u32,
+  pub numa_nodes: u32,
+  pub cpus: Vec<LogicalCpu>,
+  pub caches: Vec<CpuCache>,
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "android",
+  windows,
+  target_vendor = "apple"
+))]
+impl CpuTopology {
+  fn new(cpus: Vec<LogicalCpu>, caches: Vec<CpuCache>) -> Self {
+    let count = |field: fn(&LogicalCpu) -> u32| {
+      cpus
+        .iter()
+        .map(field)
+        .collect::<std::collections::BTreeSet<_>>()
+        .len() as u32
+    };
+    Self {
+      sockets: count(|cpu| cpu.socket),
+      physical_cores: count(|cpu| cpu.core),
+      logical_cpus: cpus.len() as u32,
+      numa_nodes: count(|cpu| cpu.numa_node),
+      cpus,
+      caches,
+    }
+  }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_sysfs(path: &str) -> std::io::Result<String> {
+  Ok(std::fs::read_to_string(path)?.trim().to_owned())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn invalid_sysfs(path: &str) -> std::io::Error {
+  std::io::Error::new(
+    std::io::ErrorKind::InvalidData,
+    format!("unexpected content in {path}"),
+  )
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_linux_topology() -> std::io::Result<CpuTopology> {
+  use std::collections::BTreeMap;
+
+  use crate::affinity::try_parse_cpu_list;
+
+  const CPU_DIR: &str = "/sys/devices/system/cpu";
+
+  let online_path = format!("{CPU_DIR}/online");
+  let online =
+    try_parse_cpu_list(&read_sysfs(&online_path)?).ok_or_else(|| invalid_sysfs(&online_path))?;
+
+  let mut numa_nodes = BTreeMap::new();
+  if let Ok(entries) = std::fs::read_dir("/sys/devices/system/node") {
+    for entry in entries {
+      let entry = entry?;
+      let Some(node) = entry
+        .file_name()
+        .to_str()
+        .and_then(|name| name.strip_prefix("node"))
+        .and_then(|node| node.parse::<u32>().ok())
+      else {
+        continue;
+      };
+      let path = format!("{}/cpulist", entry.path().display());
+      for cpu in try_parse_cpu_list(&read_sysfs(&path)?).ok_or_else(|| invalid_sysfs(&path))? {
+        numa_nodes.insert(cpu, node);
+      }
+    }
+  }
+
+  let mut sockets = BTreeMap::new();
+  let mut cores = BTreeMap::new();
+  let mut caches = BTreeMap::new();
+  let mut cpus = Vec::with_capacity(online.len());
+  for cpu in online {
+    let topology = format!("{CPU_DIR}/cpu{cpu}/topology");
+    // some architectures report `-1` when the package is unknown
+    let package = read_sysfs(&format!("{topology}/physical_package_id"))?
+      .parse::<i64>()
+      .unwrap_or(0)
+      .max(0);
+    let core_id = read_sysfs(&format!("{topology}/core_id"))?;
+    let next_socket = sockets.len() as u32;
+    let socket = *sockets.entry(package).or_insert(next_socket);
+    let next_core = cores.len() as u32;
+    let core = *cores.entry((package, core_id)).or_insert(next_core);
+    cpus.push(LogicalCpu {
+      cpu,
+      core,
+      socket,
+      numa_node: numa_nodes.get(&cpu).copied().unwrap_or(0),
+    });
+
+    let Ok(indexes) = std::fs::read_dir(format!("{CPU_DIR}/cpu{cpu}/cache")) else {
+      continue;
+    };
+    for index in indexes {
+      let index = index?.path();
+      if !index.join("level").exists() {
+        continue;
+      }
+      let read = |name: &str| read_sysfs(&format!("{}/{name}", index.display()));
+      let level = read("level")?;
+      let kind = match read("type")?.as_str() {
+        "Data" => "data",
+        "Instruction" => "instruction",
+        _ => "unified",
+      };
+      let shared = read("shared_cpu_list")?;
+      if caches.contains_key(&(level.clone(), kind, shared.clone())) {
+        continue;
+      }
+      let size = read("size")?;
+      let (digits, unit) = size.split_at(size.trim_end_matches(char::is_alphabetic).len());
+      let multiplier = match unit {
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => 1,
+      };
+      let cache = CpuCache {
+        level: level
+          .parse()
+          .map_err(|_| invalid_sysfs(&format!("{}/level", index.display())))?,
+        kind: kind.to_owned(),
+        size: digits
+          .parse::<u32>()
+          .unwrap_or(0)
+          .saturating_mul(multiplier),
+        cpus: try_parse_cpu_list(&shared)
+          .ok_or_else(|| invalid_sysfs(&format!("{}/shared_cpu_list", index.display())))?,
+      };
+      caches.insert((level, kind, shared), cache);
+    }
+  }
+  let mut caches = caches.into_values().collect::<Vec<_>>();
+  caches.sort_by_key(|cache| (cache.level, cache.cpus.first().copied()));
+  Ok(CpuTopology::new(cpus, caches))
+}
+
+#[cfg(windows)]
+/// CPUs of every group are numbered `group * bits + index within the group`, where `bits` is the number of bits of a processor group mask (64 on 64-bit Windows, 32 on 32-bit Windows).
+fn group_masks_to_cpus(
+  masks: &[windows::Win32::System::SystemInformation::GROUP_AFFINITY],
+) -> Vec<u32> {
+  masks
+    .iter()
+    .flat_map(|mask| {
+      crate::affinity::mask_to_cpus(mask.Mask)
+        .into_iter()
+        .map(move |cpu| mask.Group as u32 * usize::BITS + cpu)
+    })
+    .collect()
+}
+
+#[cfg(windows)]
+fn read_windows_topology() -> std::io::Result<CpuTopology> {
+  use std::collections::BTreeMap;
+
+  use windows::Win32::System::SystemInformation::{
+    CacheData, CacheInstruction, CacheUnified, RelationAll, RelationCache, RelationNumaNode,
+    RelationProcessorCore, RelationProcessorPackage,
+  };
+
+  let mut cpus = BTreeMap::<u32, LogicalCpu>::new();
+  let mut caches = Vec::new();
+  let mut sockets = 0;
+  let mut cores = 0;
+  crate::affinity::logical_processor_information(RelationAll, |info| unsafe {
+    let relationship = info.Relationship;
+    if relationship == RelationProcessorPackage || relationship == RelationProcessorCore {
+      let processor = &info.Anonymous.Processor;
+      let masks =
+        std::slice::from_raw_parts(processor.GroupMask.as_ptr(), processor.GroupCount as usize);
+      for cpu in group_masks_to_cpus(masks) {
+        let entry = cpus.entry(cpu).or_default();
+        entry.cpu = cpu;
+        if relationship == RelationProcessorPackage {
+          entry.socket = sockets;
+        } else {
+          entry.core = cores;
+        }
+      }
+      if relationship == RelationProcessorPackage {
+        sockets += 1;
+      } else {
+        cores += 1;
+      }
+    } else if relationship == RelationNumaNode {
+      let node = &info.Anonymous.NumaNode;
+      let masks = std::slice::from_raw_parts(
+        node.Anonymous.GroupMasks.as_ptr(),
+        (node.GroupCount as usize).max(1),
+      );
+      for cpu in group_masks_to_cpus(masks) {
+        let entry = cpus.entry(cpu).or_default();
+        entry.cpu = cpu;
+        entry.numa_node = node.NodeNumber;
+      }
+    } else if relationship == RelationCache {
+      let cache = &info.Anonymous.Cache;
+      let kind = if cache.Type == CacheData {
+        "data"
+      } else if cache.Type == CacheInstruction {
+        "instruction"
+      } else if cache.Type == CacheUnified {
+        "unified"
+      } else {
+        // trace caches only hold decoded instructions
+        return;
+      };
+      let masks = std::slice::from_raw_parts(
+        cache.Anonymous.GroupMasks.as_ptr(),
+        (cache.GroupCount as usize).max(1),
+      );
+      caches.push(CpuCache {
+        level: cache.Level as u32,
+        kind: kind.to_owned(),
+        size: cache.CacheSize,
+        cpus: group_masks_to_cpus(masks),
+      });
+    }
+  })?;
+  caches.sort_by_key(|cache| (cache.level, cache.cpus.first().copied()));
+  Ok(CpuTopology::new(cpus.into_values().collect(), caches))
+}
+
+#[cfg(target_vendor = "apple")]
+/// macOS doesn't expose which CPU belongs to which core or cache, so CPUs are assumed to be numbered core by core.
+fn read_apple_topology() -> std::io::Result<CpuTopology> {
+  let read = |name: &std::ffi::CStr| {
+    sys::sysctl_numbers(name).map(|values| values.first().copied().unwrap_or(0) as u32)
+  };
+  let logical = read(c"hw.logicalcpu")?.max(1);
+  let physical = read(c"hw.physicalcpu")?.clamp(1, logical);
+  let packages = read(c"hw.packages").unwrap_or(1).clamp(1, physical);
+  let threads_per_core = logical / physical;
+  let cpus = (0..logical)
+    .map(|cpu| {
+      let core = (cpu / threads_per_core).min(physical - 1);
+      LogicalCpu {
+        cpu,
+        core,
+        socket: core * packages / physical,
+        numa_node: 0,
+      }
+    })
+    .collect();
+  // `hw.cacheconfig` holds the number of CPUs sharing each level, starting with the memory
+  let sharing = sys::sysctl_numbers(c"hw.cacheconfig").unwrap_or_default();
+  let mut caches = Vec::new();
+  for (level, kind, name) in [
+    (1, "instruction", c"hw.l1icachesize"),
+    (1, "data", c"hw.l1dcachesize"),
+    (2, "unified", c"hw.l2cachesize"),
+    (3, "unified", c"hw.l3cachesize"),
+  ] {
+    let size = read(name).unwrap_or(0);
+    let shared_by = sharing.get(level).copied().unwrap_or(0) as u32;
+    if size == 0 || shared_by == 0 {
+      continue;
+    }
+    for first in (0..logical).step_by(shared_by as usize) {
+      caches.push(CpuCache {
+        level: level as u32,
+        kind: kind.to_owned(),
+        size,
+        cpus: (first..(first + shared_by).min(logical)).collect(),
+      });
+    }
+  }
+  caches.sort_by_key(|cache| (cache.level, cache.cpus.first().copied()));
+  Ok(CpuTopology::new(cpus, caches))
+}
+
+#[napi]
+/// This function returns the sockets, physical cores, logical CPUs, caches and NUMA nodes of the machine.
+///
+/// On Linux, it reads `/sys/devices/system/cpu` and `/sys/devices/system/node`.
+///
+/// On Windows, it uses the [`GetLogicalProcessorInformationEx`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getlogicalprocessorinformationex) function with `RelationAll`.
+///
+/// On macOS, it uses the `hw.*` [`sysctl`](https://developer.apple.com/documentation/kernel/1387446-sysctlbyname) values, which don't say which CPU belongs to which core, so CPUs are assumed to be numbered core by core.
+///
+/// On other platforms, it throws.
+pub fn get_cpu_topology() -> Result<CpuTopology> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  let topology = read_linux_topology();
+  #[cfg(windows)]
+  let topology = read_windows_topology();
+  #[cfg(target_vendor = "apple")]
+  let topology = read_apple_topology();
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    windows,
+    target_vendor = "apple"
+  ))]
+  {
+    topology.map_err(|err| sys::priority_error(err, "get the CPU topology"))
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    windows,
+    target_vendor = "apple"
+  )))]
+  {
+    Err(sys::unsupported("Getting the CPU topology"))
+  }
+}
+
+#[napi(object)]
+pub struct CoreClasses {
+  /// The CPUs of the performance cores, every CPU on machines without hybrid CPUs.
+  pub performance: Vec<u32>,
+  /// The CPUs of the efficiency cores, empty on machines without hybrid CPUs.
+  pub efficiency: Vec<u32>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn read_linux_core_classes() -> std::io::Result<CoreClasses> {
+  use crate::affinity::try_parse_cpu_list;
+
+  let read_list =
+    |path: &str| try_parse_cpu_list(&read_sysfs(path)?).ok_or_else(|| invalid_sysfs(path));
+  // Intel hybrid CPUs register a PMU per core type
+  if std::path::Path::new("/sys/devices/cpu_atom/cpus").exists() {
+    return Ok(CoreClasses {
+      performance: read_list("/sys/devices/cpu_core/cpus")?,
+      efficiency: read_list("/sys/devices/cpu_atom/cpus")?,
+    });
+  }
+  // ARM big.LITTLE CPUs report the relative capacity of each CPU
+  let online = read_list("/sys/devices/system/cpu/online")?;
+  let capacities = online
+    .iter()
+    .map(|cpu| {
+      read_sysfs(&format!("/sys/devices/system/cpu/cpu{cpu}/cpu_capacity"))
+        .ok()
+        .and_then(|capacity| capacity.parse::<u32>().ok())
+    })
+    .collect::<Option<Vec<_>>>();
+  let Some(capacities) = capacities else {
+    return Ok(CoreClasses {
+      performance: online,
+      efficiency: Vec::new(),
+    });
+  };
+  let highest = capacities.iter().copied().max().unwrap_or(0);
+  let (performance, efficiency) = online
+    .into_iter()
+    .zip(capacities)
+    .partition::<Vec<_>, _>(|&(_, capacity)| capacity == highest);
+  Ok(CoreClasses {
+    performance: performance.into_iter().map(|(cpu, _)| cpu).collect(),
+    efficiency: efficiency.into_iter().map(|(cpu, _)| cpu).collect(),
+  })
+}
+
+#[cfg(windows)]
+fn read_windows_core_classes() -> std::io::Result<CoreClasses> {
+  use windows::Win32::System::SystemInformation::RelationProcessorCore;
+
+  let mut cores = Vec::new();
+  crate::affinity::logical_processor_information(RelationProcessorCore, |info| unsafe {
+    let processor = &info.Anonymous.Processor;
+    let masks =
+      std::slice::from_raw_parts(processor.GroupMask.as_ptr(), processor.GroupCount as usize);
+    cores.push((processor.EfficiencyClass, group_masks_to_cpus(masks)));
+  })?;
+  // a higher efficiency class means a more performant core
+  let highest = cores.iter().map(|(class, _)| *class).max().unwrap_or(0);
+  let mut classes = CoreClasses {
+    performance: Vec::new(),
+    efficiency: Vec::new(),
+  };
+  for (class, cpus) in cores {
+    if class == highest {
+      classes.performance.extend(cpus);
+    } else {
+      classes.efficiency.extend(cpus);
+    }
+  }
+  classes.performance.sort_unstable();
+  classes.efficiency.sort_unstable();
+  Ok(classes)
+}
+
+#[cfg(target_vendor = "apple")]
+/// Apple silicon numbers the efficiency cores (`hw.perflevel1`) before the performance cores (`hw.perflevel0`).
+fn read_apple_core_classes() -> std::io::Result<CoreClasses> {
+  let read = |name: &std::ffi::CStr| {
+    sys::sysctl_numbers(name).map(|values| values.first().copied().unwrap_or(0) as u32)
+  };
+  if read(c"hw.nperflevels").unwrap_or(1) < 2 {
+    return Ok(CoreClasses {
+      performance: (0..read(c"hw.logicalcpu")?).collect(),
+      efficiency: Vec::new(),
+    });
+  }
+  let efficiency = read(c"hw.perflevel1.logicalcpu")?;
+  let performance = read(c"hw.perflevel0.logicalcpu")?;
+  Ok(CoreClasses {
+    performance: (efficiency..efficiency + performance).collect(),
+    efficiency: (0..efficiency).collect(),
+  })
+}
+
+fn read_core_classes() -> Result<CoreClasses> {
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  let classes = read_linux_core_classes();
+  #[cfg(windows)]
+  let classes = read_windows_core_classes();
+  #[cfg(target_vendor = "apple")]
+  let classes = read_apple_core_classes();
+  #[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    windows,
+    target_vendor = "apple"
+  ))]
+  {
+    classes.map_err(|err| sys::priority_error(err, "get the core classes"))
+  }
+  #[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    windows,
+    target_vendor = "apple"
+  )))]
+  {
+    Err(sys::unsupported("Getting the core classes"))
+  }
+}
+
+#[napi]
+/// This function splits the CPUs of hybrid machines into performance cores and efficiency cores.
+///
+/// On Linux, it reads `/sys/devices/cpu_core/cpus` and `/sys/devices/cpu_atom/cpus` on Intel hybrid CPUs, and the `cpu_capacity` of every CPU on ARM big.LITTLE CPUs, where only the CPUs with the highest capacity are performance cores.
+///
+/// On Windows, it uses the `EfficiencyClass` returned by the [`GetLogicalProcessorInformationEx`](https://learn.microsoft.com/en-us/windows/win32/api/sysinfoapi/nf-sysinfoapi-getlogicalprocessorinformationex) function, where only the cores with the highest class are performance cores.
+///
+/// On macOS, it uses the `hw.perflevel0` and `hw.perflevel1` `sysctl` values.
+///
+/// On other platforms, it throws.
+pub fn get_core_classes() -> Result<CoreClasses> {
+  read_core_classes()
+}
+
+fn pin_to_core_class(efficiency: bool, options: Option<AffinityOptions>) -> Result<bool> {
+  let classes = read_core_classes()?;
+  #[cfg(target_vendor = "apple")]
+  {
+    // threads can't be pinned on macOS, but the scheduler places them by QoS class
+    if options.is_some() {
+      return Err(sys::unsupported("Pinning another thread or process"));
+    }
+    let (cpus, qos_class) = if efficiency {
+      (classes.efficiency, libc::qos_class_t::QOS_CLASS_BACKGROUND)
+    } else {
+      (
+        classes.performance,
+        libc::qos_class_t::QOS_CLASS_USER_INTERACTIVE,
+      )
+    };
+    if cpus.is_empty() {
+      return Ok(false);
+    }
+    let ret = unsafe { libc::pthread_set_qos_class_self_np(qos_class, 0) };
+    if ret != 0 {
+      return Err(sys::priority_error(
+        std::io::Error::from_raw_os_error(ret),
+        "set the QoS class of the current thread",
+      ));
+    }
+    Ok(true)
+  }
+  #[cfg(not(target_vendor = "apple"))]
+  {
+    let cpus = if efficiency {
+      classes.efficiency
+    } else {
+      classes.performance
+    };
+    // the affinity mask only covers the first processor group
+    #[cfg(windows)]
+    let cpus = cpus
+      .into_iter()
+      .filter(|&cpu| cpu < usize::BITS)
+      .collect::<Vec<_>>();
+    if cpus.is_empty() {
+      return Ok(false);
+    }
+    crate::affinity::apply_cpu_affinity(napi::bindgen_prelude::Either::A(cpus), options)?;
+    Ok(true)
+  }
+}
+
+#[napi]
+/// This function restricts the calling thread to the efficiency cores returned by `getCoreClasses`, and returns `false` without changing anything when there are none.
+/// It accepts the same options as `setCpuAffinity`.
+///
+/// On Linux and Windows, it uses `setCpuAffinity`, on Windows only the CPUs of the first processor group are used.
+///
+/// On macOS, threads can't be pinned, so it sets the QoS class of the calling thread to `QOS_CLASS_BACKGROUND`, which the scheduler keeps on the efficiency cores, and `options` is not supported.
+///
+/// On other platforms, it throws.
+pub fn pin_to_efficiency_cores(options: Option<AffinityOptions>) -> Result<bool> {
+  crate::stats::record("pinToEfficiencyCores", || pin_to_core_class(true, options))
+}
+
+#[napi]
+/// This function restricts the calling thread to the performance cores returned by `getCoreClasses`.
+/// It accepts the same options as `setCpuAffinity`.
+///
+/// On Linux and Windows, it uses `setCpuAffinity`, on Windows only the CPUs of the first processor group are used.
+///
+/// On macOS, threads can't be pinned, so it sets the QoS class of the calling thread to `QOS_CLASS_USER_INTERACTIVE`, which the scheduler prefers to run on the performance cores, and `options` is not supported.
+///
+/// On other platforms, it throws.
+pub fn pin_to_performance_cores(options: Option<AffinityOptions>) -> Result<bool> {
+  crate::stats::record("pinToPerformanceCores", || {
+    pin_to_core_class(false, options)
+  })
+}
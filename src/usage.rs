@@ -0,0 +1,473 @@
+use napi::bindgen_prelude::Object;
+use napi::{Env, Error, JsDeferred, Result, Status};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[napi(object)]
+pub struct ResourceUsage {
+  /// The CPU time spent in user mode in microseconds.
+  pub user_cpu_time: f64,
+  /// The CPU time spent in kernel mode in microseconds.
+  pub system_cpu_time: f64,
+  /// The maximum resident set size in bytes, it's `undefined` for the `thread` scope and on Windows.
+  pub max_rss: Option<f64>,
+}
+
+#[cfg(target_vendor = "apple")]
+extern "C" {
+  fn mach_thread_self() -> libc::mach_port_t;
+}
+
+#[cfg(unix)]
+fn getrusage(who: libc::c_int) -> std::io::Result<libc::rusage> {
+  let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+  if unsafe { libc::getrusage(who, &mut usage) } == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(usage)
+}
+
+#[cfg(unix)]
+fn timeval_micros(time: libc::timeval) -> f64 {
+  time.tv_sec as f64 * 1e6 + time.tv_usec as f64
+}
+
+#[cfg(windows)]
+/// `FILETIME` durations are in 100 nanoseconds units.
+pub(crate) fn filetime_micros(time: windows::Win32::Foundation::FILETIME) -> f64 {
+  ((u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime)) as f64 / 10.0
+}
+
+#[napi]
+/// This function returns the CPU time consumed by the current process, the calling thread, or the terminated and waited-for children of the current process, so callers can check that lowering the priority actually reduced the CPU consumption.
+/// `scope` is `self`, `thread` or `children`.
+///
+/// On Unix, it uses [`getrusage(2)`](https://man7.org/linux/man-pages/man2/getrusage.2.html) with `RUSAGE_SELF`, `RUSAGE_THREAD` or `RUSAGE_CHILDREN`. On macOS, which doesn't have `RUSAGE_THREAD`, the `thread` scope uses the [`thread_info`](https://github.com/apple-oss-distributions/xnu/blob/main/osfmk/mach/thread_info.h) function with `THREAD_BASIC_INFO`.
+///
+/// On Windows, it uses the [`GetProcessTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getprocesstimes) and [`GetThreadTimes`](https://learn.microsoft.com/en-us/windows/win32/api/processthreadsapi/nf-processthreadsapi-getthreadtimes) functions, the `children` scope throws.
+pub fn get_resource_usage(
+  #[napi(ts_arg_type = "'self' | 'thread' | 'children'")] scope: String,
+) -> Result<ResourceUsage> {
+  if !matches!(scope.as_str(), "self" | "thread" | "children") {
+    return Err(Error::new(
+      Status::InvalidArg,
+      format!("{scope} is not a valid scope, expected self, thread or children"),
+    ));
+  }
+  let action = format!("get the resource usage of {scope}");
+  #[cfg(target_vendor = "apple")]
+  if scope == "thread" {
+    let task = unsafe { sys::mach_task_self_ };
+    let thread = unsafe { mach_thread_self() };
+    let mut info: libc::thread_basic_info = unsafe { std::mem::zeroed() };
+    let mut count = libc::THREAD_BASIC_INFO_COUNT;
+    let ret = unsafe {
+      libc::thread_info(
+        thread,
+        libc::THREAD_BASIC_INFO as libc::thread_flavor_t,
+        &mut info as *mut _ as libc::thread_info_t,
+        &mut count,
+      )
+    };
+    unsafe { sys::mach_port_deallocate(task, thread) };
+    if ret != libc::KERN_SUCCESS {
+      return Err(crate::thread::kern_error(ret, &action));
+    }
+    let micros = |time: libc::time_value_t| time.seconds as f64 * 1e6 + time.microseconds as f64;
+    return Ok(ResourceUsage {
+      user_cpu_time: micros(info.user_time),
+      system_cpu_time: micros(info.system_time),
+      max_rss: None,
+    });
+  }
+  #[cfg(unix)]
+  {
+    let who = match scope.as_str() {
+      "self" => libc::RUSAGE_SELF,
+      "children" => libc::RUSAGE_CHILDREN,
+      #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+      _ => libc::RUSAGE_THREAD,
+      #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+      _ => {
+        return Err(sys::unsupported(
+          "Getting the resource usage of the calling thread",
+        ))
+      }
+    };
+    let usage = getrusage(who).map_err(|err| sys::priority_error(err, &action))?;
+    // `ru_maxrss` is in bytes on macOS and in kilobytes elsewhere
+    #[cfg(target_vendor = "apple")]
+    let max_rss = usage.ru_maxrss as f64;
+    #[cfg(not(target_vendor = "apple"))]
+    let max_rss = usage.ru_maxrss as f64 * 1024.0;
+    Ok(ResourceUsage {
+      user_cpu_time: timeval_micros(usage.ru_utime),
+      system_cpu_time: timeval_micros(usage.ru_stime),
+      max_rss: (scope != "thread").then_some(max_rss),
+    })
+  }
+  #[cfg(windows)]
+  {
+    use windows::Win32::Foundation::FILETIME;
+    use windows::Win32::System::Threading::{
+      GetCurrentProcess, GetCurrentThread, GetProcessTimes, GetThreadTimes,
+    };
+
+    let mut creation = FILETIME::default();
+    let mut exit = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+    match scope.as_str() {
+      "self" => unsafe {
+        GetProcessTimes(
+          GetCurrentProcess(),
+          &mut creation,
+          &mut exit,
+          &mut kernel,
+          &mut user,
+        )
+      },
+      "thread" => unsafe {
+        GetThreadTimes(
+          GetCurrentThread(),
+          &mut creation,
+          &mut exit,
+          &mut kernel,
+          &mut user,
+        )
+      },
+      _ => return Err(sys::unsupported("Getting the resource usage of children")),
+    }
+    .map_err(|err| sys::priority_error(sys::win32_error(err), &action))?;
+    Ok(ResourceUsage {
+      user_cpu_time: filetime_micros(user),
+      system_cpu_time: filetime_micros(kernel),
+      max_rss: None,
+    })
+  }
+}
+
+#[napi]
+/// This function returns the number of CPU clock cycles consumed by the thread identified by `tid`, it defaults to the calling thread.
+/// Unlike `getResourceUsage`, which is updated on clock ticks, it's cycle-accurate, so it can verify the effect of priority and EcoQoS changes on short workloads. Cycles are not comparable across machines, and they depend on the CPU frequency.
+///
+/// On Windows, it uses the [`QueryThreadCycleTime`](https://learn.microsoft.com/en-us/windows/win32/api/realtimeapiset/nf-realtimeapiset-querythreadcycletime) function.
+///
+/// On other platforms, it throws.
+pub fn get_thread_cycle_time(tid: Option<u32>) -> Result<f64> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{GetCurrentThread, THREAD_QUERY_LIMITED_INFORMATION};
+    use windows::Win32::System::WindowsProgramming::QueryThreadCycleTime;
+
+    let get = || {
+      let owned = tid
+        .map(|tid| sys::open_thread(THREAD_QUERY_LIMITED_INFORMATION, tid))
+        .transpose()?;
+      let thread = owned
+        .as_ref()
+        .map_or_else(|| unsafe { GetCurrentThread() }, |thread| thread.0);
+      let mut cycles = 0u64;
+      unsafe { QueryThreadCycleTime(thread, &mut cycles) }.map_err(sys::win32_error)?;
+      Ok(cycles as f64)
+    };
+    get().map_err(|err| sys::priority_error(err, "get the thread cycle time"))
+  }
+  #[cfg(not(windows))]
+  {
+    let _ = tid;
+    Err(sys::unsupported("Thread cycle time"))
+  }
+}
+
+#[napi]
+/// This function returns the number of CPU clock cycles consumed by all the threads of the process identified by `pid`, including the terminated ones, it defaults to the current process.
+///
+/// On Windows, it uses the [`QueryProcessCycleTime`](https://learn.microsoft.com/en-us/windows/win32/api/realtimeapiset/nf-realtimeapiset-queryprocesscycletime) function.
+///
+/// On other platforms, it throws.
+pub fn get_process_cycle_time(pid: Option<u32>) -> Result<f64> {
+  #[cfg(windows)]
+  {
+    use windows::Win32::System::Threading::{GetCurrentProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+    use windows::Win32::System::WindowsProgramming::QueryProcessCycleTime;
+
+    let get = || {
+      let owned = pid
+        .map(|pid| sys::open_process(PROCESS_QUERY_LIMITED_INFORMATION, pid))
+        .transpose()?;
+      let process = owned
+        .as_ref()
+        .map_or_else(|| unsafe { GetCurrentProcess() }, |process| process.0);
+      let mut cycles = 0u64;
+      unsafe { QueryProcessCycleTime(process, &mut cycles) }.map_err(sys::win32_error)?;
+      Ok(cycles as f64)
+    };
+    get().map_err(|err| sys::priority_error(err, "get the process cycle time"))
+  }
+  #[cfg(not(windows))]
+  {
+    let _ = pid;
+    Err(sys::unsupported("Process cycle time"))
+  }
+}
+
+#[napi(object)]
+pub struct ContextSwitchesOptions {
+  /// Whether to list the context switches of every thread of the current process as well, which is only supported on Linux and Windows.
+  pub threads: Option<bool>,
+}
+
+#[napi(object)]
+pub struct ThreadContextSwitches {
+  /// The native id of the thread, see `getNativeThreadId`.
+  pub tid: i64,
+  /// The context switches because the thread blocked, for example on I/O or a lock, `undefined` on Windows.
+  pub voluntary: Option<f64>,
+  /// The context switches because the thread was preempted, `undefined` on Windows.
+  pub involuntary: Option<f64>,
+  pub total: f64,
+}
+
+#[napi(object)]
+pub struct ContextSwitches {
+  /// The context switches because a thread blocked, for example on I/O or a lock, `undefined` on Windows.
+  pub voluntary: Option<f64>,
+  /// The context switches because a thread was preempted, `undefined` on Windows.
+  pub involuntary: Option<f64>,
+  pub total: f64,
+  /// The context switches of every thread, when the `threads` option is set.
+  pub threads: Option<Vec<ThreadContextSwitches>>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+/// Reads the context switches of a thread from the `voluntary_ctxt_switches` and `nonvoluntary_ctxt_switches` lines of `/proc/self/task/<tid>/status`.
+fn thread_context_switches(tid: u32) -> std::io::Result<ThreadContextSwitches> {
+  let status = std::fs::read_to_string(format!("/proc/self/task/{tid}/status"))?;
+  let field = |name: &str| {
+    status
+      .lines()
+      .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+      .and_then(|value| value.trim().parse::<f64>().ok())
+      .unwrap_or(0.0)
+  };
+  let voluntary = field("voluntary_ctxt_switches");
+  let involuntary = field("nonvoluntary_ctxt_switches");
+  Ok(ThreadContextSwitches {
+    tid: i64::from(tid),
+    voluntary: Some(voluntary),
+    involuntary: Some(involuntary),
+    total: voluntary + involuntary,
+  })
+}
+
+#[cfg(windows)]
+/// Lists the threads of the current process with their context switches, from `SYSTEM_THREAD_INFORMATION`, whose `Reserved3` field is `ContextSwitches`.
+fn windows_thread_context_switches() -> std::io::Result<Vec<ThreadContextSwitches>> {
+  use windows::Wdk::System::SystemInformation::{
+    NtQuerySystemInformation, SystemProcessInformation,
+  };
+  use windows::Win32::Foundation::STATUS_INFO_LENGTH_MISMATCH;
+  use windows::Win32::System::WindowsProgramming::{
+    SYSTEM_PROCESS_INFORMATION, SYSTEM_THREAD_INFORMATION,
+  };
+
+  // `u64` elements keep the entries aligned
+  let mut buffer = vec![0u64; 64 * 1024];
+  loop {
+    let mut length = 0u32;
+    let status = unsafe {
+      NtQuerySystemInformation(
+        SystemProcessInformation,
+        buffer.as_mut_ptr().cast(),
+        (buffer.len() * std::mem::size_of::<u64>()) as u32,
+        &mut length,
+      )
+    };
+    if status == STATUS_INFO_LENGTH_MISMATCH {
+      // processes may start in the meantime, so leave some headroom
+      buffer.resize(length as usize / std::mem::size_of::<u64>() + 4096, 0);
+      continue;
+    }
+    sys::nt_result(status)?;
+    break;
+  }
+  let pid = std::process::id() as usize;
+  let mut offset = 0;
+  loop {
+    let process = unsafe {
+      &*buffer
+        .as_ptr()
+        .cast::<u8>()
+        .add(offset)
+        .cast::<SYSTEM_PROCESS_INFORMATION>()
+    };
+    if process.UniqueProcessId.0 as usize == pid {
+      // the threads directly follow the process entry
+      let threads = unsafe {
+        std::slice::from_raw_parts(
+          (process as *const SYSTEM_PROCESS_INFORMATION)
+            .add(1)
+            .cast::<SYSTEM_THREAD_INFORMATION>(),
+          process.NumberOfThreads as usize,
+        )
+      };
+      return Ok(
+        threads
+          .iter()
+          .map(|thread| ThreadContextSwitches {
+            tid: thread.ClientId.UniqueThread.0 as usize as i64,
+            voluntary: None,
+            involuntary: None,
+            total: thread.Reserved3 as f64,
+          })
+          .collect(),
+      );
+    }
+    if process.NextEntryOffset == 0 {
+      return Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "the current process is not listed",
+      ));
+    }
+    offset += process.NextEntryOffset as usize;
+  }
+}
+
+#[napi]
+/// This function returns the voluntary and involuntary context switches of the current process, and optionally of each of its threads, to diagnose scheduling thrash after priority changes.
+/// Many involuntary context switches mean the threads are preempted, for example by higher priority work or a tight CPU quota.
+///
+/// On Unix, it uses [`getrusage(2)`](https://man7.org/linux/man-pages/man2/getrusage.2.html) with `RUSAGE_SELF`. On Linux, the threads are read from [`/proc/self/task/<tid>/status`](https://man7.org/linux/man-pages/man5/proc_pid_status.5.html).
+///
+/// On Windows, it uses the [`NtQuerySystemInformation`](https://learn.microsoft.com/en-us/windows/win32/api/winternl/nf-winternl-ntquerysysteminformation) function with `SystemProcessInformation`, which only counts the context switches of the running threads, and doesn't tell voluntary and involuntary ones apart.
+pub fn get_context_switches(options: Option<ContextSwitchesOptions>) -> Result<ContextSwitches> {
+  let with_threads = options.and_then(|options| options.threads).unwrap_or(false);
+  #[cfg(unix)]
+  {
+    let usage = getrusage(libc::RUSAGE_SELF)
+      .map_err(|err| sys::priority_error(err, "get the context switches"))?;
+    let voluntary = usage.ru_nvcsw as f64;
+    let involuntary = usage.ru_nivcsw as f64;
+    let threads = if with_threads {
+      #[cfg(any(target_os = "linux", target_os = "android"))]
+      {
+        let tids = crate::thread::list_thread_ids(0)
+          .map_err(|err| sys::priority_error(err, "list the threads"))?;
+        // threads may exit while they are listed
+        Some(
+          tids
+            .into_iter()
+            .filter_map(|tid| thread_context_switches(tid).ok())
+            .collect(),
+        )
+      }
+      #[cfg(not(any(target_os = "linux", target_os = "android")))]
+      {
+        return Err(sys::unsupported(
+          "Getting the context switches of each thread",
+        ));
+      }
+    } else {
+      None
+    };
+    Ok(ContextSwitches {
+      voluntary: Some(voluntary),
+      involuntary: Some(involuntary),
+      total: voluntary + involuntary,
+      threads,
+    })
+  }
+  #[cfg(windows)]
+  {
+    let threads = windows_thread_context_switches()
+      .map_err(|err| sys::priority_error(err, "get the context switches"))?;
+    Ok(ContextSwitches {
+      voluntary: None,
+      involuntary: None,
+      total: threads.iter().map(|thread| thread.total).sum(),
+      threads: with_threads.then_some(threads),
+    })
+  }
+}
+
+#[napi(object)]
+pub struct SampleThreadCpuOptions {
+  /// How long to measure in milliseconds, it defaults to `1000`.
+  pub duration_ms: Option<u32>,
+  /// The process whose threads are measured, it defaults to the current process. Only the current process is supported on macOS.
+  pub pid: Option<u32>,
+}
+
+#[napi(object, use_nullable = true)]
+pub struct ThreadCpuSample {
+  /// The native id of the thread, see `getNativeThreadId`.
+  pub tid: i64,
+  pub name: Option<String>,
+  /// The CPU time consumed by the thread during the window in microseconds.
+  pub cpu_time: f64,
+  /// The share of one CPU used by the thread during the window, from `0` to `100`.
+  pub cpu_percent: f64,
+}
+
+/// Measures the CPU time consumed by each thread of `pid` over `duration`, blocking the calling thread.
+fn sample_threads(pid: Option<u32>, duration: std::time::Duration) -> Result<Vec<ThreadCpuSample>> {
+  let before = crate::thread::list_threads(pid)?;
+  let start = std::time::Instant::now();
+  std::thread::sleep(duration);
+  let after = crate::thread::list_threads(pid)?;
+  let elapsed = start.elapsed().as_secs_f64() * 1e6;
+  // threads which started or exited during the window are skipped, and so is the sampling thread
+  let sampler = crate::thread::get_native_thread_id().ok();
+  let mut samples = after
+    .into_iter()
+    .filter_map(|thread| {
+      if Some(thread.tid) == sampler {
+        return None;
+      }
+      let previous = before.iter().find(|previous| previous.tid == thread.tid)?;
+      let cpu_time = (thread.cpu_time? - previous.cpu_time?).max(0.0);
+      Some(ThreadCpuSample {
+        tid: thread.tid,
+        name: thread.name,
+        cpu_time,
+        cpu_percent: if elapsed > 0.0 {
+          (cpu_time / elapsed * 100.0).min(100.0)
+        } else {
+          0.0
+        },
+      })
+    })
+    .collect::<Vec<_>>();
+  samples.sort_by(|a, b| b.cpu_time.total_cmp(&a.cpu_time));
+  Ok(samples)
+}
+
+#[napi(ts_return_type = "Promise<Array<ThreadCpuSample>>")]
+/// This function measures the CPU time consumed by each thread of a process over `durationMs`, and resolves with the threads sorted from the busiest, so users can find the right thread to renice or pin.
+/// The measurement runs on a dedicated thread which is left out of the results, so neither the JavaScript thread nor the libuv thread pool is blocked, and both are measured as well.
+///
+/// It uses `listThreads` at the start and the end of the window, and only supports the same platforms, other platforms reject. The precision is limited by the clock tick on Linux, usually 10 milliseconds.
+pub fn sample_thread_cpu<'env>(
+  env: &'env Env,
+  options: Option<SampleThreadCpuOptions>,
+) -> Result<Object<'env>> {
+  let (duration_ms, pid) = options
+    .map(|options| (options.duration_ms, options.pid))
+    .unwrap_or_default();
+  let duration = std::time::Duration::from_millis(duration_ms.unwrap_or(1000).into());
+  let (start, started) = std::sync::mpsc::channel::<JsDeferred<_, _>>();
+  // the thread is started first, a deferred which is never settled would keep the event loop alive
+  std::thread::Builder::new()
+    .name("nice-sampler".to_owned())
+    .spawn(move || {
+      if let Ok(deferred) = started.recv() {
+        let samples = sample_threads(pid, duration);
+        deferred.resolve(move |_| samples);
+      }
+    })?;
+  let (deferred, promise) = env.create_deferred()?;
+  let _ = start.send(deferred);
+  Ok(promise)
+}
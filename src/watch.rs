@@ -0,0 +1,100 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::Thread;
+use std::time::{Duration, Instant};
+
+use napi::bindgen_prelude::{Function, ToNapiValue};
+use napi::threadsafe_function::ThreadsafeFunctionCallMode;
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+#[napi(object)]
+pub struct WatchOptions {
+  /// How often the value is checked in milliseconds, it defaults to `1000` and must be at least `1`.
+  pub interval: Option<u32>,
+}
+
+impl WatchOptions {
+  pub(crate) fn interval(options: Option<Self>) -> Result<Duration> {
+    interval_from_millis(options.and_then(|options| options.interval))
+  }
+}
+
+/// Validates a polling interval in milliseconds, `0` would make the background thread spin.
+pub(crate) fn interval_from_millis(interval: Option<u32>) -> Result<Duration> {
+  match interval.unwrap_or(1000) {
+    0 => Err(Error::new(
+      Status::InvalidArg,
+      "0 is not a valid interval, expected at least 1 millisecond".to_owned(),
+    )),
+    interval => Ok(Duration::from_millis(interval.into())),
+  }
+}
+
+#[napi]
+/// Calls a callback from a background thread when a watched value changes, until `stop()` is called.
+/// It doesn't keep the process alive, and it keeps watching even when it's garbage collected, so it must be stopped when it's no longer needed.
+///
+/// It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+pub struct Watcher {
+  /// The flag checked by the background thread, and the thread to wake up once it's set.
+  /// Dropping it doesn't stop the thread, only `stop()` and the exit of the JavaScript environment do.
+  stop: Option<(Arc<AtomicBool>, Thread)>,
+}
+
+#[napi]
+impl Watcher {
+  #[napi]
+  /// Stops watching, calling it more than once has no effect.
+  pub fn stop(&mut self) {
+    if let Some((stopped, thread)) = self.stop.take() {
+      stopped.store(true, Ordering::Relaxed);
+      thread.unpark();
+    }
+  }
+
+  #[napi(getter)]
+  /// Whether `stop()` has already been called.
+  pub fn stopped(&self) -> bool {
+    self.stop.is_none()
+  }
+}
+
+/// Calls `poll` every `interval` on a background thread, and `callback` with each value it returns.
+pub(crate) fn spawn_watcher<T: ToNapiValue + Send + 'static>(
+  callback: Function<T, ()>,
+  interval: Duration,
+  mut poll: impl FnMut() -> Option<T> + Send + 'static,
+) -> Result<Watcher> {
+  let callback = callback
+    .build_threadsafe_function()
+    .callee_handled::<false>()
+    .weak::<true>()
+    .build()?;
+  let stopped = Arc::new(AtomicBool::new(false));
+  let thread = std::thread::Builder::new()
+    .name("nice-watcher".to_owned())
+    .spawn({
+      let stopped = stopped.clone();
+      move || loop {
+        let deadline = Instant::now() + interval;
+        // `park_timeout` can wake up spuriously
+        while let Some(timeout) = deadline
+          .checked_duration_since(Instant::now())
+          .filter(|_| !stopped.load(Ordering::Relaxed))
+        {
+          std::thread::park_timeout(timeout);
+        }
+        // the threadsafe function is aborted when the JavaScript environment exits
+        if stopped.load(Ordering::Relaxed) || callback.aborted() {
+          break;
+        }
+        if let Some(value) = poll() {
+          callback.call(value, ThreadsafeFunctionCallMode::NonBlocking);
+        }
+      }
+    })?;
+  Ok(Watcher {
+    stop: Some((stopped, thread.thread().clone())),
+  })
+}
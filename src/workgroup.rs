@@ -0,0 +1,105 @@
+use napi::{JsExternal, Result};
+use napi_derive::napi;
+
+use crate::sys;
+
+#[cfg(target_vendor = "apple")]
+/// `os_workgroup_join_token_s`, a `u32` signature followed by 36 opaque bytes on 64-bit platforms, with some headroom.
+type JoinToken = [u64; 8];
+
+#[cfg(target_vendor = "apple")]
+extern "C" {
+  fn os_workgroup_join(workgroup: *mut libc::c_void, token: *mut JoinToken) -> libc::c_int;
+  fn os_workgroup_leave(workgroup: *mut libc::c_void, token: *mut JoinToken);
+  fn os_retain(object: *mut libc::c_void) -> *mut libc::c_void;
+  fn os_release(object: *mut libc::c_void);
+}
+
+#[napi]
+/// Joins the calling thread to an `os_workgroup`, such as the workgroup of an audio device, so threads created in Node.js are scheduled with the deadline of the workgroup on Apple silicon.
+/// The membership lasts until `leave()` is called or the object is garbage collected, and it must be created on the thread to join, such as a worker thread.
+///
+/// It implements `Symbol.dispose` and `Symbol.asyncDispose`, so it works with `using` declarations.
+///
+/// On macOS, it uses the [`os_workgroup_join` and `os_workgroup_leave`](https://github.com/apple-oss-distributions/libdispatch/blob/main/os/workgroup_object.h) functions.
+///
+/// On other platforms, the constructor throws.
+pub struct WorkgroupJoin {
+  /// The retained `os_workgroup_t` and the join token, `None` once left.
+  #[cfg(target_vendor = "apple")]
+  joined: Option<(usize, Box<JoinToken>)>,
+  #[cfg(not(target_vendor = "apple"))]
+  joined: Option<()>,
+}
+
+#[napi]
+impl WorkgroupJoin {
+  #[napi(constructor)]
+  /// `workgroup` is an external wrapping an `os_workgroup_t`, for example the `kAudioDevicePropertyIOThreadOSWorkgroup` of an audio device exposed by a CoreAudio addon.
+  /// The workgroup is retained while the thread is a member.
+  pub fn new(workgroup: JsExternal) -> Result<Self> {
+    crate::stats::record("WorkgroupJoin", || {
+      use napi::JsValue;
+
+      let value = workgroup.value();
+      let mut pointer = std::ptr::null_mut();
+      napi::check_status!(
+        unsafe { napi::sys::napi_get_value_external(value.env, value.value, &mut pointer) },
+        "The workgroup is not an external"
+      )?;
+      if pointer.is_null() {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          "The workgroup external is empty",
+        ));
+      }
+      #[cfg(target_vendor = "apple")]
+      {
+        let mut token = Box::new([0; 8]);
+        let ret = unsafe { os_workgroup_join(pointer, &mut *token) };
+        if ret != 0 {
+          // `EINVAL` when the workgroup is canceled, `EALREADY` when the thread is already in a workgroup
+          return Err(sys::priority_error(
+            std::io::Error::from_raw_os_error(ret),
+            "join the workgroup",
+          ));
+        }
+        unsafe { os_retain(pointer) };
+        Ok(Self {
+          joined: Some((pointer as usize, token)),
+        })
+      }
+      #[cfg(not(target_vendor = "apple"))]
+      {
+        Err(sys::unsupported("Joining a workgroup"))
+      }
+    })
+  }
+
+  #[napi]
+  /// Leaves the workgroup, calling it more than once has no effect.
+  pub fn leave(&mut self) {
+    #[cfg(target_vendor = "apple")]
+    if let Some((workgroup, mut token)) = self.joined.take() {
+      let workgroup = workgroup as *mut libc::c_void;
+      unsafe {
+        os_workgroup_leave(workgroup, &mut *token);
+        os_release(workgroup);
+      }
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    self.joined.take();
+  }
+
+  #[napi(getter)]
+  /// Whether `leave()` has already been called.
+  pub fn left(&self) -> bool {
+    self.joined.is_none()
+  }
+}
+
+impl Drop for WorkgroupJoin {
+  fn drop(&mut self) {
+    self.leave();
+  }
+}